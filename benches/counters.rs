@@ -0,0 +1,27 @@
+use counting_sort::CountingSort;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use oorandom::Rand32;
+
+fn create_vector(rng: &mut Rand32, length: usize, distance: u32) -> Vec<u32> {
+    (0..length).map(|_| rng.rand_range(0..distance)).collect()
+}
+
+fn counter_types(c: &mut Criterion) {
+    let mut group = c.benchmark_group("counters");
+    let mut rng = Rand32::new(4321);
+    for distance in [256, 4096, 65536, 1 << 20].iter() {
+        let vector = create_vector(&mut rng, 100_000, *distance);
+        group.bench_with_input(BenchmarkId::new("usize", distance), &vector, |b, v| {
+            b.iter(|| black_box(v.iter().cnt_sort()))
+        });
+        group.bench_with_input(BenchmarkId::new("auto", distance), &vector, |b, v| {
+            b.iter(|| black_box(v.iter().cnt_sort_auto_counter()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, counter_types);
+criterion_main!(benches);
@@ -0,0 +1,46 @@
+use counting_sort::{reconstruct_sorted_desc, TryFromIndex};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use oorandom::Rand32;
+
+use core::convert::TryFrom;
+
+fn create_counts(rng: &mut Rand32, length: usize) -> Vec<usize> {
+    let mut counts = vec![0; usize::from(u16::MAX) + 1];
+    for _ in 0..length {
+        let value = u16::try_from(rng.rand_range(0..65536)).unwrap();
+        counts[usize::from(value)] += 1;
+    }
+    counts
+}
+
+// reconstructs from the largest value down, i.e. reads the counts backward
+fn reconstruct_backward(counts: &[usize], min_value: &u16) -> Vec<u16> {
+    let length = counts.iter().sum();
+    let mut sorted_vector = Vec::with_capacity(length);
+    for (index, count) in counts.iter().enumerate().rev() {
+        if *count > 0 {
+            let value = u16::try_from_index(index, min_value).unwrap();
+            sorted_vector.resize(sorted_vector.len() + count, value);
+        }
+    }
+    sorted_vector
+}
+
+fn descending_reconstruction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("descending_reconstruction");
+    group.sample_size(20);
+    let mut rng = Rand32::new(1357);
+    let counts = create_counts(&mut rng, 1_000_000);
+    group.bench_function("backward", |b| {
+        b.iter(|| black_box(reconstruct_backward(&counts, &0)))
+    });
+    group.bench_function("reversed_forward", |b| {
+        b.iter(|| black_box(reconstruct_sorted_desc(&counts, &0)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, descending_reconstruction);
+criterion_main!(benches);
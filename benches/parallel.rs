@@ -0,0 +1,31 @@
+use counting_sort::{par_cnt_sort_unstable, CountingSort};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use oorandom::Rand32;
+
+fn create_vector(length: usize) -> Vec<u16> {
+    let mut rng = Rand32::new(5678);
+    (0..length)
+        .map(|_| rng.rand_range(0..u32::from(u16::MAX)) as u16)
+        .collect()
+}
+
+fn parallel_u16_50m(c: &mut Criterion) {
+    let vector = create_vector(50_000_000);
+    let mut group = c.benchmark_group("u16_50m");
+    group.sample_size(10);
+    group.bench_function("cnt_sort", |b| {
+        b.iter(|| black_box(vector.iter().cnt_sort()))
+    });
+    group.bench_function("par_cnt_sort_unstable", |b| {
+        b.iter(|| black_box(par_cnt_sort_unstable(&vector)))
+    });
+    group.bench_function("cnt_sort_parallel_merge", |b| {
+        b.iter(|| black_box(vector.iter().cnt_sort_parallel_merge()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parallel_u16_50m);
+criterion_main!(benches);
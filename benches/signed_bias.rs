@@ -0,0 +1,33 @@
+use counting_sort::{cnt_sort_i32_biased, CountingSort};
+
+use core::convert::TryFrom;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use oorandom::Rand32;
+
+fn create_vector(rng: &mut Rand32, length: usize, distance: u32) -> Vec<i32> {
+    let offset = i32::try_from(distance / 2).unwrap();
+    (0..length)
+        .map(|_| i32::try_from(rng.rand_range(0..distance)).unwrap() - offset)
+        .collect()
+}
+
+fn signed_paths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("signed_bias");
+    group.sample_size(20);
+    let mut rng = Rand32::new(2468);
+    for distance in [1000, 100_000].iter() {
+        let vector = create_vector(&mut rng, 1_000_000, *distance);
+        group.bench_with_input(BenchmarkId::new("widening", distance), &vector, |b, v| {
+            b.iter(|| black_box(v.iter().cnt_sort()))
+        });
+        group.bench_with_input(BenchmarkId::new("biased", distance), &vector, |b, v| {
+            b.iter(|| black_box(cnt_sort_i32_biased(v.iter())))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, signed_paths);
+criterion_main!(benches);
@@ -0,0 +1,31 @@
+use counting_sort::CountingSort;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use oorandom::Rand32;
+
+fn create_vector(rng: &mut Rand32, length: usize) -> Vec<u16> {
+    (0..length)
+        .map(|_| rng.rand_range(0..1000) as u16)
+        .collect()
+}
+
+fn insertion_sort_crossover(c: &mut Criterion) {
+    let mut group = c.benchmark_group("small_inputs");
+    let mut rng = Rand32::new(1234);
+    for length in [4, 8, 12, 16, 24, 32, 64].iter() {
+        let vector = create_vector(&mut rng, *length);
+        group.bench_with_input(BenchmarkId::new("cnt_sort", length), &vector, |b, v| {
+            b.iter(|| black_box(v.iter().cnt_sort()))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("cnt_sort_adaptive", length),
+            &vector,
+            |b, v| b.iter(|| black_box(v.iter().cnt_sort_adaptive())),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, insertion_sort_crossover);
+criterion_main!(benches);
@@ -17,6 +17,9 @@
 //! * [`i16`](std::i16)
 //! * [`i32`](std::i32)
 //!
+//! Additionally it is implemented for [`bool`](std::bool), [`char`](std::char) and, with the
+//! `ordered-float` feature, for `OrderedFloat<f32>`.
+//!
 //! This means for all [`Vec`](std::vec::Vec)s,
 //! [`LinkedList`](std::collections::LinkedList)s,
 //! [`slice`](std::slice)s or any other
@@ -64,6 +67,8 @@
 //! * counting sort for e.g. [`HashSet`](std::collections::HashSet)'s is sub-optimal since every element exists only
 //!   once in a [`HashSet`](std::collections::HashSet). Counting sort excels when a lot of elements exist in the
 //!   collection but the number of distinct elements is small.
+//! * On targets with a 16-bit [`usize`](std::usize) the count vector can hold at most 65535 elements, sorting
+//!   the complete [`u16`](std::u16) range or more than 65535 elements returns an error instead of overflowing
 //! * **<span style="color:red">Caution:</span>** Be careful using this algorithm when the range between minumum value and maximum value is large
 //! * An excellent illustration about the counting sort algorithm can be found [here](https://www.cs.usfca.edu/~galles/visualization/CountingSort.html)
 //! * Wikipedia article on [counting sort](https://en.wikipedia.org/wiki/Counting_sort)
@@ -74,15 +79,29 @@
 #![deny(clippy::pedantic)]
 
 use core::cmp::{max, min, Ord};
-use core::convert::TryInto;
+use core::convert::{TryFrom, TryInto};
 use core::fmt;
 use core::fmt::Display;
+use core::iter::Peekable;
+use core::ops::Range;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::error::Error;
+use std::io::{BufRead, Read, Seek, SeekFrom};
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::SyncSender;
 
 /// This enumeration is a list of all possible errors that can happen during
 /// [`cnt_sort`](CountingSort::cnt_sort()) or
 /// [`cnt_sort_min_max`](CountingSort::cnt_sort_min_max()).
+///
+/// The error is guaranteed to be [`Send`](std::marker::Send) and [`Sync`](std::marker::Sync), so
+/// it can be passed across threads, e.g. as `Box<dyn Error + Send + Sync>`.
+///
+/// New variants may be added in minor releases, hence matching on the error requires a wildcard
+/// arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum CountingSortError {
     /// The conversion from a value of the to-be-sorted type `T` into an
     /// index ([`usize`](std::usize)) failed.
@@ -100,6 +119,51 @@ pub enum CountingSortError {
     /// when the given maximum value is smaller than the actual maximum value when
     /// [`cnt_sort_min_max`](CountingSort::cnt_sort_min_max()) is used.
     IndexOutOfBounds(&'static str),
+    /// Parsing the input into integers failed, either because an element is not a valid integer
+    /// or because reading the input failed.
+    ParseFailed(&'static str),
+    /// The receiving half of the channel was disconnected before all sorted elements were sent.
+    ChannelDisconnected(&'static str),
+    /// The trim fraction is not within `[0, 0.5)`, see
+    /// [`trimmed_mean`](CountingSort::trimmed_mean()).
+    InvalidTrimFraction(&'static str),
+    /// The length of a given slice does not match the number of elements to be sorted.
+    LengthMismatch(&'static str),
+    /// The value to be removed or replaced is not present, see
+    /// [`IncrementalSort::update`](IncrementalSort::update()).
+    ValueNotPresent(&'static str),
+    /// A row is too short to contain the column to sort by, see
+    /// [`cnt_sort_rows_by_column`](cnt_sort_rows_by_column()).
+    ColumnOutOfBounds(&'static str),
+    /// A NaN was encountered although the [`NanPolicy`] is [`NanPolicy::Error`].
+    NanEncountered(&'static str),
+    /// The conversion from an index ([`usize`](std::usize)) back into a value of the type `T`
+    /// failed, most likely because the value is out of the range of `T`.
+    FromIndexFailed(&'static str),
+    /// The [`Iterator`](std::iter::Iterator) yielded a different number of elements when it was
+    /// iterated again, i.e. it is not replayable.
+    NonDeterministicSource(&'static str),
+    /// Two elements which are not equal were converted into the same index, i.e. the
+    /// [`TryIntoIndex`](crate::TryIntoIndex) implementation is lossy. Only checked by
+    /// [`cnt_sort_with_collision_check`](crate::CountingSort::cnt_sort_with_collision_check()).
+    IndexCollision(&'static str),
+    /// An internal invariant of the counting sort algorithm does not hold, e.g. the sum of all
+    /// counts, i.e. the number of elements, overflows.
+    ContractViolation(&'static str),
+    /// A value occurred more than once although the elements are expected to be distinct, see
+    /// [`cnt_sort_distinct`](CountingSort::cnt_sort_distinct()).
+    DuplicateFound(&'static str),
+    /// Sorting was aborted because the cancel flag was set, see
+    /// [`cnt_sort_cancellable`](CountingSort::cnt_sort_cancellable()).
+    Cancelled(&'static str),
+    /// Reading the input failed, see [`cnt_sort_read`](cnt_sort_read()).
+    Io(&'static str),
+    /// The elements are not a permutation of a contiguous range of values, see
+    /// [`cnt_sort_dense`](CountingSort::cnt_sort_dense()).
+    NotAPermutation(&'static str),
+    /// The distance between the minimum and the maximum value exceeds the configured maximum
+    /// distance, see [`SortStrategy`](crate::SortStrategy).
+    DistanceTooLarge(&'static str),
 }
 
 impl Display for CountingSortError {
@@ -109,13 +173,36 @@ impl Display for CountingSortError {
             | CountingSortError::IteratorEmpty(description)
             | CountingSortError::SortingUnnecessary(description)
             | CountingSortError::MinValueLargerMaxValue(description)
-            | CountingSortError::IndexOutOfBounds(description) => description.fmt(f),
+            | CountingSortError::IndexOutOfBounds(description)
+            | CountingSortError::ParseFailed(description)
+            | CountingSortError::ChannelDisconnected(description)
+            | CountingSortError::InvalidTrimFraction(description)
+            | CountingSortError::LengthMismatch(description)
+            | CountingSortError::ValueNotPresent(description)
+            | CountingSortError::ColumnOutOfBounds(description)
+            | CountingSortError::NanEncountered(description)
+            | CountingSortError::FromIndexFailed(description)
+            | CountingSortError::NonDeterministicSource(description)
+            | CountingSortError::IndexCollision(description)
+            | CountingSortError::ContractViolation(description)
+            | CountingSortError::DuplicateFound(description)
+            | CountingSortError::Cancelled(description)
+            | CountingSortError::Io(description)
+            | CountingSortError::NotAPermutation(description)
+            | CountingSortError::DistanceTooLarge(description) => description.fmt(f),
         }
     }
 }
 
 impl Error for CountingSortError {}
 
+// Compile-time assertion that CountingSortError stays Send and Sync. Any payload added in the
+// future, e.g. an error source, must preserve this, i.e. be a `Box<dyn Error + Send + Sync>`.
+const _: fn() = || {
+    fn assert_send_sync<E: Send + Sync>() {}
+    assert_send_sync::<CountingSortError>();
+};
+
 impl CountingSortError {
     /// Create `IntoIndexFailed` error when conversion to index failed.
     fn from_try_into_index_failed() -> CountingSortError {
@@ -145,8 +232,119 @@ impl CountingSortError {
             "Index is out of bounds, most likely the given maximum value is too small",
         )
     }
+
+    /// Create `ParseFailed` when parsing the input into an integer failed.
+    fn from_parse_failed() -> CountingSortError {
+        CountingSortError::ParseFailed("Parsing the input into an integer failed")
+    }
+
+    /// Create `ChannelDisconnected` when sending into a channel failed.
+    fn from_channel_disconnected() -> CountingSortError {
+        CountingSortError::ChannelDisconnected("The receiver of the channel is disconnected")
+    }
+
+    /// Create `InvalidTrimFraction` when the trim fraction is not within `[0, 0.5)`.
+    fn from_invalid_trim_fraction() -> CountingSortError {
+        CountingSortError::InvalidTrimFraction("Trim fraction must be within [0, 0.5)")
+    }
+
+    /// Create `LengthMismatch` when a given slice has the wrong length.
+    fn from_length_mismatch() -> CountingSortError {
+        CountingSortError::LengthMismatch(
+            "Length of the slice does not match the number of elements",
+        )
+    }
+
+    /// Create `ValueNotPresent` when a value to be replaced has a count of zero.
+    fn from_value_not_present() -> CountingSortError {
+        CountingSortError::ValueNotPresent("The value to be replaced is not present")
+    }
+
+    /// Create `ColumnOutOfBounds` when a row does not contain the column to sort by.
+    fn from_column_out_of_bounds() -> CountingSortError {
+        CountingSortError::ColumnOutOfBounds("A row does not contain the column to sort by")
+    }
+
+    /// Create `NanEncountered` when a NaN is not allowed.
+    fn from_nan_encountered() -> CountingSortError {
+        CountingSortError::NanEncountered("A NaN was encountered")
+    }
+
+    /// Create `FromIndexFailed` error when conversion from an index failed.
+    fn from_try_from_index_failed() -> CountingSortError {
+        CountingSortError::FromIndexFailed("Conversion from index failed")
+    }
+
+    /// Create `NonDeterministicSource` when re-iterating yields different elements.
+    fn from_non_deterministic_source() -> CountingSortError {
+        CountingSortError::NonDeterministicSource(
+            "Iterator yielded different elements when iterated again",
+        )
+    }
+
+    /// Create `IndexCollision` when two unequal elements share an index.
+    fn from_index_collision() -> CountingSortError {
+        CountingSortError::IndexCollision("Unequal elements were converted into the same index")
+    }
+
+    /// Create `ContractViolation` when an internal invariant does not hold.
+    fn from_contract_violation() -> CountingSortError {
+        CountingSortError::ContractViolation(
+            "Internal invariant of the counting sort algorithm violated",
+        )
+    }
+
+    /// Create `DuplicateFound` when a value occurs more than once in a distinct sort.
+    fn from_duplicate_found() -> CountingSortError {
+        CountingSortError::DuplicateFound(
+            "Value occurred more than once, the elements are not distinct",
+        )
+    }
+
+    /// Create `Cancelled` when the cancel flag was set during sorting.
+    fn from_cancelled() -> CountingSortError {
+        CountingSortError::Cancelled("Sorting was cancelled")
+    }
+
+    /// Create `Io` when reading the input fails.
+    fn from_io() -> CountingSortError {
+        CountingSortError::Io("Reading the input failed")
+    }
+
+    /// Create `NotAPermutation` when the elements are not a permutation of a contiguous range.
+    fn from_not_a_permutation() -> CountingSortError {
+        CountingSortError::NotAPermutation(
+            "Elements are not a permutation of the range between the minimum and maximum value",
+        )
+    }
+
+    /// Create `DistanceTooLarge` when the distance exceeds the maximum distance of a strategy.
+    fn from_distance_too_large() -> CountingSortError {
+        CountingSortError::DistanceTooLarge(
+            "The distance between minimum and maximum value exceeds the maximum distance",
+        )
+    }
 }
 
+/// The number of elements below which
+/// [`cnt_sort_adaptive`](CountingSort::cnt_sort_adaptive()) uses an insertion sort instead of
+/// counting sort.
+///
+/// The value was determined with the `small_inputs` benchmark, where for [`u16`](std::u16) values
+/// between 0 and 1000 the insertion sort is faster until roughly 40 elements. The smaller the
+/// distance `d` the earlier counting sort pays off, hence the threshold is chosen conservatively.
+pub const INSERTION_SORT_THRESHOLD: usize = 32;
+
+/// The minimum distance `d` from which
+/// [`cnt_sort_auto_counter`](CountingSort::cnt_sort_auto_counter()) uses [`u32`](std::u32) instead
+/// of [`usize`](std::usize) counters, as long as the number of elements fits into an
+/// [`u32`](std::u32).
+///
+/// The value was determined with the `counters` benchmark: for small distances the count values
+/// vector fits into the cache anyway and both counters are equally fast, for larger distances the
+/// smaller count values vector of [`u32`](std::u32) counters is faster and needs half the memory.
+pub const COMPACT_COUNTER_MIN_DISTANCE: usize = 4096;
+
 /// The interface for counting sort algorithm.
 ///
 /// Interface provides blanket implementation of all collections that implement
@@ -170,6 +368,17 @@ impl CountingSortError {
 /// as [`Copy`](std::marker::Copy), since the elements are copied
 /// during the count phase as well as the re-order phase. Finally the type must implement the in this
 /// crate defined [`TryIntoIndex`] trait.
+///
+/// **Note:** The iterator is cloned and iterated several times, hence it must be replayable, i.e.
+/// each clone must yield exactly the same elements. This holds for the iterators of all collections,
+/// but not for e.g. an iterator backed by a random number generator or a file cursor. If the
+/// re-order phase sees a different number of elements than the count phase,
+/// [`CountingSortError::NonDeterministicSource`] is returned.
+///
+/// All methods returning the sorted elements as [`Vec`](std::vec::Vec) guarantee that its capacity
+/// equals its length, i.e. no memory is wasted when storing many sorted vectors. Only
+/// [`cnt_sort_merge_into`](CountingSort::cnt_sort_merge_into()) extends a given
+/// [`Vec`](std::vec::Vec) and therefore may leave spare capacity.
 pub trait CountingSort<'a, T>
 where
     T: Ord + Copy + TryIntoIndex + 'a,
@@ -215,7 +424,7 @@ where
     ///   is empty (and there is nothing to sort)
     /// * [`CountingSortError::SortingUnnecessary`]] when
     ///   the minimum value is equal to the maximum value, this means all values are essentially equal and no sorting
-    ///   is necessary
+    ///   is necessary, a single element however is returned as is
     fn cnt_sort(self) -> Result<Vec<T>, CountingSortError> {
         counting_sort(self)
     }
@@ -278,686 +487,10691 @@ where
     fn cnt_sort_min_max(self, min_value: &T, max_value: &T) -> Result<Vec<T>, CountingSortError> {
         counting_sort_min_max(self, min_value, max_value)
     }
-}
-
-// Counting sort implementation for ITER with trait bound Iterator.
-// This enables that CountingSort is implemented for all implementors of
-// Iterator, especially for Vec, LinkedList and slice.
-impl<'a, T, ITER> CountingSort<'a, T> for ITER
-where
-    T: Ord + Copy + TryIntoIndex + 'a,
-    ITER: Sized + Iterator<Item = &'a T> + Clone,
-{
-}
-
-/// The interface for converting values into an index.
-///
-/// Index is always [`usize`](std::usize). Unfortunatelly
-/// [`TryInto`](std::convert::TryInto) for
-/// [`usize`](std::usize) is not sufficient since signed
-/// integers overflow when calculating `max_value - min_value`. Therefore this trait was added to
-/// implement an non-overflowing conversion to [`usize`](std::usize).
-///
-/// You can implement this trait yourself as long as there is a natural conversion from your type to
-/// [`usize`](std::usize). However it must hold for your type that if
-/// `t_1 <= t_2` then `YourType::try_into_index(t_1, min_value)? <= YourType::try_into_index(t_2, min_value)?`.
-/// Also consider that the size [`Vec`](std::vec::Vec) that holds the
-/// frequency of all elements in the collection is calculated like this
-///
-/// ```rust,compile_fail
-/// let length = YourType::try_into_index(max_value,min_value)? + 1;
-/// ```
-///
-/// It is not highly recommended to do this if your type's order is not simply dependent on one integer value
-/// of your struct.
-///
-/// # Example
-///
-/// ```rust
-/// use core::cmp::{Ord, Ordering};
-/// use counting_sort::TryIntoIndex;
-///
-/// #[derive(Copy, Clone)]
-/// struct Person {
-///     name: &'static str,
-///     id: usize
-/// }
-///
-/// impl Ord for Person {
-///     fn cmp(&self, other: &Self) -> Ordering {
-///         self.id.cmp(&other.id)
-///     }
-/// }
-///
-/// impl PartialOrd for Person {
-///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-///         Some(self.cmp(other))
-///     }
-/// }
-///
-/// impl PartialEq for Person {
-///     fn eq(&self, other: &Self) -> bool {
-///         self.id == other.id
-///     }
-/// }
-///
-/// impl Eq for Person {}
-///
-/// impl TryIntoIndex for Person {
-///     type Error = &'static str;
-///     
-///     fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
-///         Ok(value.id - min_value.id)
-///     }
-/// }
-///
-/// let john = Person { name: "John", id: 1234 };
-/// let min_value = Person { name: "Jim", id: 234 };
-/// let index_result = Person::try_into_index(&john, &min_value);
-/// assert!(index_result.is_ok());
-/// assert_eq!(1000, index_result.unwrap_or(0));
-/// ```
-pub trait TryIntoIndex {
-    /// The type returned whenever the conversion into an index failed.
-    type Error;
 
-    /// Tries to convert the value into an index.
+    /// Sorts the elements in the
+    /// [`Iterator`](std::iter::Iterator)
+    /// with the counting sort algorithm and initializes the sorted [`Vec`](std::vec::Vec) with `fill`.
     ///
-    /// The `min_value` parameter is for calculating the offset between the actual value
-    /// and the minimum value. This concept is used in order to only allocate a
-    /// [`Vec`](std::vec::Vec) that only covers the
-    /// distance between the maximum value and the minimum value of the collection.
+    /// Before re-ordering, the sorted [`Vec`](std::vec::Vec) is allocated and initialized with copies of a
+    /// throwaway value, by default the minimum value. Since every slot is overwritten during the re-order
+    /// phase, any valid value of `T` can be used. If copying the minimum value is costly but a cheap value
+    /// of `T` exists, this value can be given as `fill`. `fill` is never present in the returned
+    /// [`Vec`](std::vec::Vec), unless it is an element of the collection itself.
+    ///
+    /// Otherwise this method behaves like [`cnt_sort`](CountingSort::cnt_sort()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![20, 13, 17, 11];
+    /// let sorted_vec_result = vec.iter().cnt_sort_with_fill(0);
+    ///
+    /// assert_eq!(vec![11, 13, 17, 20], sorted_vec_result.unwrap());
+    /// ```
     ///
     /// # Errors
     ///
-    /// Shall return an `Error` when converting value into an index (`usize`) fails.
-    fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error>;
-}
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+    fn cnt_sort_with_fill(self, fill: T) -> Result<Vec<T>, CountingSortError> {
+        counting_sort_with_fill(self, fill)
+    }
 
-// Macro is needed to implement TryIntoIndex for signed integers, which can overflow during
-// index conversion.
-macro_rules! try_into_index_impl_for_signed {
-    ($smaller_int:ty,$larger_int:ty) => {
-        impl TryIntoIndex for $smaller_int {
-            type Error = <$larger_int as TryInto<usize>>::Error;
+    /// Returns the rank of the first occurrence of `target` in the sorted order of the
+    /// [`Iterator`](std::iter::Iterator), or [`None`](std::option::Option::None) if `target`
+    /// is not an element of the collection.
+    ///
+    /// The rank is the position `target` would have in the [`Vec`](std::vec::Vec) returned by
+    /// [`cnt_sort`](CountingSort::cnt_sort()), i.e. the number of elements that are smaller than
+    /// `target`. It is computed from the cumulative frequencies of the count values vector, the sorted
+    /// [`Vec`](std::vec::Vec) is never materialized. Therefore runtime is `O(n + d)` but memory usage
+    /// is only `O(d)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![13, 2, 7, 2, 9];
+    ///
+    /// assert_eq!(Some(2), vec.iter().cnt_sort_rank_of(&7).unwrap());
+    /// // 8 is within the range of the elements, but not an element
+    /// assert_eq!(None, vec.iter().cnt_sort_rank_of(&8).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty (and there is nothing to rank)
+    fn cnt_sort_rank_of(self, target: &T) -> Result<Option<usize>, CountingSortError> {
+        counting_sort_rank_of(self, target)
+    }
 
-            fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
-                // This conversion can only fail, if the larger integer type has a larger maximum
-                // value then usize. To-be-converted value is always be >= 0 as long as min_value <=
-                // value.
-                <$larger_int>::try_into(
-                    // convert smaller signed integer into larger signed integer to
-                    // avoid integer overflow for the smaller integer.
-                    // Example: 127 - (-128) overflows in i8 since 255 > 127 = i8::max_value().
-                    // It is safe to convert a smaller integer into a larger integer.
-                    <$larger_int>::from(*value) - <$larger_int>::from(*min_value),
-                )
-            }
-        }
-    };
-}
+    /// Sorts the elements in the
+    /// [`Iterator`](std::iter::Iterator)
+    /// with the counting sort algorithm and returns the sorted elements as a run-length-encoded
+    /// byte stream.
+    ///
+    /// This method is intended for [`u8`](std::u8) elements, but works for all types that implement
+    /// [`Into<u8>`](std::convert::Into), in which case the elements are sorted by their
+    /// [`u8`](std::u8) representation. The frequency of every byte is counted in a fixed array of
+    /// 256 elements and the encoded stream is emitted directly from this array, i.e. the sorted
+    /// elements are never materialized.
+    ///
+    /// The encoding is a sequence of 3-byte runs `value, count_lo, count_hi`, where
+    ///
+    /// * `value` is the byte value of the run
+    /// * `count_lo` and `count_hi` are the low and high byte of the run length
+    ///   `count = count_lo + 256 * count_hi`, i.e. a little-endian [`u16`](std::u16)
+    ///
+    /// Runs are emitted in ascending order of `value`, values that do not occur are omitted and every
+    /// run has a length of at least 1. A value that occurs more than 65535 times is split into
+    /// consecutive runs of the same `value`, all but the last one of length 65535.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec: Vec<u8> = vec![7, 2, 7, 7, 2];
+    /// let encoded = vec.iter().cnt_sort_rle_bytes().unwrap();
+    ///
+    /// assert_eq!(vec![2, 2, 0, 7, 3, 0], encoded);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty (and there is nothing to sort)
+    fn cnt_sort_rle_bytes(self) -> Result<Vec<u8>, CountingSortError>
+    where
+        T: Into<u8>,
+    {
+        counting_sort_rle_bytes(self)
+    }
 
-// Macro used for unsigned integer implementations of TryIntoIndex.
-macro_rules! try_into_index_impl_for_unsigned {
-    ($unsigned:ty) => {
-        impl TryIntoIndex for $unsigned {
-            type Error = <$unsigned as TryInto<usize>>::Error;
+    /// Estimates the number of element copies [`cnt_sort`](CountingSort::cnt_sort()) performs for the
+    /// elements of the [`Iterator`](std::iter::Iterator).
+    ///
+    /// The re-order phase copies every element into the sorted [`Vec`](std::vec::Vec), which is
+    /// initialized with copies of a fill value beforehand. The returned estimate is therefore `n + length`,
+    /// i.e. one copy per placement plus one copy per slot of the sentinel fill. Combined with
+    /// [`size_of::<T>()`](core::mem::size_of) this gives an estimate of the copy bandwidth needed,
+    /// which is useful for large types.
+    ///
+    /// This is only a diagnostic, it reuses the minimum and maximum scan and the count phase of
+    /// [`cnt_sort`](CountingSort::cnt_sort()) but does not re-order any element.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![4, 2, 3, 2];
+    ///
+    /// assert_eq!(8, vec.iter().cnt_sort_estimate_copies().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()), i.e. the estimate fails whenever the sort
+    /// itself would fail.
+    fn cnt_sort_estimate_copies(self) -> Result<usize, CountingSortError> {
+        counting_sort_estimate_copies(self)
+    }
 
-            #[inline]
-            fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
-                // Unsigned integer (e.g. u32) could be larger than usize on some HW.
-                <$unsigned>::try_into(*value - *min_value)
-            }
-        }
-    };
-}
+    /// Counts the elements in the [`Iterator`](std::iter::Iterator) and returns the distinct values in
+    /// ascending order together with their frequencies as two parallel [`Vec`](std::vec::Vec)s.
+    ///
+    /// The returned tuple `(distinct_values, counts)` is aligned by index: `distinct_values[i]` occurs
+    /// `counts[i]` times in the collection. This columnar layout is convenient for further numeric
+    /// processing. The frequencies are taken from the count phase of the counting sort algorithm, the
+    /// sorted elements are never materialized.
+    ///
+    /// Equal elements are represented by their first occurrence in the collection.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![3, 1, 3, 2, 3];
+    /// let (distinct_values, counts) = vec.iter().cnt_sort_split_counts().unwrap();
+    ///
+    /// assert_eq!(vec![1, 2, 3], distinct_values);
+    /// assert_eq!(vec![1, 1, 3], counts);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty (and there is nothing to count)
+    fn cnt_sort_split_counts(self) -> Result<(Vec<T>, Vec<usize>), CountingSortError> {
+        let distinct_values_with_counts = count_distinct_values(self)?;
+        Ok(distinct_values_with_counts.into_iter().unzip())
+    }
 
-// Macro used for small unsigned integer implementations of TryIntoIndex.
-macro_rules! try_into_index_impl_for_small_unsigned {
-    ($unsigned:ty) => {
-        impl TryIntoIndex for $unsigned {
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) with the counting sort algorithm
+    /// and partitions them around `pivot`.
+    ///
+    /// Returns the tuple `(less, greater_or_equal)`, where `less` holds all elements smaller than
+    /// `pivot` and `greater_or_equal` all other elements, both in sorted order. The split position is
+    /// the cumulative frequency of the values preceeding the pivot, which is taken from the count values
+    /// vector. Therefore only a single sort is necessary instead of two separate filtered sorts.
+    ///
+    /// `pivot` does not need to be an element of the collection. If it is smaller than the minimum
+    /// value, `less` is empty, if it is larger than the maximum value, `greater_or_equal` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![5, 1, 4, 2, 3];
+    /// let (less, greater_or_equal) = vec.iter().cnt_sort_partition(&3).unwrap();
+    ///
+    /// assert_eq!(vec![1, 2], less);
+    /// assert_eq!(vec![3, 4, 5], greater_or_equal);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty (and there is nothing to partition)
+    fn cnt_sort_partition(self, pivot: &T) -> Result<(Vec<T>, Vec<T>), CountingSortError> {
+        counting_sort_partition(self, pivot)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) with the counting sort algorithm
+    /// and hands the sorted elements to `sink` in chunks of equal values, starting with the largest value.
+    ///
+    /// `sink` is invoked once per distinct value with a contiguous slice holding all elements equal to
+    /// this value, in descending order of the values. Within a chunk the original order of the elements
+    /// is preserved. Concatenating all chunks results in the elements sorted in descending order. The
+    /// chunk boundaries are the cumulative frequencies of the count values vector.
+    ///
+    /// Note that all elements are sorted into a single [`Vec`](std::vec::Vec) before `sink` is invoked
+    /// for the first chunk, i.e. the memory usage is `O(n + d)` like the one of
+    /// [`cnt_sort`](CountingSort::cnt_sort()). Emitting a chunk without this [`Vec`](std::vec::Vec)
+    /// would require another pass over the iterator per distinct value, since equal elements need
+    /// not be identical and must be handed to `sink` in their original order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![2, 3, 1, 3];
+    /// let mut chunks = Vec::new();
+    /// vec.iter().cnt_sort_chunks_desc(|chunk| chunks.push(chunk.to_vec())).unwrap();
+    ///
+    /// assert_eq!(vec![vec![3, 3], vec![2], vec![1]], chunks);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty (and there is nothing to sort)
+    fn cnt_sort_chunks_desc<F>(self, sink: F) -> Result<(), CountingSortError>
+    where
+        F: FnMut(&[T]),
+    {
+        counting_sort_chunks_desc(self, sink)
+    }
+
+    /// Returns the maximum bucket occupancy of the count values vector, given the minimum and maximum
+    /// element of the collection.
+    ///
+    /// The maximum bucket occupancy is the frequency of the most frequent value. Since every element
+    /// of the count values vector is at most this large, callers can use it to choose a narrower integer
+    /// type for storing counts themselves, e.g. [`u16`](std::u16) instead of [`usize`](std::usize).
+    /// An empty iterator has a maximum bucket occupancy of 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![5, 3, 5, 4, 5];
+    ///
+    /// assert_eq!(3, vec.iter().max_bucket_count(&3, &5).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::MinValueLargerMaxValue`] when
+    ///   the given minimum value is larger than the given maximum value
+    /// * [`CountingSortError::IndexOutOfBounds`] when
+    ///   the given maximum value is smaller than the actual maximum value of the collection
+    fn max_bucket_count(self, min_value: &T, max_value: &T) -> Result<usize, CountingSortError> {
+        counting_sort_max_bucket_count(self, min_value, max_value)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) with the counting sort algorithm
+    /// and merges them into the already sorted `sorted` [`Vec`](std::vec::Vec).
+    ///
+    /// The elements of the iterator are sorted first and afterwards merged in place with a linear merge
+    /// starting at the end of `sorted`, i.e. the runtime is `O(n + d + m)` where `m` is the length of
+    /// `sorted`. This is far cheaper than concatenating and re-sorting, if `sorted` is large.
+    ///
+    /// The merge is stable: new elements are placed after existing elements that are equal to them.
+    ///
+    /// **<span style="color:red">Caution:</span>** `sorted` must already be sorted in ascending
+    /// order, otherwise the result is not sorted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let mut sorted = vec![1, 4, 9];
+    /// let batch = vec![7, 2, 4];
+    /// batch.iter().cnt_sort_merge_into(&mut sorted).unwrap();
+    ///
+    /// assert_eq!(vec![1, 2, 4, 4, 7, 9], sorted);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    ///
+    /// An empty iterator is no error, `sorted` stays unchanged.
+    fn cnt_sort_merge_into(self, sorted: &mut Vec<T>) -> Result<(), CountingSortError> {
+        counting_sort_merge_into(self, sorted)
+    }
+
+    /// Returns the exact number of distinct values in the [`Iterator`](std::iter::Iterator).
+    ///
+    /// The number of distinct values is the number of non-empty buckets of the count values vector. Neither
+    /// the sorted elements nor the distinct values themselves are materialized, which makes this a
+    /// cheap precursor for deciding whether sorting is worthwhile.
+    ///
+    /// An empty iterator has 0 distinct values and an iterator holding only equal values has 1
+    /// distinct value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![3, 1, 3, 2, 3];
+    ///
+    /// assert_eq!(3, vec.iter().cnt_sort_distinct_count().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    fn cnt_sort_distinct_count(self) -> Result<usize, CountingSortError> {
+        counting_sort_distinct_count(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) with the counting sort algorithm
+    /// and sends the sorted elements one by one through the bounded channel `sender`.
+    ///
+    /// Sending blocks whenever the channel is full, i.e. a consumer on another thread can process the
+    /// sorted elements at its own pace (backpressure). The sorted elements are materialized before
+    /// sending, therefore the memory usage on the producer side is `O(n + d)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::mpsc::sync_channel;
+    /// use std::thread;
+    /// use counting_sort::CountingSort;
+    ///
+    /// let (sender, receiver) = sync_channel(1);
+    /// let consumer = thread::spawn(move || receiver.iter().collect::<Vec<u8>>());
+    ///
+    /// let vec: Vec<u8> = vec![3, 1, 2];
+    /// vec.iter().cnt_sort_send(&sender).unwrap();
+    /// drop(sender);
+    ///
+    /// assert_eq!(vec![1, 2, 3], consumer.join().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::ChannelDisconnected`] when the receiver was dropped before all
+    ///   elements were sent
+    /// * All errors of [`cnt_sort`](CountingSort::cnt_sort()), nothing is sent in this case
+    fn cnt_sort_send(self, sender: &SyncSender<T>) -> Result<(), CountingSortError> {
+        counting_sort_send(self, sender)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) with the counting sort algorithm
+    /// and returns an [`Iterator`](std::iter::Iterator) over the sorted elements.
+    ///
+    /// Only the construction of the returned [`SortedIter`] can fail, iterating it is infallible.
+    /// This makes the sorted result composable with all standard iterator adapters. Since
+    /// [`SortedIter`] implements [`Default`](std::default::Default), errors can be mapped to an empty
+    /// iterator with `unwrap_or_default()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![4, 1, 3, 2];
+    /// let even: Vec<i32> = vec
+    ///     .iter()
+    ///     .try_sorted_by_counting()
+    ///     .unwrap_or_default()
+    ///     .filter(|value| value % 2 == 0)
+    ///     .collect();
+    ///
+    /// assert_eq!(vec![2, 4], even);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+    fn try_sorted_by_counting(self) -> Result<SortedIter<T>, CountingSortError> {
+        let sorted_vector = counting_sort(self)?;
+        Ok(SortedIter {
+            inner: sorted_vector.into_iter(),
+        })
+    }
+
+    /// Computes the trimmed mean of the elements in the [`Iterator`](std::iter::Iterator).
+    ///
+    /// The `trim_fraction` of the smallest and the `trim_fraction` of the largest elements are
+    /// discarded and the remaining elements are averaged. The number of discarded elements on each
+    /// side is `floor(n * trim_fraction)`. The tails are skipped using the frequencies of the count
+    /// values vector, i.e. no sorted [`Vec`](std::vec::Vec) is materialized and the runtime is
+    /// `O(n + d)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![-100, 4, 5, 6, 100];
+    ///
+    /// assert_eq!(5.0, vec.iter().trimmed_mean(0.2).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::InvalidTrimFraction`] when `trim_fraction` is not within `[0, 0.5)`
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty (and there is nothing to average)
+    fn trimmed_mean(self, trim_fraction: f64) -> Result<f64, CountingSortError>
+    where
+        T: Into<f64>,
+    {
+        counting_sort_trimmed_mean(self, trim_fraction)
+    }
+
+    /// Returns whether counting sort is recommended for the elements in the
+    /// [`Iterator`](std::iter::Iterator), compared to a comparison based sort like
+    /// [`slice::sort`](https://doc.rust-lang.org/std/primitive.slice.html#method.sort).
+    ///
+    /// Counting sort excels when there are a lot of elements but the distance `d` between the
+    /// minimum and maximum value is small. This method encodes this guidance as the comparison `n > d`,
+    /// where `n` is the number of elements and `d = max_value - min_value` (as computed by
+    /// [`TryIntoIndex`]). Both are computed in a single pass over the iterator.
+    ///
+    /// **Note:** This is a heuristic, not a guarantee. It neither considers the constant factors of
+    /// both algorithms nor the memory consumption of the count values vector.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let dense = vec![3, 1, 2, 1, 3, 2];
+    /// let sparse = vec![1, 1000, 100000];
+    ///
+    /// assert!(dense.iter().cnt_sort_recommended().unwrap());
+    /// assert!(!sparse.iter().cnt_sort_recommended().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty (and there is nothing to sort)
+    fn cnt_sort_recommended(self) -> Result<bool, CountingSortError> {
+        counting_sort_recommended(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) by a category looked up in a table
+    /// with the counting sort algorithm.
+    ///
+    /// This method is intended for [`u8`](std::u8) elements, but works for all types that implement
+    /// [`Into<u8>`](std::convert::Into). The category of an element is `table[usize::from(byte)]`, where
+    /// `byte` is the [`u8`](std::u8) representation of the element, and the elements are sorted by
+    /// ascending category. The sort is stable, i.e. elements of the same category keep their input
+    /// order. This enables fast grouping of e.g. ASCII bytes into character classes.
+    ///
+    /// The count values vector is sized by the largest category of all elements, not by the largest
+    /// entry of the table.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// // category 0 for digits, 1 for everything else
+    /// let mut table = [1; 256];
+    /// for digit in b'0'..=b'9' {
+    ///     table[usize::from(digit)] = 0;
+    /// }
+    ///
+    /// let bytes = b"a1b2".to_vec();
+    ///
+    /// assert_eq!(b"12ab".to_vec(), bytes.iter().cnt_sort_by_table(&table).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when the largest category is too large to allocate a
+    ///   count values vector for it
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty (and there is nothing to sort)
+    fn cnt_sort_by_table(self, table: &[usize; 256]) -> Result<Vec<T>, CountingSortError>
+    where
+        T: Into<u8>,
+    {
+        counting_sort_by_table(self, table)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) with the counting sort algorithm
+    /// into the given slice `output`.
+    ///
+    /// The sorted elements are written exclusively via indexed assignment into `output`, it is never
+    /// reallocated and nothing is assumed about where its memory comes from. Therefore the sorted
+    /// elements can land directly in e.g. a memory mapped file: sort into the `&mut [T]` view of the
+    /// memory map. Only the count values vector is allocated.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![3, 1, 2];
+    /// let mut output = [0; 3];
+    /// vec.iter().cnt_sort_into_slice(&mut output).unwrap();
+    ///
+    /// assert_eq!([1, 2, 3], output);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::LengthMismatch`] when the length of `output` differs from the number of
+    ///   elements, `output` is not modified in this case
+    /// * All errors of [`cnt_sort`](CountingSort::cnt_sort())
+    fn cnt_sort_into_slice(self, output: &mut [T]) -> Result<(), CountingSortError> {
+        counting_sort_into_slice(self, output)
+    }
+
+    /// Returns the gaps between consecutive distinct values of the elements in the
+    /// [`Iterator`](std::iter::Iterator).
+    ///
+    /// A gap is a pair `(lower, upper)` of consecutive occupied buckets of the count values vector,
+    /// whose distance (as computed by [`TryIntoIndex`]) is larger than 1, i.e. all values between
+    /// `lower` and `upper` are missing. The gaps are returned in ascending order. This is useful for
+    /// detecting missing IDs in a dense sequence.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let ids = vec![6, 1, 2, 5, 9];
+    ///
+    /// assert_eq!(vec![(2, 5), (6, 9)], ids.iter().cnt_sort_gaps().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    fn cnt_sort_gaps(self) -> Result<Vec<(T, T)>, CountingSortError> {
+        counting_sort_gaps(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) like
+    /// [`cnt_sort_min_max`](CountingSort::cnt_sort_min_max()) and additionally returns the actually
+    /// observed minimum and maximum value.
+    ///
+    /// Given bounds that are wider than the actual data still work, but allocate a larger count values
+    /// vector than necessary. The observed extremes are tracked during the count phase and returned as
+    /// `Some((observed_min_value, observed_max_value))`, or [`None`](std::option::Option::None) for an
+    /// empty iterator, so that callers can tighten their bounds next time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![13, 11, 12];
+    /// let (sorted_vec, observed) = vec.iter().cnt_sort_min_max_checked(&0, &100).unwrap();
+    ///
+    /// assert_eq!(vec![11, 12, 13], sorted_vec);
+    /// assert_eq!(Some((11, 13)), observed);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort_min_max`](CountingSort::cnt_sort_min_max()).
+    #[allow(clippy::type_complexity)]
+    fn cnt_sort_min_max_checked(
+        self,
+        min_value: &T,
+        max_value: &T,
+    ) -> Result<(Vec<T>, Option<(T, T)>), CountingSortError> {
+        counting_sort_min_max_checked(self, min_value, max_value)
+    }
+
+    /// Counts the `false` and `true` elements in the [`Iterator`](std::iter::Iterator) of
+    /// [`bool`](std::bool)s and returns them as `(false_count, true_count)`.
+    ///
+    /// The sorted elements are simply `false_count` times `false` followed by `true_count` times `true`,
+    /// therefore the sorted [`Vec`](std::vec::Vec) is not materialized. This method never fails, an
+    /// empty iterator returns `(0, 0)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![true, false, true, true];
+    ///
+    /// assert_eq!((1, 3), vec.iter().cnt_sort_bool_counts());
+    /// ```
+    fn cnt_sort_bool_counts(self) -> (usize, usize)
+    where
+        T: Into<bool>,
+    {
+        self.fold((0, 0), |(false_count, true_count), value| {
+            if (*value).into() {
+                (false_count, true_count + 1)
+            } else {
+                (false_count + 1, true_count)
+            }
+        })
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) in ascending order, but orders
+    /// equal elements pseudo-randomly instead of by their position in the input.
+    ///
+    /// This can be used to break ties fairly, e.g. for equal scores. The order of equal elements is
+    /// deterministic for a given `seed`, i.e. two runs with the same seed and the same input always
+    /// return the same [`Vec`](std::vec::Vec). The sort is therefore not stable. Each group of equal
+    /// elements is shuffled with a Fisher-Yates shuffle driven by a small seeded pseudo-random number
+    /// generator, which is not suitable for cryptographic purposes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![3, 1, 2, 1, 3];
+    /// let sorted_vec = vec.iter().cnt_sort_shuffle_ties(42).unwrap();
+    ///
+    /// assert_eq!(vec![1, 1, 2, 3, 3], sorted_vec);
+    /// assert_eq!(sorted_vec, vec.iter().cnt_sort_shuffle_ties(42).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+    fn cnt_sort_shuffle_ties(self, seed: u64) -> Result<Vec<T>, CountingSortError> {
+        counting_sort_shuffle_ties(self, seed)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) in ascending order while moving
+    /// all elements equal to `sentinel` to the end and returns the sorted
+    /// [`Vec`](std::vec::Vec) together with the number of sentinels.
+    ///
+    /// Sentinel markers, e.g. [`u16::MAX`](std::u16::MAX) meaning "missing", are excluded from the
+    /// search of the minimum and maximum value, hence they do not stretch the count values vector.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec: Vec<u16> = vec![3, u16::MAX, 1, 2, u16::MAX];
+    /// let (sorted_vec, sentinel_count) = vec.iter().cnt_sort_with_sentinel(u16::MAX).unwrap();
+    ///
+    /// assert_eq!(vec![1, 2, 3, u16::MAX, u16::MAX], sorted_vec);
+    /// assert_eq!(2, sentinel_count);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` of the non-sentinel
+    ///   values is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    fn cnt_sort_with_sentinel(self, sentinel: T) -> Result<(Vec<T>, usize), CountingSortError> {
+        counting_sort_with_sentinel(self, sentinel)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) like
+    /// [`cnt_sort`](CountingSort::cnt_sort()), but falls back to an insertion sort for tiny inputs.
+    ///
+    /// For fewer than [`INSERTION_SORT_THRESHOLD`] elements the multiple passes and the allocation of
+    /// the count values vector are more expensive than a simple insertion sort on the collected
+    /// [`Vec`](std::vec::Vec). This is common when sorting many small groups. Both algorithms are
+    /// stable, therefore the result is identical to the one of
+    /// [`cnt_sort`](CountingSort::cnt_sort()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![3, 1, 2];
+    ///
+    /// assert_eq!(vec![1, 2, 3], vec.iter().cnt_sort_adaptive().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()), however
+    /// [`CountingSortError::IntoIndexFailed`] can only occur when at least
+    /// [`INSERTION_SORT_THRESHOLD`] elements are sorted.
+    fn cnt_sort_adaptive(self) -> Result<Vec<T>, CountingSortError> {
+        counting_sort_adaptive(self)
+    }
+
+    /// Counts the elements in the [`Iterator`](std::iter::Iterator) into an [`IncrementalSort`]
+    /// that maintains the sorted elements under point updates.
+    ///
+    /// The given minimum and maximum value define the bounds of all values that may ever be added
+    /// by [`IncrementalSort::update`], since the count values vector is not resized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec: Vec<u8> = vec![3, 1, 2];
+    /// let mut incremental_sort = vec.iter().cnt_sort_incremental(&0, &10).unwrap();
+    /// incremental_sort.update(2, 7).unwrap();
+    ///
+    /// assert_eq!(vec![1, 3, 7], incremental_sort.sorted());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::MinValueLargerMaxValue`] when
+    ///   the given minimum value is larger than the given maximum value
+    /// * [`CountingSortError::IndexOutOfBounds`] when a value
+    ///   is larger than the given maximum value
+    fn cnt_sort_incremental(
+        self,
+        min_value: &T,
+        max_value: &T,
+    ) -> Result<IncrementalSort<T>, CountingSortError> {
+        IncrementalSort::new(self, min_value, max_value)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) whose values are known to lie
+    /// within the window `[base, base + window)`.
+    ///
+    /// Each value is mapped to the index `value - base`, hence the count values vector always holds
+    /// exactly `window` (plus one) elements regardless of the range of the type `T`. In contrast to
+    /// [`cnt_sort_min_max`](CountingSort::cnt_sort_min_max()) this guarantees a bounded memory
+    /// consumption for fixed windows, e.g. when sorting ages in `[0, 150)` stored as
+    /// [`u32`](std::u32). The minimum value is not searched and all values are checked to lie within
+    /// the window.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let ages: Vec<u32> = vec![42, 7, 99, 7, 18];
+    ///
+    /// assert_eq!(vec![7, 7, 18, 42, 99], ages.iter().cnt_sort_windowed(0, 150).unwrap());
+    /// assert!(ages.iter().cnt_sort_windowed(10, 150).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    /// * [`CountingSortError::IndexOutOfBounds`] when a value
+    ///   is outside of `[base, base + window)`
+    fn cnt_sort_windowed(self, base: T, window: usize) -> Result<Vec<T>, CountingSortError> {
+        counting_sort_windowed(self, base, window)
+    }
+
+    /// Returns a compact summary of the elements in the [`Iterator`](std::iter::Iterator) computed
+    /// from the count values vector, e.g. `"n=30 min=1 max=30 distinct=23 mode=3(x3)"`.
+    ///
+    /// The summary contains the number of elements `n`, the minimum and maximum value, the number of
+    /// distinct values and the most frequent value (the smallest one if several values are equally
+    /// frequent) with its frequency. This is useful for debugging, since the sorted elements are not
+    /// materialized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![5, 2, 5, 9];
+    ///
+    /// assert_eq!(
+    ///     "n=4 min=2 max=9 distinct=3 mode=5(x2)",
+    ///     vec.iter().cnt_sort_summary().unwrap()
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    fn cnt_sort_summary(self) -> Result<String, CountingSortError>
+    where
+        T: Display,
+    {
+        counting_sort_summary(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) and additionally returns all
+    /// values that occur more than once.
+    ///
+    /// Returns `(sorted_vector, duplicate_values)`, where `duplicate_values` contains each value,
+    /// whose count in the count values vector is larger than 1, exactly once in ascending order. This
+    /// is useful for data validation, e.g. for checking that IDs are unique. In contrast to
+    /// [`cnt_sort`](CountingSort::cnt_sort()) it is no error if all elements are equal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let ids = vec![4, 2, 7, 4, 2, 4];
+    /// let (sorted_ids, duplicate_ids) = ids.iter().cnt_sort_with_duplicates().unwrap();
+    ///
+    /// assert_eq!(vec![2, 2, 4, 4, 4, 7], sorted_ids);
+    /// assert_eq!(vec![2, 4], duplicate_ids);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    #[allow(clippy::type_complexity)]
+    fn cnt_sort_with_duplicates(self) -> Result<(Vec<T>, Vec<T>), CountingSortError> {
+        counting_sort_with_duplicates(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) and additionally returns the
+    /// Shannon entropy (in bits) of the distribution of the values.
+    ///
+    /// The entropy is computed from the normalized counts `p_i` of the count values vector as
+    /// `H = -sum(p_i * log2(p_i))`. It is 0 if all elements are equal and `log2(k)` if `k` distinct
+    /// values are uniformly distributed. A low entropy signals few distinct values, i.e. counting
+    /// sort is a great fit. In contrast to [`cnt_sort`](CountingSort::cnt_sort()) it is no error if
+    /// all elements are equal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![2, 1, 4, 3];
+    /// let (sorted_vec, entropy) = vec.iter().cnt_sort_with_entropy().unwrap();
+    ///
+    /// assert_eq!(vec![1, 2, 3, 4], sorted_vec);
+    /// assert!((entropy - 2.0).abs() < 1e-12);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    fn cnt_sort_with_entropy(self) -> Result<(Vec<T>, f64), CountingSortError> {
+        counting_sort_with_entropy(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) like
+    /// [`cnt_sort`](CountingSort::cnt_sort()), but automatically selects the element type of the
+    /// count values vector.
+    ///
+    /// The counts of the count values vector never exceed the number of elements `n`. Hence, if
+    /// `n` fits into an [`u32`](std::u32) and the distance `d` is at least
+    /// [`COMPACT_COUNTER_MIN_DISTANCE`], [`u32`](std::u32) counters are used, which halves the memory
+    /// of the count values vector on 64 bit platforms. Otherwise [`usize`](std::usize) counters are
+    /// used like in [`cnt_sort`](CountingSort::cnt_sort()). The number of elements is counted while
+    /// searching the minimum and maximum value, so no additional pass is needed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec: Vec<u16> = vec![60000, 3, 40000, 3];
+    ///
+    /// assert_eq!(vec![3, 3, 40000, 60000], vec.iter().cnt_sort_auto_counter().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+    fn cnt_sort_auto_counter(self) -> Result<Vec<T>, CountingSortError> {
+        counting_sort_auto_counter(self)
+    }
+
+    /// Counts the elements in the [`Iterator`](std::iter::Iterator) into a histogram with one
+    /// bucket per value in `[min_value, max_value]`, clamping outliers into the edge buckets.
+    ///
+    /// Returns `(counts, underflow_count, overflow_count)`, where `counts` is the count values vector
+    /// (without the additional 0-th element) with `d + 1` elements. Values smaller than `min_value`
+    /// are counted in the first bucket and values larger than `max_value` in the last bucket, the
+    /// number of these clamped values is reported as `underflow_count` and `overflow_count`. This is
+    /// useful for fixed-width histograms, e.g. for monitoring dashboards. An empty iterator returns
+    /// an all-zero histogram.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let latencies = vec![1, 3, 9, 2, 0, 3];
+    /// let (counts, underflow_count, overflow_count) =
+    ///     latencies.iter().cnt_sort_clamped_histogram(1, 4).unwrap();
+    ///
+    /// assert_eq!(vec![2, 1, 2, 1], counts);
+    /// assert_eq!((1, 1), (underflow_count, overflow_count));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::MinValueLargerMaxValue`] when
+    ///   the given minimum value is larger than the given maximum value
+    #[allow(clippy::type_complexity)]
+    fn cnt_sort_clamped_histogram(
+        self,
+        min_value: T,
+        max_value: T,
+    ) -> Result<(Vec<usize>, usize, usize), CountingSortError> {
+        counting_sort_clamped_histogram(self, min_value, max_value)
+    }
+
+    /// Returns only the sorted elements at the ranks `[lo, hi)`, e.g. for paginated results.
+    ///
+    /// The whole sorted [`Vec`](std::vec::Vec) is never materialized: after the prefix sum each
+    /// element's rank is computed during the re-order phase and only elements with a rank within the
+    /// range are copied. This needs `O(n+d)` time, but only `O(hi-lo+d)` memory. If `hi` is larger
+    /// than the number of elements `n`, it is clamped to `n`, if `lo >= hi` an empty
+    /// [`Vec`](std::vec::Vec) is returned. The order of equal elements is stable, i.e. the result
+    /// equals `cnt_sort()[lo..hi]`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![9, 3, 7, 3, 1, 8];
+    ///
+    /// assert_eq!(vec![3, 7], vec.iter().cnt_sort_range_slice(2, 4).unwrap());
+    /// assert_eq!(vec![8, 9], vec.iter().cnt_sort_range_slice(4, 100).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    fn cnt_sort_range_slice(self, lo: usize, hi: usize) -> Result<Vec<T>, CountingSortError> {
+        counting_sort_range_slice(self, lo, hi)
+    }
+
+    /// Returns the permutation that sorts the elements in the [`Iterator`](std::iter::Iterator)
+    /// and its inverse as `(sort_permutation, inverse_permutation)`.
+    ///
+    /// `sort_permutation[k]` is the original index of the element at the sorted position `k`, i.e.
+    /// it gathers the sorted elements from the original layout. `inverse_permutation[i]` is the
+    /// sorted position of the element at the original index `i`, i.e. it scatters the original
+    /// elements into the sorted layout. Both are computed in the same stable re-order pass, so equal
+    /// elements keep their order. In contrast to [`cnt_sort`](CountingSort::cnt_sort()) it is no
+    /// error if all elements are equal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![30, 10, 20];
+    /// let (sort_permutation, inverse_permutation) = vec.iter().cnt_sort_permutations().unwrap();
+    ///
+    /// assert_eq!(vec![1, 2, 0], sort_permutation);
+    /// assert_eq!(vec![2, 0, 1], inverse_permutation);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    #[allow(clippy::type_complexity)]
+    fn cnt_sort_permutations(self) -> Result<(Vec<usize>, Vec<usize>), CountingSortError> {
+        counting_sort_permutations(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) in ascending order and breaks
+    /// ties by a secondary key instead of by their position in the input.
+    ///
+    /// After the counting sort has placed equal elements in input order, each group of equal
+    /// elements is sorted by the key returned by `secondary` with the stable comparison sort of the
+    /// standard library. Since these groups are typically short, the additional cost is low. This
+    /// gives a two-level ordering without a second counting sort pass, e.g. a deterministic total
+    /// order of events with equal keys by their timestamps when the input order is arbitrary.
+    /// Elements with equal primary and secondary keys keep their input order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::{CountingSort, TryIntoIndex};
+    /// use std::cmp::Ordering;
+    ///
+    /// #[derive(Copy, Clone, Debug)]
+    /// struct Score {
+    ///     points: u8,
+    ///     name: &'static str,
+    /// }
+    ///
+    /// impl Ord for Score {
+    ///     fn cmp(&self, other: &Self) -> Ordering {
+    ///         self.points.cmp(&other.points)
+    ///     }
+    /// }
+    ///
+    /// impl PartialOrd for Score {
+    ///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    ///         Some(self.cmp(other))
+    ///     }
+    /// }
+    ///
+    /// impl PartialEq for Score {
+    ///     fn eq(&self, other: &Self) -> bool {
+    ///         self.points == other.points
+    ///     }
+    /// }
+    ///
+    /// impl Eq for Score {}
+    ///
+    /// impl TryIntoIndex for Score {
+    ///     type Error = <u8 as TryIntoIndex>::Error;
+    ///
+    ///     fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+    ///         u8::try_into_index(&value.points, &min_value.points)
+    ///     }
+    /// }
+    ///
+    /// let scores = vec![
+    ///     Score { points: 2, name: "eve" },
+    ///     Score { points: 1, name: "bob" },
+    ///     Score { points: 2, name: "ann" },
+    /// ];
+    /// let sorted_scores = scores.iter().cnt_sort_tiebreak_by(|score| score.name).unwrap();
+    ///
+    /// let names: Vec<&str> = sorted_scores.iter().map(|score| score.name).collect();
+    /// assert_eq!(vec!["bob", "ann", "eve"], names);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+    #[doc(alias = "cnt_sort_tie_break_by")]
+    fn cnt_sort_tiebreak_by<K2, F>(self, secondary: F) -> Result<Vec<T>, CountingSortError>
+    where
+        F: Fn(&T) -> K2,
+        K2: Ord,
+    {
+        counting_sort_tiebreak_by(self, secondary)
+    }
+
+    /// Returns the longest run of equal consecutive values in the sorted order, i.e. the value
+    /// with the highest count and its count, without materializing the sorted
+    /// [`Vec`](std::vec::Vec).
+    ///
+    /// This is the mode of the elements together with its frequency, framed for streak analysis on
+    /// sorted data. The result is computed from the count values vector. If several values have the
+    /// same highest count, the smallest value wins, i.e. the first of these runs in sorted order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![4, 9, 4, 1, 9];
+    ///
+    /// assert_eq!((4, 2), vec.iter().cnt_sort_longest_run().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    fn cnt_sort_longest_run(self) -> Result<(T, usize), CountingSortError> {
+        counting_sort_longest_run(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) in ascending order and returns
+    /// them already split into groups of equal elements.
+    ///
+    /// For each distinct value the result contains a tuple of the value, i.e. its first occurrence
+    /// in the input, and a [`Vec`](std::vec::Vec) of all its occurrences in input order. This is the
+    /// "GROUP BY" complement to sorting. For plain integers the inner vectors are just repetitions
+    /// of the value, it becomes useful for types whose [`Ord`](std::cmp::Ord) and
+    /// [`TryIntoIndex`](crate::TryIntoIndex) implementations only consider a key and which carry
+    /// a payload besides this key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![3, 1, 3, 2];
+    ///
+    /// assert_eq!(
+    ///     vec![(1, vec![1]), (2, vec![2]), (3, vec![3, 3])],
+    ///     vec.iter().cnt_sort_grouped().unwrap()
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+    #[allow(clippy::type_complexity)]
+    fn cnt_sort_grouped(self) -> Result<Vec<(T, Vec<T>)>, CountingSortError> {
+        counting_sort_grouped(self)
+    }
+
+    /// Sorts the [`char`](std::char)s in the [`Iterator`](std::iter::Iterator) in ascending order
+    /// while ignoring the case of ASCII letters.
+    ///
+    /// The chars are bucketed by [`to_ascii_lowercase`](char::to_ascii_lowercase()), i.e. `'A'` and
+    /// `'a'` share a bucket, but the original case is preserved in the sorted
+    /// [`Vec`](std::vec::Vec). The sort is stable, hence chars of the same bucket keep their input
+    /// order. Non-ASCII chars are sorted by their Unicode scalar value only.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec!['b', 'A', 'a', 'B'];
+    ///
+    /// assert_eq!(vec!['A', 'a', 'b', 'B'], vec.iter().cnt_sort_chars_ci().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    /// * [`CountingSortError::SortingUnnecessary`] when the iterator
+    ///   contains only identical chars, chars that differ only in case are returned in input order
+    fn cnt_sort_chars_ci(self) -> Result<Vec<char>, CountingSortError>
+    where
+        T: Into<char>,
+    {
+        counting_sort_chars_ci(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) in ascending order with the
+    /// given [`Stability`], i.e. the order of equal elements.
+    ///
+    /// [`Stability::Stable`] is the behaviour of [`cnt_sort`](CountingSort::cnt_sort()), equal
+    /// elements keep their input order. [`Stability::ReverseStable`] reverses the order of equal
+    /// elements, i.e. later elements sort first, which some merge algorithms require. This is
+    /// implemented by filling each bucket from its end instead of from its start, hence it has the
+    /// same cost as the stable sort.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::{CountingSort, Stability};
+    ///
+    /// let vec = vec![2, 1, 2];
+    ///
+    /// assert_eq!(vec![1, 2, 2], vec.iter().cnt_sort_with_stability(Stability::ReverseStable).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+    fn cnt_sort_with_stability(self, stability: Stability) -> Result<Vec<T>, CountingSortError> {
+        counting_sort_with_stability(self, stability)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) in ascending order and returns
+    /// each element together with its original position in the input.
+    ///
+    /// This is the sorted [`Vec`](std::vec::Vec) and the sort permutation of
+    /// [`cnt_sort_permutations`](CountingSort::cnt_sort_permutations()) combined, which is useful
+    /// for traceability. Since the sort is stable, the origins of equal elements are ascending.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![30, 10, 20, 10];
+    ///
+    /// assert_eq!(
+    ///     vec![(10, 1), (10, 3), (20, 2), (30, 0)],
+    ///     vec.iter().cnt_sort_with_origins().unwrap()
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    fn cnt_sort_with_origins(self) -> Result<Vec<(T, usize)>, CountingSortError> {
+        counting_sort_with_origins(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) in ascending order and computes
+    /// aggregate [`Stats`] of the elements on the way.
+    ///
+    /// The minimum, maximum, count and sum are accumulated in the scan that identifies the minimum
+    /// and maximum value anyway, hence the statistics do not need a separate pass over the sorted
+    /// [`Vec`](std::vec::Vec). For the sum the elements must be convertible into an
+    /// [`i128`](https://doc.rust-lang.org/std/primitive.i128.html), which is the case for all
+    /// integer types that implement [`TryIntoIndex`](crate::TryIntoIndex).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec: Vec<i16> = vec![4, -2, 10, 4];
+    /// let (sorted_vec, stats) = vec.iter().cnt_sort_with_stats().unwrap();
+    ///
+    /// assert_eq!(vec![-2, 4, 4, 10], sorted_vec);
+    /// assert_eq!((-2, 10, 4, 16), (stats.min, stats.max, stats.count, stats.sum));
+    /// assert!((stats.mean - 4.0).abs() < f64::EPSILON);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+    fn cnt_sort_with_stats(self) -> Result<(Vec<T>, Stats<T>), CountingSortError>
+    where
+        T: Into<i128>,
+    {
+        counting_sort_with_stats(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) with the counting sort algorithm
+    /// and returns them as [`SortedVec`].
+    ///
+    /// In contrast to the [`Vec`](std::vec::Vec) returned by
+    /// [`cnt_sort`](CountingSort::cnt_sort()), the type guarantees the sortedness, since it cannot
+    /// be mutated out of order, and provides sorted-only queries like
+    /// [`rank`](SortedVec::rank()) and [`contains`](SortedVec::contains()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![3, 1, 2];
+    /// let sorted_vec = vec.iter().cnt_sort_to_sorted_vec().unwrap();
+    ///
+    /// assert_eq!(&[1, 2, 3], &sorted_vec[..]);
+    /// assert_eq!(1, sorted_vec.rank(&2));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+    fn cnt_sort_to_sorted_vec(self) -> Result<SortedVec<T>, CountingSortError> {
+        let sorted_vector = counting_sort(self)?;
+        Ok(SortedVec {
+            inner: sorted_vector,
+        })
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) in ascending order and returns
+    /// a [`Locator`] together with the sorted [`Vec`](std::vec::Vec).
+    ///
+    /// The [`Locator`] keeps the bucket boundaries of the counting sort algorithm and returns the
+    /// range of positions of any value in the sorted [`Vec`](std::vec::Vec) in `O(1)`. In contrast
+    /// to [`cnt_sort`](CountingSort::cnt_sort()) elements that are all equal are no error, since
+    /// the [`Locator`] is useful for them as well.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![2, 1, 2];
+    /// let (sorted_vec, locator) = vec.iter().cnt_sort_with_locator().unwrap();
+    ///
+    /// assert_eq!(&[2, 2], &sorted_vec[locator.positions(&2).unwrap()]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    fn cnt_sort_with_locator(self) -> Result<(Vec<T>, Locator<T>), CountingSortError> {
+        counting_sort_with_locator(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) like
+    /// [`cnt_sort_min_max`](CountingSort::cnt_sort_min_max()), but calls `on_unnecessary` instead
+    /// of returning [`CountingSortError::SortingUnnecessary`].
+    ///
+    /// When `min_value` equals `max_value` all elements are essentially equal, hence sorting is
+    /// unnecessary. Instead of forcing the caller to handle this error, the
+    /// [`Vec`](std::vec::Vec) returned by `on_unnecessary` is returned, e.g. a copy of the input.
+    /// The closure is only called in this case. Note that the elements are not checked against the
+    /// bounds when sorting is unnecessary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![4, 4, 4];
+    /// let sorted_vec = vec.iter().cnt_sort_min_max_or(&4, &4, || vec.clone()).unwrap();
+    ///
+    /// assert_eq!(vec![4, 4, 4], sorted_vec);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort_min_max`](CountingSort::cnt_sort_min_max()), except for
+    /// [`CountingSortError::SortingUnnecessary`].
+    fn cnt_sort_min_max_or<F>(
+        self,
+        min_value: &T,
+        max_value: &T,
+        on_unnecessary: F,
+    ) -> Result<Vec<T>, CountingSortError>
+    where
+        F: FnOnce() -> Vec<T>,
+    {
+        match counting_sort_min_max(self, min_value, max_value) {
+            Err(CountingSortError::SortingUnnecessary(_)) => Ok(on_unnecessary()),
+            result => result,
+        }
+    }
+
+    /// Sorts the distinct elements in the [`Iterator`](std::iter::Iterator) in ascending order
+    /// using a bitset instead of a count vector.
+    ///
+    /// When every value occurs at most once each count is either 0 or 1, hence a single bit per
+    /// value suffices. The bitset needs 1/64th of the memory of the count vector of
+    /// [`cnt_sort`](CountingSort::cnt_sort()) on 64-bit targets, which makes sorting distinct
+    /// IDs with a large distance `d` feasible. The sorted elements are reconstructed from the set
+    /// bits with [`TryFromIndex`](crate::TryFromIndex).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let ids = vec![1_000_000_u32, 3, 70_000, 42];
+    /// let sorted_ids = ids.iter().cnt_sort_distinct().unwrap();
+    ///
+    /// assert_eq!(vec![3, 42, 70_000, 1_000_000], sorted_ids);
+    /// assert!(vec![3, 1, 3].iter().cnt_sort_distinct().is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::FromIndexFailed`] when
+    ///   converting an index back into a value fails
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    /// * [`CountingSortError::DuplicateFound`] when a value occurs more than once
+    fn cnt_sort_distinct(self) -> Result<Vec<T>, CountingSortError>
+    where
+        T: TryFromIndex,
+    {
+        counting_sort_distinct(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) in ascending order like
+    /// [`cnt_sort`](CountingSort::cnt_sort()), but aborts as soon as `cancel` is set.
+    ///
+    /// The flag is checked once every 4096 elements in every pass over the elements, i.e. while
+    /// determining the minimum and maximum value, while counting and while re-ordering, so that
+    /// long-running sorts, e.g. in a UI, can be aborted from another thread without slowing down
+    /// the hot loops noticeably. If the flag is set when sorting finishes, the sort is considered
+    /// cancelled as well.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// let vec = vec![3, 1, 2];
+    /// let cancel = AtomicBool::new(false);
+    /// assert_eq!(vec![1, 2, 3], vec.iter().cnt_sort_cancellable(&cancel).unwrap());
+    ///
+    /// cancel.store(true, Ordering::Relaxed);
+    /// assert!(vec.iter().cnt_sort_cancellable(&cancel).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()) and additionally
+    /// [`CountingSortError::Cancelled`] when `cancel` is set.
+    fn cnt_sort_cancellable(self, cancel: &AtomicBool) -> Result<Vec<T>, CountingSortError> {
+        counting_sort_cancellable(self, cancel)
+    }
+
+    /// Returns every distinct element in the [`Iterator`](std::iter::Iterator) exactly once in the
+    /// order of its first occurrence.
+    ///
+    /// This is an order-preserving deduplication powered by the counting infrastructure: instead of
+    /// counting, a bit per bucket of the count vector marks whether a value was already seen. In
+    /// contrast to the sorting methods all elements being equal is no error, the single distinct
+    /// element is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![3, 1, 3, 2, 1];
+    /// let first_occurrences = vec.iter().cnt_first_occurrences().unwrap();
+    ///
+    /// assert_eq!(vec![3, 1, 2], first_occurrences);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    fn cnt_first_occurrences(self) -> Result<Vec<T>, CountingSortError> {
+        counting_first_occurrences(self)
+    }
+
+    /// Counts the elements in the [`Iterator`](std::iter::Iterator) and returns the distinct values in
+    /// ascending order together with the start offsets of their runs in the sorted elements.
+    ///
+    /// The returned tuple `(distinct_values, start_offsets)` is aligned by index: the run of
+    /// `distinct_values[i]` starts at `start_offsets[i]` in the fully sorted sequence, i.e. the
+    /// offsets are the prefix sums of the counting sort algorithm. This is exactly the data needed to
+    /// build a dictionary-encoded column. The run of the last value ends at the number of elements,
+    /// which is not part of the offsets. The sorted elements are never materialized.
+    ///
+    /// Equal elements are represented by their first occurrence in the collection.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![3, 1, 3, 2, 3];
+    /// let (dictionary, start_offsets) = vec.iter().cnt_sort_dictionary().unwrap();
+    ///
+    /// assert_eq!(vec![1, 2, 3], dictionary);
+    /// assert_eq!(vec![0, 1, 2], start_offsets);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty (and there is nothing to count)
+    fn cnt_sort_dictionary(self) -> Result<(Vec<T>, Vec<usize>), CountingSortError> {
+        let distinct_values_with_counts = count_distinct_values(self)?;
+        let mut start_offset = 0;
+        Ok(distinct_values_with_counts
+            .into_iter()
+            .map(|(value, count)| {
+                let run = (value, start_offset);
+                // the counts sum up to the number of elements, hence no overflow
+                start_offset += count;
+                run
+            })
+            .unzip())
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) in the [`Order`] selected by the
+    /// marker type `O`, i.e. [`Ascending`] or [`Descending`].
+    ///
+    /// The order decides the bucket of each value at monomorphization time, hence both directions
+    /// are as fast as [`cnt_sort`](CountingSort::cnt_sort()) and there is no need for separate
+    /// descending methods. The sort is stable in both directions, i.e. equal elements keep their
+    /// input order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::{Ascending, CountingSort, Descending};
+    ///
+    /// let vec = vec![2, 4, 1, 3];
+    ///
+    /// assert_eq!(vec![1, 2, 3, 4], vec.iter().cnt_sort_ordered::<Ascending>().unwrap());
+    /// assert_eq!(vec![4, 3, 2, 1], vec.iter().cnt_sort_ordered::<Descending>().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+    fn cnt_sort_ordered<O>(self) -> Result<Vec<T>, CountingSortError>
+    where
+        O: Order,
+    {
+        counting_sort_ordered::<O, _, _>(self)
+    }
+
+    /// Computes a deterministic checksum of the sorted elements in the
+    /// [`Iterator`](std::iter::Iterator) without materializing them.
+    ///
+    /// The checksum is the 64-bit FNV-1a hash over every distinct value and its count in ascending
+    /// order, taken from the count phase of the counting sort algorithm. Hence it represents the
+    /// sorted multiset: two collections that are permutations of each other have the same
+    /// checksum, e.g. to verify that a remote sort matches a local one. Integers are hashed in
+    /// little endian byte order, so that the checksum is identical on every platform, as long as
+    /// the [`Hash`](core::hash::Hash) implementation of `T` is platform independent.
+    ///
+    /// Equal elements are represented by their first occurrence in the collection.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![3, 1, 2, 3];
+    /// let shuffled_vec = vec![2, 3, 3, 1];
+    ///
+    /// assert_eq!(
+    ///     vec.iter().cnt_sort_checksum().unwrap(),
+    ///     shuffled_vec.iter().cnt_sort_checksum().unwrap()
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty (and there is nothing to count)
+    fn cnt_sort_checksum(self) -> Result<u64, CountingSortError>
+    where
+        T: core::hash::Hash,
+    {
+        use core::hash::Hasher;
+
+        let mut hasher = Fnv1a::default();
+        for (value, count) in count_distinct_values(self)? {
+            value.hash(&mut hasher);
+            hasher.write_usize(count);
+        }
+        Ok(hasher.finish())
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) like
+    /// [`cnt_sort`](CountingSort::cnt_sort()), but returns an empty [`Vec`](std::vec::Vec) instead
+    /// of [`CountingSortError::IteratorEmpty`].
+    ///
+    /// An empty collection is trivially sorted, hence callers do not need to special-case it.
+    /// All other errors are still returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec: Vec<u8> = vec![];
+    /// let sorted_vec = vec.iter().cnt_sort_or_empty().unwrap();
+    ///
+    /// assert!(sorted_vec.is_empty());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()), except for
+    /// [`CountingSortError::IteratorEmpty`].
+    fn cnt_sort_or_empty(self) -> Result<Vec<T>, CountingSortError> {
+        match counting_sort(self) {
+            Err(CountingSortError::IteratorEmpty(_)) => Ok(Vec::new()),
+            result => result,
+        }
+    }
+
+    /// Counts the elements in the [`Iterator`](std::iter::Iterator) and returns an
+    /// [`Iterator`](std::iter::Iterator) over the distinct values in ascending order together with
+    /// their cumulative counts.
+    ///
+    /// The cumulative count of a value is the number of elements smaller than or equal to it, i.e.
+    /// the prefix sum of the counting sort algorithm over the occupied buckets. Hence the last
+    /// cumulative count is the number of elements. This is e.g. the step function of the empirical
+    /// cumulative distribution function. The sorted elements are never materialized.
+    ///
+    /// Equal elements are represented by their first occurrence in the collection.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![3, 1, 3, 2, 3];
+    /// let cumulative: Vec<(i32, usize)> = vec.iter().cnt_sort_cumulative().unwrap().collect();
+    ///
+    /// assert_eq!(vec![(1, 1), (2, 2), (3, 5)], cumulative);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty (and there is nothing to count)
+    fn cnt_sort_cumulative(self) -> Result<std::vec::IntoIter<(T, usize)>, CountingSortError> {
+        let mut distinct_values_with_counts = count_distinct_values(self)?;
+        let mut cumulative_count = 0;
+        for (_, count) in &mut distinct_values_with_counts {
+            // the counts sum up to the number of elements, hence no overflow
+            cumulative_count += *count;
+            *count = cumulative_count;
+        }
+        Ok(distinct_values_with_counts.into_iter())
+    }
+
+    /// Checks whether [`cnt_sort`](CountingSort::cnt_sort()) would succeed without sorting.
+    ///
+    /// Only the cheap prechecks of [`cnt_sort`](CountingSort::cnt_sort()) are performed: the
+    /// minimum and maximum value are determined and the distance `d` between them is converted
+    /// into the length of the count values vector. Neither the count values vector nor the sorted
+    /// [`Vec`](std::vec::Vec) is allocated, hence the runtime is `O(n)` without additional memory,
+    /// e.g. to validate a request cheaply before committing to the work. Errors of the conversion
+    /// of single elements, which only occur for a faulty [`TryIntoIndex`] implementation, and
+    /// allocation failures are not detected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// assert!(vec![2, 1, 3].iter().cnt_sort_check().is_ok());
+    /// assert!(vec![2, 2].iter().cnt_sort_check().is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting the distance `d` into the length of the count values vector fails, this could
+    ///   happen if `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    /// * [`CountingSortError::SortingUnnecessary`] when the iterator
+    ///   contains more than one element and all elements are equal
+    fn cnt_sort_check(self) -> Result<(), CountingSortError> {
+        counting_sort_check(&self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) like
+    /// [`cnt_sort`](CountingSort::cnt_sort()), but first checks that no two unequal elements are
+    /// converted into the same index.
+    ///
+    /// A lossy [`TryIntoIndex`] implementation silently treats unequal elements as equal, i.e. they
+    /// end up in the same bucket in input order. This opt-in check keeps the first element of each
+    /// bucket and compares all further elements of the bucket with it, which costs an additional
+    /// pass and `O(d)` memory for the representatives. Use it in tests or when validating a custom
+    /// [`TryIntoIndex`] implementation, see also [`check_try_into_index_monotonic`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![3, 1, 2];
+    ///
+    /// assert_eq!(vec![1, 2, 3], vec.iter().cnt_sort_with_collision_check().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IndexCollision`] when
+    ///   two unequal elements are converted into the same index
+    /// * All errors of [`cnt_sort`](CountingSort::cnt_sort())
+    fn cnt_sort_with_collision_check(self) -> Result<Vec<T>, CountingSortError> {
+        counting_sort_with_collision_check(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator), which must be a permutation of
+    /// a contiguous range of values, e.g. dense IDs.
+    ///
+    /// If every value between the minimum and the maximum value occurs exactly once, i.e. all
+    /// elements of the count values vector are one, the sorted elements are just the range
+    /// `min_value..=max_value`. Hence they are reconstructed directly from the indices with
+    /// [`TryFromIndex`](crate::TryFromIndex) without re-ordering the elements.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let ids = vec![12_u32, 10, 13, 11];
+    ///
+    /// assert_eq!(vec![10, 11, 12, 13], ids.iter().cnt_sort_dense().unwrap());
+    /// assert!(vec![10_u32, 12].iter().cnt_sort_dense().is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::FromIndexFailed`] when
+    ///   converting an index back into a value fails
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    /// * [`CountingSortError::NotAPermutation`] when a value is missing or occurs more than once
+    fn cnt_sort_dense(self) -> Result<Vec<T>, CountingSortError>
+    where
+        T: TryFromIndex,
+    {
+        counting_sort_dense(self)
+    }
+
+    /// Returns the fraction of adjacent pairs of elements in the [`Iterator`](std::iter::Iterator)
+    /// that are in non-decreasing order.
+    ///
+    /// This is a lightweight diagnostic to decide whether sorting is worth it: `1.0` means the
+    /// elements are already sorted, about `0.5` means random order and `0.0` strictly descending
+    /// order. The iterator is walked once like when determining the minimum and maximum value,
+    /// nothing is counted or allocated. Fewer than two elements are trivially sorted, hence `1.0`
+    /// is returned for them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// assert_eq!(1.0, vec![1, 2, 2, 3].iter().sortedness());
+    /// assert_eq!(0.0, vec![3, 2, 1].iter().sortedness());
+    /// assert_eq!(0.5, vec![1, 3, 2].iter().sortedness());
+    /// ```
+    fn sortedness(self) -> f64 {
+        counting_sortedness(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) in ascending order, counting
+    /// them in parallel using [rayon](https://docs.rs/rayon).
+    ///
+    /// Available with the `rayon` feature. The elements are split into one chunk per thread and
+    /// each chunk is counted into its own count values vector. These count values vectors are
+    /// merged, which is cheaper than merging sorted runs, before the elements are re-ordered once
+    /// like in [`cnt_sort`](CountingSort::cnt_sort()). Hence the sort is stable, in contrast to
+    /// [`par_cnt_sort_unstable`](crate::par_cnt_sort_unstable), which reconstructs the elements
+    /// from representatives in parallel. Note that every thread allocates its own count values
+    /// vector and that the references to the elements are collected to split them into chunks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec: Vec<u16> = vec![9, 3, 7, 3, 1];
+    ///
+    /// assert_eq!(vec![1, 3, 3, 7, 9], vec.iter().cnt_sort_parallel_merge().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+    #[cfg(feature = "rayon")]
+    fn cnt_sort_parallel_merge(self) -> Result<Vec<T>, CountingSortError>
+    where
+        T: Send + Sync,
+    {
+        counting_sort_parallel_merge(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) by counting them in a
+    /// [`HashMap`](std::collections::HashMap) with the capacity for `expected_distinct` values
+    /// reserved up front.
+    ///
+    /// This is meant for sparse but wide data, i.e. few distinct values spread over a large
+    /// distance `d`, where the count values vector of [`cnt_sort`](CountingSort::cnt_sort()) would
+    /// be huge. Instead, only the distinct values are counted in a hash map and sorted by comparison,
+    /// hence the runtime is `O(n + k log(k))` and the memory consumption `O(k)` for `k` distinct
+    /// values, independent of `d`. If the hint is accurate, the hash map is never rehashed while
+    /// counting. The sorted [`Vec`](std::vec::Vec) is reconstructed from the first occurrence of
+    /// every value, hence the sort is unstable for elements that are equal but distinguishable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![4_000_000_u32, 7, 4_000_000, 120_000];
+    /// let sorted_vec = vec.iter().cnt_sort_hashed_with_capacity(3).unwrap();
+    ///
+    /// assert_eq!(vec![7, 120_000, 4_000_000, 4_000_000], sorted_vec);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    /// * [`CountingSortError::SortingUnnecessary`] when the iterator
+    ///   contains more than one element and all elements are equal
+    fn cnt_sort_hashed_with_capacity(
+        self,
+        expected_distinct: usize,
+    ) -> Result<Vec<T>, CountingSortError>
+    where
+        T: core::hash::Hash,
+    {
+        counting_sort_hashed(self, expected_distinct)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) in ascending order and returns
+    /// the rank of every element together with the sorted [`Vec`](std::vec::Vec).
+    ///
+    /// `ranks[i]` is the sorted position of the element at the original index `i`, i.e.
+    /// `sorted_vec[ranks[i]]` is the original element `i`. Both are computed in the same stable
+    /// re-order pass from a single count phase, which is cheaper than sorting and ranking
+    /// separately. Equal elements keep their order, hence they have distinct ranks. Like
+    /// [`cnt_sort_permutations`](CountingSort::cnt_sort_permutations()) it is no error if all
+    /// elements are equal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![30, 10, 20, 10];
+    /// let (sorted_vec, ranks) = vec.iter().cnt_sort_and_ranks().unwrap();
+    ///
+    /// assert_eq!(vec![10, 10, 20, 30], sorted_vec);
+    /// assert_eq!(vec![3, 0, 2, 1], ranks);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IteratorEmpty`] when the iterator
+    ///   is empty
+    #[allow(clippy::type_complexity)]
+    fn cnt_sort_and_ranks(self) -> Result<(Vec<T>, Vec<usize>), CountingSortError> {
+        counting_sort_and_ranks(self)
+    }
+
+    /// Sorts the [`char`](std::char)s in the [`Iterator`](std::iter::Iterator) in ascending order,
+    /// assuming that all chars fall within the contiguous Unicode block `[block_start, block_end]`.
+    ///
+    /// Sorting chars with [`cnt_sort`](CountingSort::cnt_sort()) sizes the count values vector by the
+    /// distance of the smallest and the largest char, which may be large for text that mixes ASCII
+    /// with a non-ASCII script. With the bounds of a contiguous block, e.g. Cyrillic (`'\u{0400}'` to
+    /// `'\u{04FF}'`), the count values vector is always sized to the width of that block and the
+    /// minimum and maximum value need not be searched. Every char is checked against the block before
+    /// counting, a char outside of it is an error instead of a silently enlarged allocation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec: Vec<char> = "гвбда".chars().collect();
+    /// let sorted_vec = vec.iter().cnt_sort_chars_block('\u{0400}', '\u{04FF}').unwrap();
+    ///
+    /// assert_eq!("абвгд", sorted_vec.into_iter().collect::<String>());
+    ///
+    /// // 'z' is not a Cyrillic char
+    /// let vec = vec!['б', 'z', 'а'];
+    /// assert!(vec.iter().cnt_sort_chars_block('\u{0400}', '\u{04FF}').is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IndexOutOfBounds`] when
+    ///   a char is not within `[block_start, block_end]`
+    /// * otherwise the same as [`cnt_sort_min_max`](CountingSort::cnt_sort_min_max()) with
+    ///   `block_start` and `block_end` as the minimum and maximum value
+    fn cnt_sort_chars_block(
+        self,
+        block_start: char,
+        block_end: char,
+    ) -> Result<Vec<char>, CountingSortError>
+    where
+        T: Into<char>,
+    {
+        counting_sort_chars_block(self, block_start, block_end)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) with the counting sort algorithm
+    /// and returns a [`Peekable`](std::iter::Peekable) [`SortedRuns`] over the sorted elements.
+    ///
+    /// Peeking the next smallest element without consuming it is what a k-way merge of several
+    /// sorted outputs needs: the stream with the smallest head is advanced, while all other streams
+    /// keep their head. The sorted elements are never materialized, the stream only holds the
+    /// distinct values with their counts and yields each value as often as it occurs. Hence a
+    /// stream needs `O(d)` memory while it is built and `O(k)` memory for `k` distinct values
+    /// afterwards, independent of the number of elements `n`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let first = vec![5, 1, 3];
+    /// let second = vec![4, 2, 6];
+    /// let mut first_stream = first.iter().cnt_sort_peekable().unwrap();
+    /// let mut second_stream = second.iter().cnt_sort_peekable().unwrap();
+    ///
+    /// let mut merged = Vec::new();
+    /// while let (Some(first_head), Some(second_head)) = (first_stream.peek(), second_stream.peek()) {
+    ///     if first_head <= second_head {
+    ///         merged.extend(first_stream.next());
+    ///     } else {
+    ///         merged.extend(second_stream.next());
+    ///     }
+    /// }
+    /// merged.extend(first_stream);
+    /// merged.extend(second_stream);
+    ///
+    /// assert_eq!(vec![1, 2, 3, 4, 5, 6], merged);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+    fn cnt_sort_peekable(self) -> Result<Peekable<SortedRuns<T>>, CountingSortError> {
+        counting_sort_peekable(self)
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) like
+    /// [`cnt_sort`](CountingSort::cnt_sort()) and additionally returns the working set, i.e. the
+    /// number of distinct buckets of the count values vector written during the count phase.
+    ///
+    /// Each distinct value is counted in its own bucket, hence the working set equals the number of
+    /// distinct values. Compared to the length of the count values vector, i.e. the distance `d`
+    /// plus one, it shows how sparse the written buckets are. Few buckets that are spread over a
+    /// large count values vector cause more cache misses than the same number of adjacent buckets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSort;
+    ///
+    /// let vec = vec![3, 1, 3, 1000, 1];
+    /// let (sorted_vec, working_set) = vec.iter().cnt_sort_with_working_set().unwrap();
+    ///
+    /// assert_eq!(vec![1, 1, 3, 3, 1000], sorted_vec);
+    /// // 3 out of 1000 buckets are written
+    /// assert_eq!(3, working_set);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+    fn cnt_sort_with_working_set(self) -> Result<(Vec<T>, usize), CountingSortError> {
+        counting_sort_with_working_set(self)
+    }
+}
+
+// Counting sort implementation for ITER with trait bound Iterator.
+// This enables that CountingSort is implemented for all implementors of
+// Iterator, especially for Vec, LinkedList and slice.
+impl<'a, T, ITER> CountingSort<'a, T> for ITER
+where
+    T: Ord + Copy + TryIntoIndex + 'a,
+    ITER: Sized + Iterator<Item = &'a T> + Clone,
+{
+}
+
+/// The interface for converting values into an index.
+///
+/// Index is always [`usize`](std::usize). Unfortunatelly
+/// [`TryInto`](std::convert::TryInto) for
+/// [`usize`](std::usize) is not sufficient since signed
+/// integers overflow when calculating `max_value - min_value`. Therefore this trait was added to
+/// implement an non-overflowing conversion to [`usize`](std::usize).
+///
+/// You can implement this trait yourself as long as there is a natural conversion from your type to
+/// [`usize`](std::usize). However it must hold for your type that if
+/// `t_1 <= t_2` then `YourType::try_into_index(t_1, min_value)? <= YourType::try_into_index(t_2, min_value)?`.
+/// Also consider that the size [`Vec`](std::vec::Vec) that holds the
+/// frequency of all elements in the collection is calculated like this
+///
+/// ```rust,compile_fail
+/// let length = YourType::try_into_index(max_value,min_value)? + 1;
+/// ```
+///
+/// It is not highly recommended to do this if your type's order is not simply dependent on one integer value
+/// of your struct.
+/// If it is, [`offset_index`] converts the distance of this integer value without overflowing.
+/// Newtypes around a single integer can use [`impl_try_into_index_newtype`] instead.
+///
+/// # Example
+///
+/// ```rust
+/// use core::cmp::{Ord, Ordering};
+/// use counting_sort::TryIntoIndex;
+///
+/// #[derive(Copy, Clone)]
+/// struct Person {
+///     name: &'static str,
+///     id: usize
+/// }
+///
+/// impl Ord for Person {
+///     fn cmp(&self, other: &Self) -> Ordering {
+///         self.id.cmp(&other.id)
+///     }
+/// }
+///
+/// impl PartialOrd for Person {
+///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+///         Some(self.cmp(other))
+///     }
+/// }
+///
+/// impl PartialEq for Person {
+///     fn eq(&self, other: &Self) -> bool {
+///         self.id == other.id
+///     }
+/// }
+///
+/// impl Eq for Person {}
+///
+/// impl TryIntoIndex for Person {
+///     type Error = &'static str;
+///     
+///     fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+///         Ok(value.id - min_value.id)
+///     }
+/// }
+///
+/// let john = Person { name: "John", id: 1234 };
+/// let min_value = Person { name: "Jim", id: 234 };
+/// let index_result = Person::try_into_index(&john, &min_value);
+/// assert!(index_result.is_ok());
+/// assert_eq!(1000, index_result.unwrap_or(0));
+/// ```
+pub trait TryIntoIndex {
+    /// The type returned whenever the conversion into an index failed.
+    type Error;
+
+    /// Tries to convert the value into an index.
+    ///
+    /// The `min_value` parameter is for calculating the offset between the actual value
+    /// and the minimum value. This concept is used in order to only allocate a
+    /// [`Vec`](std::vec::Vec) that only covers the
+    /// distance between the maximum value and the minimum value of the collection.
+    ///
+    /// # Errors
+    ///
+    /// Shall return an `Error` when converting value into an index (`usize`) fails.
+    fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error>;
+}
+
+// Macro is needed to implement TryIntoIndex for signed integers, which can overflow during
+// index conversion.
+macro_rules! try_into_index_impl_for_signed {
+    ($smaller_int:ty,$larger_int:ty) => {
+        impl TryIntoIndex for $smaller_int {
+            type Error = <$larger_int as TryInto<usize>>::Error;
+
+            fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+                // This conversion can only fail, if the larger integer type has a larger maximum
+                // value then usize. To-be-converted value is always be >= 0 as long as min_value <=
+                // value.
+                <$larger_int>::try_into(
+                    // convert smaller signed integer into larger signed integer to
+                    // avoid integer overflow for the smaller integer.
+                    // Example: 127 - (-128) overflows in i8 since 255 > 127 = i8::max_value().
+                    // It is safe to convert a smaller integer into a larger integer.
+                    <$larger_int>::from(*value) - <$larger_int>::from(*min_value),
+                )
+            }
+        }
+    };
+}
+
+// Macro used for unsigned integer implementations of TryIntoIndex.
+macro_rules! try_into_index_impl_for_unsigned {
+    ($unsigned:ty) => {
+        impl TryIntoIndex for $unsigned {
+            type Error = CountingSortError;
+
+            #[inline]
+            fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+                // Unsigned integer (e.g. u32) could be larger than usize on some HW.
+                // A value smaller than min_value fails like for the small unsigned integers.
+                value
+                    .checked_sub(*min_value)
+                    .and_then(|distance| <$unsigned>::try_into(distance).ok())
+                    .ok_or_else(CountingSortError::from_try_into_index_failed)
+            }
+        }
+    };
+}
+
+// Macro used for small unsigned integer implementations of TryIntoIndex.
+macro_rules! try_into_index_impl_for_small_unsigned {
+    ($unsigned:ty) => {
+        impl TryIntoIndex for $unsigned {
+            type Error = CountingSortError;
+            #[inline]
+            fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+                // u8 and u16 should always fit into an usize. Therefore no TryInto is needed.
+                value
+                    .checked_sub(*min_value)
+                    .map(usize::from)
+                    .ok_or_else(CountingSortError::from_try_into_index_failed)
+            }
+        }
+    };
+}
+
+// macro instances for signed integer implementation of TryIntoIndex
+try_into_index_impl_for_signed!(i8, i16);
+try_into_index_impl_for_signed!(i16, i32);
+try_into_index_impl_for_signed!(i32, i64);
+// i64 was not added, since i64 could be larger than usize and can also
+// result in huge memory consumption if the distance between max_value and
+// min_value of the collection is huge.
+
+// macro instances for small unsigned integer implementation of TryIntoIndex
+try_into_index_impl_for_small_unsigned!(u8);
+try_into_index_impl_for_small_unsigned!(u16);
+
+// macro instances for unsigned integer implementation of TryIntoIndex
+try_into_index_impl_for_unsigned!(u32);
+try_into_index_impl_for_unsigned!(usize);
+// u64 was not added, since i64 could be larger than usize and can also
+// result in huge memory consumption if the distance between max_value and
+// min_value of the collection is huge.
+
+// false is mapped to 0 and true to 1, which preserves the order of bool.
+impl TryIntoIndex for bool {
+    type Error = CountingSortError;
+
+    #[inline]
+    fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+        usize::from(*value)
+            .checked_sub(usize::from(*min_value))
+            .ok_or_else(CountingSortError::from_try_into_index_failed)
+    }
+}
+
+// A char is mapped to its Unicode scalar value, which preserves the order of char.
+impl TryIntoIndex for char {
+    type Error = CountingSortError;
+
+    #[inline]
+    fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+        offset_index(u32::from(*value), u32::from(*min_value))
+    }
+}
+
+/// The interface for converting values into an index with the help of a runtime context, an
+/// alternative to [`TryIntoIndex`].
+///
+/// The static [`try_into_index`](TryIntoIndex::try_into_index()) can not carry any runtime
+/// configuration, e.g. a base value or a scaling factor. This trait additionally receives a context
+/// of the type `C`, see [`cnt_sort_with_ctx`]. The same monotonicity contract as for
+/// [`TryIntoIndex`] applies for every context: if `t_1 <= t_2` then
+/// `T::try_into_index_with(t_1, min_value, ctx)? <= T::try_into_index_with(t_2, min_value, ctx)?`.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::TryIntoIndexWith;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// struct Millis(u32);
+///
+/// // the resolution in milliseconds, values are expected to be multiples of it
+/// struct Resolution(u32);
+///
+/// impl TryIntoIndexWith<Resolution> for Millis {
+///     type Error = &'static str;
+///
+///     fn try_into_index_with(
+///         value: &Self,
+///         min_value: &Self,
+///         ctx: &Resolution,
+///     ) -> Result<usize, Self::Error> {
+///         Ok(((value.0 - min_value.0) / ctx.0) as usize)
+///     }
+/// }
+///
+/// let index = Millis::try_into_index_with(&Millis(250), &Millis(50), &Resolution(50));
+/// assert_eq!(Ok(4), index);
+/// ```
+pub trait TryIntoIndexWith<C> {
+    /// The type returned whenever the conversion into an index failed.
+    type Error;
+
+    /// Tries to convert the value into an index using the context `ctx`.
+    ///
+    /// # Errors
+    ///
+    /// Shall return an `Error` when converting value into an index (`usize`) fails.
+    fn try_into_index_with(value: &Self, min_value: &Self, ctx: &C) -> Result<usize, Self::Error>;
+}
+
+/// Implements [`TryIntoIndex`] for tuple struct newtypes around a type that implements it.
+///
+/// The index of a newtype is the index of the wrapped value, hence the newtypes must order like
+/// their wrapped values, e.g. by deriving `Ord`, `PartialOrd`, `PartialEq` and `Eq`. For sorting
+/// they additionally need to derive `Copy` and `Clone`.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::{impl_try_into_index_newtype, CountingSort};
+///
+/// #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+/// struct UserId(u32);
+///
+/// #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+/// struct Temperature(i16);
+///
+/// impl_try_into_index_newtype!(UserId(u32), Temperature(i16));
+///
+/// let user_ids = vec![UserId(7), UserId(3)];
+/// assert_eq!(vec![UserId(3), UserId(7)], user_ids.iter().cnt_sort().unwrap());
+/// ```
+#[macro_export]
+macro_rules! impl_try_into_index_newtype {
+    ($($newtype:ident($inner:ty)),+ $(,)?) => {
+        $(
+            impl $crate::TryIntoIndex for $newtype {
+                type Error = <$inner as $crate::TryIntoIndex>::Error;
+
+                #[inline]
+                fn try_into_index(
+                    value: &Self,
+                    min_value: &Self,
+                ) -> ::core::result::Result<::core::primitive::usize, Self::Error> {
+                    <$inner as $crate::TryIntoIndex>::try_into_index(&value.0, &min_value.0)
+                }
+            }
+        )+
+    };
+}
+
+/// The interface for converting an index back into a value, the inverse of [`TryIntoIndex`].
+///
+/// It must hold that `T::try_from_index(T::try_into_index(value, min_value)?, min_value)? == value`.
+/// This is needed when the sorted elements are reconstructed from counts only, see
+/// [`reconstruct_sorted`]. It is implemented for the same integer types as [`TryIntoIndex`] and
+/// for [`bool`](std::bool).
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::TryFromIndex;
+///
+/// assert_eq!(Some(-3), i8::try_from_index(5, &-8).ok());
+/// assert!(u8::try_from_index(256, &0).is_err());
+/// ```
+pub trait TryFromIndex: Sized {
+    /// The type returned whenever the conversion from an index failed.
+    type Error;
+
+    /// Tries to convert the index, i.e. the distance to the minimum value, back into a value.
+    ///
+    /// # Errors
+    ///
+    /// The conversion fails if the resulting value is out of the range of the type.
+    fn try_from_index(index: usize, min_value: &Self) -> Result<Self, Self::Error>;
+}
+
+macro_rules! try_from_index_impl_for_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl TryFromIndex for $integer {
+                type Error = CountingSortError;
+
+                #[inline]
+                fn try_from_index(index: usize, min_value: &Self) -> Result<Self, Self::Error> {
+                    // i128 holds the sum of all supported integers and indices without overflow
+                    i128::try_from(index)
+                        .ok()
+                        .zip(i128::try_from(*min_value).ok())
+                        .and_then(|(index, min_value)| <$integer>::try_from(min_value + index).ok())
+                        .ok_or_else(CountingSortError::from_try_from_index_failed)
+                }
+            }
+        )*
+    };
+}
+
+try_from_index_impl_for_integer!(u8, u16, u32, usize, i8, i16, i32);
+
+impl TryFromIndex for bool {
+    type Error = CountingSortError;
+
+    #[inline]
+    fn try_from_index(index: usize, min_value: &Self) -> Result<Self, Self::Error> {
+        match usize::from(*min_value).checked_add(index) {
+            Some(0) => Ok(false),
+            Some(1) => Ok(true),
+            _ => Err(CountingSortError::from_try_from_index_failed()),
+        }
+    }
+}
+
+/// Reconstructs the sorted elements directly from a count values vector supplied by the caller,
+/// skipping the count phase entirely.
+///
+/// The indexing contract is `counts[i]` = frequency of the value `min_value + i`, i.e. there is no
+/// additional 0-th element. This is exactly the `counts` returned by
+/// [`cnt_sort_clamped_histogram`](CountingSort::cnt_sort_clamped_histogram()), hence this is its
+/// inverse. It is useful for repeated sorts over data whose distribution is already known.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::reconstruct_sorted;
+///
+/// let counts = [2, 0, 1, 3];
+///
+/// assert_eq!(vec![10, 10, 12, 13, 13, 13], reconstruct_sorted(&counts, &10).unwrap());
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::FromIndexFailed`] when
+///   converting an index with a non-zero count back into a value fails, i.e. `min_value + i` is out
+///   of the range of `T`
+/// * [`CountingSortError::IndexOutOfBounds`] when the sum of
+///   all counts overflows
+pub fn reconstruct_sorted<T>(counts: &[usize], min_value: &T) -> Result<Vec<T>, CountingSortError>
+where
+    T: Copy + TryFromIndex,
+{
+    let length = counts
+        .iter()
+        .try_fold(0_usize, |total, count| total.checked_add(*count))
+        .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+    let mut sorted_vector = Vec::with_capacity(length);
+    for (index, count) in counts.iter().enumerate() {
+        if *count > 0 {
+            let value = T::try_from_index(index, min_value)
+                .map_err(|_| CountingSortError::from_try_from_index_failed())?;
+            sorted_vector.resize(sorted_vector.len() + count, value);
+        }
+    }
+    Ok(sorted_vector)
+}
+
+/// Reconstructs the elements sorted in descending order directly from a count values vector
+/// supplied by the caller, the counterpart of [`reconstruct_sorted`].
+///
+/// The indexing contract is the same as for [`reconstruct_sorted`]. Reconstructing from the largest
+/// value down would write the output forward but read the counts backward. Instead, the counts are
+/// reversed first, so that both the counts are read and the output is written forward-sequentially.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::reconstruct_sorted_desc;
+///
+/// let counts = [2, 0, 1, 3];
+///
+/// assert_eq!(vec![13, 13, 13, 12, 10, 10], reconstruct_sorted_desc(&counts, &10).unwrap());
+/// ```
+///
+/// # Errors
+///
+/// Same as [`reconstruct_sorted`].
+pub fn reconstruct_sorted_desc<T>(
+    counts: &[usize],
+    min_value: &T,
+) -> Result<Vec<T>, CountingSortError>
+where
+    T: Copy + TryFromIndex,
+{
+    let length = counts
+        .iter()
+        .try_fold(0_usize, |total, count| total.checked_add(*count))
+        .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+    let reversed_counts: Vec<usize> = counts.iter().rev().copied().collect();
+    let largest_index = counts.len().saturating_sub(1);
+    let mut sorted_vector = Vec::with_capacity(length);
+    for (reversed_index, count) in reversed_counts.iter().enumerate() {
+        if *count > 0 {
+            let value = T::try_from_index(largest_index - reversed_index, min_value)
+                .map_err(|_| CountingSortError::from_try_from_index_failed())?;
+            sorted_vector.resize(sorted_vector.len() + count, value);
+        }
+    }
+    Ok(sorted_vector)
+}
+
+/// Checks the monotonicity contract of a [`TryIntoIndex`] implementation on the given samples.
+///
+/// The counting sort algorithm requires that `a <= b` implies
+/// `T::try_into_index(a, min_value)? <= T::try_into_index(b, min_value)?`. This function verifies
+/// this for all pairs of `samples`, which must not be smaller than `min_value`, so that custom
+/// implementations can be validated in their own tests. Since the contract is transitive, the
+/// samples are ordered once and only neighbouring runs of equal samples are compared, hence the
+/// runtime is `O(n log n)`. Equal samples must have a single index, since `a <= b` and `b <= a`
+/// hold for them, and each run must not have a smaller index than the strictly smaller samples.
+/// The result does not depend on the order of `samples`.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::check_try_into_index_monotonic;
+///
+/// assert!(check_try_into_index_monotonic(&[-3_i8, 100, 7, -128], &-128).is_ok());
+/// ```
+///
+/// # Errors
+///
+/// A descriptive message referring to the positions of the samples in `samples` when
+///
+/// * converting a sample into an index fails
+/// * two samples violate the contract, i.e. `a <= b` but the index of `a` is larger than the index
+///   of `b`, this includes equal samples with different indices
+// captured identifiers in format strings require a newer Rust version than the minimum one of this crate
+#[allow(clippy::uninlined_format_args)]
+pub fn check_try_into_index_monotonic<T>(samples: &[T], min_value: &T) -> Result<(), String>
+where
+    T: TryIntoIndex + Ord + Copy,
+{
+    let mut indices = Vec::with_capacity(samples.len());
+    for (position, sample) in samples.iter().enumerate() {
+        let index = T::try_into_index(sample, min_value).map_err(|_| {
+            format!(
+                "converting samples[{}] into an index failed, is it smaller than min_value?",
+                position
+            )
+        })?;
+        indices.push(index);
+    }
+    let mut order: Vec<usize> = (0..samples.len()).collect();
+    order.sort_by(|a, b| samples[*a].cmp(&samples[*b]));
+    // the position of the sample with the largest index of all strictly smaller samples
+    let mut optional_smaller: Option<usize> = None;
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && samples[order[start]] == samples[order[end]] {
+            end += 1;
+        }
+        let run = &order[start..end];
+        start = end;
+        let lowest = *run
+            .iter()
+            .min_by_key(|position| indices[**position])
+            .unwrap_or(&run[0]);
+        let highest = *run
+            .iter()
+            .max_by_key(|position| indices[**position])
+            .unwrap_or(&run[0]);
+        if indices[lowest] != indices[highest] {
+            return Err(format!(
+                "samples[{}] == samples[{}] but their indices {} != {} differ",
+                lowest, highest, indices[lowest], indices[highest]
+            ));
+        }
+        if let Some(smaller) = optional_smaller {
+            if indices[smaller] > indices[lowest] {
+                return Err(format!(
+                    "samples[{}] <= samples[{}] but their indices {} > {} are not monotonic",
+                    smaller, lowest, indices[smaller], indices[lowest]
+                ));
+            }
+        }
+        optional_smaller = Some(highest);
+    }
+    Ok(())
+}
+
+/// Sorts [`i32`](https://doc.rust-lang.org/std/primitive.i32.html) values with the counting sort
+/// algorithm by storing an explicit bias instead of widening each value.
+///
+/// The generic implementation converts every value into an index by widening it into an
+/// [`i64`](https://doc.rust-lang.org/std/primitive.i64.html), subtracting the widened minimum
+/// value and converting the difference with [`TryInto`](std::convert::TryInto). This function
+/// instead biases the values by flipping their sign bit, which maps `i32::MIN..=i32::MAX` onto
+/// `0..=u32::MAX` while preserving the order. The distance is then computed and checked once, the
+/// index of each value in the hot loops is a plain unsigned subtraction of the biased minimum
+/// value. The result is identical to [`cnt_sort`](CountingSort::cnt_sort()).
+///
+/// Only available with the `test-utils` feature, since the `signed_bias` benchmark does not show
+/// it to be faster than [`cnt_sort`](CountingSort::cnt_sort()) yet.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_i32_biased;
+///
+/// let vec = vec![3, -70_000, -5, 3];
+///
+/// assert_eq!(vec![-70_000, -5, 3, 3], cnt_sort_i32_biased(vec.iter()).unwrap());
+/// ```
+///
+/// # Errors
+///
+/// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+#[cfg(feature = "test-utils")]
+pub fn cnt_sort_i32_biased<'a, ITER>(iterator: ITER) -> Result<Vec<i32>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a i32> + Clone,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        if min_value == max_value {
+            if iterator.clone().nth(1).is_none() {
+                // a single element is trivially sorted
+                return Ok(vec![*min_value]);
+            }
+            return Err(CountingSortError::from_sorting_unnecessary());
+        }
+        let biased_min_value = bias_i32(*min_value);
+        // max_value > min_value, hence the biased subtraction cannot underflow
+        let distance = usize::try_from(bias_i32(*max_value) - biased_min_value)
+            .map_err(|_| CountingSortError::from_try_into_index_failed())?;
+        // additional 0-th element like in count_values
+        let length = distance
+            .checked_add(2)
+            .ok_or_else(CountingSortError::from_try_into_index_failed)?;
+        let mut count_vector = vec![0; length];
+        let index_of_biased = |value: &i32| {
+            // values are not smaller than min_value, hence the wrapping subtraction never wraps
+            usize::try_from(bias_i32(*value).wrapping_sub(biased_min_value)).ok()
+        };
+        for value in iterator.clone() {
+            let count = index_of_biased(value)
+                .and_then(|index| index.checked_add(1))
+                .and_then(|index| count_vector.get_mut(index))
+                .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+            *count += 1;
+        }
+        let number_of_elements = count_total(&count_vector)?;
+        calculate_prefix_sum(&mut count_vector);
+        let mut sorted_vector = vec![*min_value; number_of_elements];
+        let mut number_of_placed_elements: usize = 0;
+        for value in iterator {
+            number_of_placed_elements += 1;
+            let position = index_of_biased(value)
+                .and_then(|index| count_vector.get_mut(index))
+                .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+            let element = sorted_vector
+                .get_mut(*position)
+                .ok_or_else(CountingSortError::from_non_deterministic_source)?;
+            *element = *value;
+            *position += 1;
+        }
+        // see re_order_into
+        if number_of_placed_elements != number_of_elements {
+            return Err(CountingSortError::from_non_deterministic_source());
+        }
+        Ok(sorted_vector)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+// Flips the sign bit, which maps i32::MIN..=i32::MAX onto 0..=u32::MAX preserving the order.
+#[cfg(feature = "test-utils")]
+#[inline]
+fn bias_i32(value: i32) -> u32 {
+    u32::from_ne_bytes(value.to_ne_bytes()) ^ 0x8000_0000
+}
+
+/// Converts the distance between an integer value and the minimum value into an index without
+/// overflowing.
+///
+/// This encapsulates the tricky part of implementing [`TryIntoIndex`] for your own type whose order
+/// depends on one integer field: both values are widened to [`i128`](std::i128), subtracted with an
+/// overflow check and converted into [`usize`](std::usize). Therefore it works for all integers
+/// that can be converted into [`i128`](std::i128).
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::{offset_index, CountingSortError, TryIntoIndex};
+///
+/// #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// struct Reading {
+///     timestamp: i64,
+/// }
+///
+/// impl TryIntoIndex for Reading {
+///     type Error = CountingSortError;
+///
+///     fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+///         offset_index(value.timestamp, min_value.timestamp)
+///     }
+/// }
+///
+/// assert_eq!(Some(3), offset_index(-1_i8, -4_i8).ok());
+/// assert!(offset_index(i128::max_value(), i128::min_value()).is_err());
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::IntoIndexFailed`] when `value` is
+///   smaller than `min_value` or the distance does not fit into [`i128`](std::i128) or
+///   [`usize`](std::usize)
+pub fn offset_index<I: Into<i128>>(value: I, min_value: I) -> Result<usize, CountingSortError> {
+    value
+        .into()
+        .checked_sub(min_value.into())
+        .and_then(|distance| usize::try_from(distance).ok())
+        .ok_or_else(CountingSortError::from_try_into_index_failed)
+}
+
+// Maps an f32 to an u32 key with the same order as OrderedFloat, i.e. all NaNs are equal and the
+// largest value and -0.0 equals 0.0. Negative floats are ordered inversely to their bits, hence
+// all their bits are flipped, positive floats are moved above them by setting the sign bit.
+#[inline]
+fn float_key(value: f32) -> u32 {
+    if value.is_nan() {
+        // directly after the key of infinity
+        0xff80_0001
+    } else if value == 0.0 {
+        0x8000_0000
+    } else if value.is_sign_negative() {
+        !value.to_bits()
+    } else {
+        value.to_bits() | 0x8000_0000
+    }
+}
+
+/// The order of equal elements, see
+/// [`cnt_sort_with_stability`](CountingSort::cnt_sort_with_stability()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stability {
+    /// Equal elements keep their input order, the default.
+    Stable,
+    /// Equal elements are in reverse input order, i.e. later elements sort first.
+    ReverseStable,
+}
+
+// #[default] on enum variants requires a newer Rust version than the minimum one of this crate
+#[allow(clippy::derivable_impls)]
+impl Default for Stability {
+    fn default() -> Self {
+        Stability::Stable
+    }
+}
+
+/// What [`SortStrategy::sort`] does when the distance between the minimum and the maximum value
+/// exceeds the configured maximum distance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fallback {
+    /// Sort the collected elements with
+    /// [`sort_unstable`](https://doc.rust-lang.org/std/primitive.slice.html#method.sort_unstable)
+    /// of the standard library.
+    Std,
+    /// Fail with [`CountingSortError::DistanceTooLarge`], the default.
+    Error,
+}
+
+// #[default] on enum variants requires a newer Rust version than the minimum one of this crate
+#[allow(clippy::derivable_impls)]
+impl Default for Fallback {
+    fn default() -> Self {
+        Fallback::Error
+    }
+}
+
+/// A builder for sorting with the counting sort algorithm only as long as the distance between the
+/// minimum and the maximum value is small enough.
+///
+/// The counting sort algorithm allocates a count values vector of the size of the distance `d`,
+/// which is wasteful or even impossible for a large `d`. A strategy configures the largest
+/// acceptable distance with [`max_distance`](SortStrategy::max_distance()) and what happens
+/// beyond it with [`fallback`](SortStrategy::fallback()). A distance that can not be converted into
+/// an index, or for which the count values vector can not be sized, is always too large. By
+/// default every distance is acceptable and the fallback is [`Fallback::Error`], i.e.
+/// [`sort`](SortStrategy::sort()) behaves like [`cnt_sort`](CountingSort::cnt_sort()) and returns
+/// the same errors.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::{Fallback, SortStrategy};
+///
+/// let strategy = SortStrategy::new().max_distance(100).fallback(Fallback::Std);
+///
+/// let small_range = vec![30, 10, 20];
+/// assert_eq!(vec![10, 20, 30], strategy.sort(small_range.iter()).unwrap());
+///
+/// // sorted by the standard library, the distance 999999 exceeds the maximum distance
+/// let large_range = vec![1000000, 1, 500];
+/// assert_eq!(vec![1, 500, 1000000], strategy.sort(large_range.iter()).unwrap());
+///
+/// let strict_strategy = SortStrategy::new().max_distance(100);
+/// assert!(strict_strategy.sort(large_range.iter()).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortStrategy {
+    max_distance: usize,
+    fallback: Fallback,
+}
+
+impl Default for SortStrategy {
+    fn default() -> Self {
+        SortStrategy {
+            max_distance: usize::MAX,
+            fallback: Fallback::default(),
+        }
+    }
+}
+
+impl SortStrategy {
+    /// Creates the default strategy, see [`SortStrategy`].
+    #[must_use]
+    pub fn new() -> Self {
+        SortStrategy::default()
+    }
+
+    /// Sets the largest distance between the minimum and the maximum value that is still sorted
+    /// with the counting sort algorithm.
+    #[must_use]
+    pub fn max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Sets what happens when the distance exceeds the maximum distance.
+    #[must_use]
+    pub fn fallback(mut self, fallback: Fallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Sorts the elements in the [`Iterator`](std::iter::Iterator) according to the strategy.
+    ///
+    /// The minimum and maximum value are determined first. If their distance is within the maximum
+    /// distance, the elements are sorted by [`cnt_sort`](CountingSort::cnt_sort()). Otherwise
+    /// [`Fallback::Std`] sorts them with `sort_unstable`, which may reorder equal elements, and
+    /// [`Fallback::Error`] fails.
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::DistanceTooLarge`] when the
+    ///   distance exceeds the maximum distance and the fallback is [`Fallback::Error`]
+    /// * [`CountingSortError::IntoIndexFailed`] like for [`cnt_sort`](CountingSort::cnt_sort())
+    ///   when the distance can not be converted into an index or the count values vector can not
+    ///   be sized, and the fallback is [`Fallback::Error`]
+    /// * otherwise the same as [`cnt_sort`](CountingSort::cnt_sort()), the fallback
+    ///   [`Fallback::Std`] only fails for an empty iterator
+    pub fn sort<'a, ITER, T>(&self, iterator: ITER) -> Result<Vec<T>, CountingSortError>
+    where
+        ITER: Iterator<Item = &'a T> + Clone,
+        T: Ord + Copy + TryIntoIndex + 'a,
+    {
+        let (min_value, max_value) = get_min_max(&mut iterator.clone())
+            .ok_or_else(CountingSortError::from_empty_iterator)?;
+        /*
+          A distance that can not be converted into an index, or for which the length of the count
+          values vector overflows, is too large for any maximum distance.
+        */
+        let optional_distance = T::try_into_index(max_value, min_value)
+            .ok()
+            .filter(|distance| distance.checked_add(2).is_some());
+        match (optional_distance, self.fallback) {
+            (Some(distance), _) if distance <= self.max_distance => {
+                if min_value == max_value && iterator.clone().nth(1).is_none() {
+                    // a single element is trivially sorted
+                    return Ok(vec![*min_value]);
+                }
+                counting_sort_min_max(iterator, min_value, max_value)
+            }
+            (_, Fallback::Std) => {
+                let mut sorted_vector: Vec<T> = iterator.copied().collect();
+                // collect may leave spare capacity if the size hint of the iterator is not exact
+                sorted_vector.shrink_to_fit();
+                sorted_vector.sort_unstable();
+                Ok(sorted_vector)
+            }
+            // the same error as the one of cnt_sort
+            (None, Fallback::Error) => Err(CountingSortError::from_try_into_index_failed()),
+            (Some(_), Fallback::Error) => Err(CountingSortError::from_distance_too_large()),
+        }
+    }
+}
+
+/// The output order of [`cnt_sort_ordered`](CountingSort::cnt_sort_ordered()), selected at compile
+/// time by the marker types [`Ascending`] and [`Descending`].
+///
+/// The order maps the index of a value, i.e. its distance to the minimum value, to its bucket in
+/// the count vector. Since the mapping is monomorphized, the direction costs no runtime branch.
+/// Equal elements always keep their input order.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::{CountingSort, Descending};
+///
+/// let vec = vec![2, 3, 1];
+/// let sorted_vec = vec.iter().cnt_sort_ordered::<Descending>().unwrap();
+///
+/// assert_eq!(vec![3, 2, 1], sorted_vec);
+/// ```
+pub trait Order {
+    /// Returns the bucket of the value with the given `index`, where `distance` is the largest
+    /// index. Every index within `[0, distance]` must be mapped to a distinct bucket within
+    /// `[0, distance]`.
+    fn bucket(index: usize, distance: usize) -> usize;
+}
+
+/// Sorts in ascending order, i.e. the smallest value first, see [`Order`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Ascending;
+
+impl Order for Ascending {
+    #[inline]
+    fn bucket(index: usize, _distance: usize) -> usize {
+        index
+    }
+}
+
+/// Sorts in descending order, i.e. the largest value first, see [`Order`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Descending;
+
+impl Order for Descending {
+    #[inline]
+    fn bucket(index: usize, distance: usize) -> usize {
+        distance - index
+    }
+}
+
+/// Where [`cnt_sort_floats`] puts NaNs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NanPolicy {
+    /// Fail with [`CountingSortError::NanEncountered`] if there is a NaN.
+    Error,
+    /// Put all NaNs before the smallest value.
+    First,
+    /// Put all NaNs after the largest value, like
+    /// [`OrderedFloat`](https://docs.rs/ordered-float) does.
+    Last,
+}
+
+// An f32 ordered by its key, so that the non-NaN floats can be sorted by the generic implementation.
+#[derive(Debug, Clone, Copy)]
+struct FloatByKey {
+    key: u32,
+    value: f32,
+}
+
+impl Ord for FloatByKey {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl PartialOrd for FloatByKey {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for FloatByKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for FloatByKey {}
+
+impl TryIntoIndex for FloatByKey {
+    type Error = CountingSortError;
+
+    #[inline]
+    fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+        offset_index(value.key, min_value.key)
+    }
+}
+
+/// Sorts [`f32`](std::f32)s stably with the counting sort algorithm and puts NaNs according to the
+/// given [`NanPolicy`].
+///
+/// Each float is mapped to an [`u32`](std::u32) key with the same order: the bits of negative floats
+/// are flipped, since their order is inverse to their bits, and positive floats get the sign bit
+/// set, so that they are larger than all negative floats. -0.0 is mapped to the key of 0.0, i.e.
+/// they are equal. NaNs are not counted at all but kept separately in their input order and put
+/// before or after all other floats, or rejected. For [`OrderedFloat`](https://docs.rs/ordered-float)
+/// map the floats with `into_inner` first.
+///
+/// Every representable [`f32`](std::f32) needs its own index, hence the distance `d` grows quickly:
+/// all floats between 1.0 and 2.0 already need `2²³` indices.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::{cnt_sort_floats, NanPolicy};
+///
+/// let floats = vec![1.5, f32::NAN, 1.25, 1.0];
+///
+/// let sorted_floats = cnt_sort_floats(floats.iter(), NanPolicy::First).unwrap();
+/// assert!(sorted_floats[0].is_nan());
+/// assert_eq!(vec![1.0, 1.25, 1.5], sorted_floats[1..].to_vec());
+///
+/// assert!(cnt_sort_floats(floats.iter(), NanPolicy::Error).is_err());
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::IntoIndexFailed`] when
+///   converting into an index fails, this could happen if the distance `d` is larger than
+///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+/// * [`CountingSortError::IteratorEmpty`] when the iterator
+///   is empty
+/// * [`CountingSortError::NanEncountered`] when there is a
+///   NaN and the policy is [`NanPolicy::Error`]
+pub fn cnt_sort_floats<'a, ITER>(
+    iterator: ITER,
+    nan_policy: NanPolicy,
+) -> Result<Vec<f32>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a f32>,
+{
+    let mut nans = Vec::new();
+    let mut floats_by_key = Vec::new();
+    for value in iterator {
+        if value.is_nan() {
+            if nan_policy == NanPolicy::Error {
+                return Err(CountingSortError::from_nan_encountered());
+            }
+            nans.push(*value);
+        } else {
+            floats_by_key.push(FloatByKey {
+                key: float_key(*value),
+                value: *value,
+            });
+        }
+    }
+    let mut sorted_vector = Vec::with_capacity(nans.len() + floats_by_key.len());
+    if nan_policy == NanPolicy::First {
+        sorted_vector.extend_from_slice(&nans);
+    }
+    if let Some((min_value, max_value)) = get_min_max(&mut floats_by_key.iter()) {
+        let sorted_floats_by_key =
+            count_and_re_order(floats_by_key.iter(), min_value, max_value, *min_value)?;
+        sorted_vector.extend(sorted_floats_by_key.iter().map(|float| float.value));
+    } else if nans.is_empty() {
+        return Err(CountingSortError::from_empty_iterator());
+    }
+    if nan_policy == NanPolicy::Last {
+        sorted_vector.extend_from_slice(&nans);
+    }
+    Ok(sorted_vector)
+}
+
+/// Available with the `ordered-float` feature.
+///
+/// Every representable [`f32`](std::f32) is mapped to its own index, i.e. the values are quantized
+/// with the full resolution of [`f32`](std::f32) and the sort is exact. However the distance `d` of
+/// floats grows quickly: all floats between 1.0 and 2.0 already need `2²³` indices. For an
+/// approximate sort with a bounded count values vector see [`cnt_sort_quantized`].
+#[cfg(feature = "ordered-float")]
+impl TryIntoIndex for ordered_float::OrderedFloat<f32> {
+    type Error = CountingSortError;
+
+    #[inline]
+    fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+        offset_index(
+            float_key(value.into_inner()),
+            float_key(min_value.into_inner()),
+        )
+    }
+}
+
+/// Sorts [`OrderedFloat<f32>`](ordered_float::OrderedFloat) approximately by quantizing them into
+/// `buckets` buckets of equal width between `min_value` and `max_value`.
+///
+/// Available with the `ordered-float` feature. The count values vector has exactly `buckets` (plus
+/// one) elements. The result is approximately sorted: elements in different buckets are in
+/// ascending order, but elements within the same bucket keep their input order. With a bucket width
+/// smaller than the smallest difference between distinct values the result is sorted exactly.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_quantized;
+/// use ordered_float::OrderedFloat;
+///
+/// let floats = vec![OrderedFloat(0.7), OrderedFloat(0.1), OrderedFloat(0.5), OrderedFloat(0.3)];
+/// let sorted_floats = cnt_sort_quantized(floats.iter(), 0.0, 1.0, 10).unwrap();
+///
+/// assert_eq!(
+///     vec![OrderedFloat(0.1), OrderedFloat(0.3), OrderedFloat(0.5), OrderedFloat(0.7)],
+///     sorted_floats
+/// );
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::MinValueLargerMaxValue`] when
+///   `min_value` is not smaller than `max_value` or one of them is not finite
+/// * [`CountingSortError::IteratorEmpty`] when the iterator
+///   is empty
+/// * [`CountingSortError::IndexOutOfBounds`] when there are
+///   no buckets or a value is NaN or not within `[min_value, max_value]`
+#[cfg(feature = "ordered-float")]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn cnt_sort_quantized<'a, ITER>(
+    iterator: ITER,
+    min_value: f32,
+    max_value: f32,
+    buckets: usize,
+) -> Result<Vec<ordered_float::OrderedFloat<f32>>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a ordered_float::OrderedFloat<f32>> + Clone,
+{
+    if !(min_value.is_finite() && max_value.is_finite() && min_value < max_value) {
+        return Err(CountingSortError::from_min_value_larger_max_value());
+    }
+    if buckets == 0 {
+        return Err(CountingSortError::from_index_out_of_bounds());
+    }
+    let bucket_width = (f64::from(max_value) - f64::from(min_value)) / buckets as f64;
+    let bucket_of = |value: &ordered_float::OrderedFloat<f32>| {
+        let value = value.into_inner();
+        // NaN is not within the bounds as well
+        if !(min_value..=max_value).contains(&value) {
+            return Err(CountingSortError::from_index_out_of_bounds());
+        }
+        // value is within the bounds, hence the quotient is non-negative and at most buckets
+        let bucket = ((f64::from(value) - f64::from(min_value)) / bucket_width) as usize;
+        Ok(min(bucket, buckets - 1))
+    };
+    // additional 0-th element like in count_values
+    let mut count_vector: Vec<usize> = vec![0; buckets + 1];
+    for value in iterator.clone() {
+        count_vector[bucket_of(value)? + 1] += 1;
+    }
+    calculate_prefix_sum(&mut count_vector);
+    let length = *count_vector.last().unwrap_or(&0);
+    if length == 0 {
+        return Err(CountingSortError::from_empty_iterator());
+    }
+    let mut sorted_vector = vec![ordered_float::OrderedFloat(min_value); length];
+    for value in iterator {
+        let bucket = bucket_of(value)?;
+        let index = count_vector[bucket];
+        sorted_vector[index] = *value;
+        count_vector[bucket] = index + 1;
+    }
+    Ok(sorted_vector)
+}
+
+/// Checks whether the elements of two [`Iterator`](std::iter::Iterator)s are permutations of
+/// each other, i.e. whether they are equal multisets.
+///
+/// The count values vector of counting sort is a canonical fingerprint of a multiset. Therefore both
+/// iterators are counted over the same range and their count values vectors are compared, without
+/// sorting either of them. If the minimum or maximum values of both iterators differ, `false` is
+/// returned without counting at all.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::multiset_eq;
+///
+/// let a = vec![3, 1, 2, 3];
+/// let b = vec![3, 3, 2, 1];
+/// let c = vec![3, 1, 2, 2];
+///
+/// assert!(multiset_eq(a.iter(), b.iter()).unwrap());
+/// assert!(!multiset_eq(a.iter(), c.iter()).unwrap());
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::IntoIndexFailed`] when
+///   converting into an index fails, this could happen if the distance `d` is larger than
+///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+pub fn multiset_eq<'a, I, J, T>(mut a: I, mut b: J) -> Result<bool, CountingSortError>
+where
+    I: Iterator<Item = &'a T> + Clone,
+    J: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    match (get_min_max(&mut a.clone()), get_min_max(&mut b.clone())) {
+        // two empty iterators are equal
+        (None, None) => Ok(true),
+        (Some((a_min_value, a_max_value)), Some((b_min_value, b_max_value))) => {
+            if a_min_value != b_min_value || a_max_value != b_max_value {
+                return Ok(false);
+            }
+            let a_count_vector = count_values(&mut a, a_min_value, a_max_value)?;
+            let b_count_vector = count_values(&mut b, b_min_value, b_max_value)?;
+            Ok(a_count_vector == b_count_vector)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Sorts a text input holding one decimal integer per line with the counting sort algorithm.
+///
+/// Every line is parsed as an [`i64`](std::i64), leading and trailing whitespace is ignored, as well
+/// as empty lines. The input is read twice, first to identify the minimum and maximum value and then
+/// to count the frequency of every value. Therefore the reader must implement
+/// [`Seek`](std::io::Seek), the second read starts at the position the reader had when calling this
+/// function. The sorted [`Vec`](std::vec::Vec) is reconstructed directly from the count values,
+/// the parsed integers are never stored.
+///
+/// **<span style="color:red">Caution:</span>** Although the full range of [`i64`](std::i64) can be
+/// parsed, the distance `d` between the maximum and minimum value must fit into an
+/// [`usize`](std::usize), since it is converted into the length of the count values vector. On a 32-bit
+/// platform this fails for a distance larger than
+/// [`u32::max_value`](https://doc.rust-lang.org/nightly/std/primitive.u32.html#method.max_value)
+/// and memory consumption is `O(d)` in any case.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use counting_sort::cnt_sort_lines;
+///
+/// let input = Cursor::new("42\n-7\n13\n-7\n");
+/// let sorted_vec_result = cnt_sort_lines(input);
+///
+/// assert_eq!(vec![-7, -7, 13, 42], sorted_vec_result.unwrap());
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::ParseFailed`] when a line is not a valid [`i64`](std::i64)
+/// * [`CountingSortError::Io`] when reading or seeking the input fails, including a line that is not
+///   valid UTF-8
+/// * [`CountingSortError::IntoIndexFailed`] when
+///   the distance `d` is larger than
+///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+/// * [`CountingSortError::IteratorEmpty`] when the input holds no integer
+/// * [`CountingSortError::SortingUnnecessary`] when the input
+///   holds more than one integer and all integers are equal
+/// * [`CountingSortError::IndexOutOfBounds`] when the input changed between both reads or the
+///   count of a single value overflows
+/// * [`CountingSortError::ContractViolation`] when the number of integers overflows
+pub fn cnt_sort_lines<R>(mut reader: R) -> Result<Vec<i64>, CountingSortError>
+where
+    R: BufRead + Seek,
+{
+    let start_position = reader
+        .stream_position()
+        .map_err(|_| CountingSortError::from_io())?;
+    let mut optional_tuple: Option<(i64, i64)> = None;
+    let mut number_of_values: usize = 0;
+    for_each_integer_line(&mut reader, |value| {
+        optional_tuple = match optional_tuple {
+            Some((min_value, max_value)) => Some((min(min_value, value), max(max_value, value))),
+            None => Some((value, value)),
+        };
+        number_of_values = number_of_values.saturating_add(1);
+        Ok(())
+    })?;
+    let (min_value, max_value) =
+        optional_tuple.ok_or_else(CountingSortError::from_empty_iterator)?;
+    if min_value == max_value {
+        if number_of_values == 1 {
+            // a single integer is trivially sorted
+            return Ok(vec![min_value]);
+        }
+        return Err(CountingSortError::from_sorting_unnecessary());
+    }
+    // widen to i128, since the distance of two i64 may overflow i64
+    let distance = usize::try_from(i128::from(max_value) - i128::from(min_value))
+        .map_err(|_| CountingSortError::from_try_into_index_failed())?;
+    let length = distance
+        .checked_add(1)
+        .ok_or_else(CountingSortError::from_try_into_index_failed)?;
+    let mut count_vector: Vec<usize> = vec![0; length];
+
+    reader
+        .seek(SeekFrom::Start(start_position))
+        .map_err(|_| CountingSortError::from_io())?;
+    for_each_integer_line(&mut reader, |value| {
+        let optional_index = usize::try_from(i128::from(value) - i128::from(min_value))
+            .ok()
+            .filter(|index| *index < count_vector.len());
+        if let Some(index) = optional_index {
+            // a reader may yield more than usize::MAX lines, e.g. on a 16-bit target
+            count_vector[index] = count_vector[index]
+                .checked_add(1)
+                .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+            Ok(())
+        } else {
+            // the input changed between both reads
+            Err(CountingSortError::from_index_out_of_bounds())
+        }
+    })?;
+
+    let number_of_elements = count_total(&count_vector)?;
+    let mut sorted_vector = Vec::with_capacity(number_of_elements);
+    let mut value = min_value;
+    for count in count_vector {
+        sorted_vector.resize(sorted_vector.len() + count, value);
+        // wrapping is only possible after the maximum value was reached
+        value = value.wrapping_add(1);
+    }
+    Ok(sorted_vector)
+}
+
+// Parses each non-empty line of the reader into an i64 and calls f with it.
+fn for_each_integer_line<R, F>(reader: &mut R, mut f: F) -> Result<(), CountingSortError>
+where
+    R: BufRead,
+    F: FnMut(i64) -> Result<(), CountingSortError>,
+{
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let bytes_read = reader
+            .read_line(&mut line)
+            .map_err(|_| CountingSortError::from_io())?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+        let trimmed_line = line.trim();
+        if !trimmed_line.is_empty() {
+            let value = trimmed_line
+                .parse::<i64>()
+                .map_err(|_| CountingSortError::from_parse_failed())?;
+            f(value)?;
+        }
+    }
+}
+
+/// Sorts the elements of an [`Iterator`](std::iter::Iterator) over [`Cow`](std::borrow::Cow)s with
+/// the counting sort algorithm.
+///
+/// This handles a mix of borrowed and owned values, e.g. from parsing pipelines. Each
+/// [`Cow`](std::borrow::Cow) is dereferenced and its value copied once, afterwards the values are
+/// sorted like with [`cnt_sort`](CountingSort::cnt_sort()). Hence the iterator is consumed only
+/// once and does not need to implement [`Clone`](std::clone::Clone), but additional memory of the
+/// size of the input is allocated.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_cow;
+/// use std::borrow::Cow;
+///
+/// let borrowed = 42u32;
+/// let vec = vec![Cow::Owned(7), Cow::Borrowed(&borrowed), Cow::Owned(1)];
+///
+/// assert_eq!(vec![1, 7, 42], cnt_sort_cow(vec.into_iter()).unwrap());
+/// ```
+///
+/// # Errors
+///
+/// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+pub fn cnt_sort_cow<'a, ITER, T>(iterator: ITER) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = Cow<'a, T>>,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let values: Vec<T> = iterator.map(|value| *value).collect();
+    counting_sort(values.iter())
+}
+
+/// Sorts the elements of a fixed-size array with the counting sort algorithm and returns them as a new
+/// sorted array.
+///
+/// In contrast to [`cnt_sort`](CountingSort::cnt_sort()) no [`Vec`](std::vec::Vec) is allocated for
+/// the sorted elements, only the count values vector of size `d` is allocated on the heap. This is
+/// useful for small fixed-size datasets.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_array;
+///
+/// let array: [u8; 5] = [9, 3, 7, 3, 1];
+///
+/// assert_eq!([1, 3, 3, 7, 9], cnt_sort_array(array).unwrap());
+/// ```
+///
+/// # Errors
+///
+/// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+pub fn cnt_sort_array<T, const N: usize>(array: [T; N]) -> Result<[T; N], CountingSortError>
+where
+    T: Ord + Copy + TryIntoIndex,
+{
+    let optional_tuple = get_min_max(&mut array.iter());
+    if let Some((min_value, max_value)) = optional_tuple {
+        if N == 1 {
+            // a single element is trivially sorted
+            return Ok(array);
+        }
+        if min_value == max_value {
+            return Err(CountingSortError::from_sorting_unnecessary());
+        }
+        let mut count_vector = count_values(&mut array.iter(), min_value, max_value)?;
+        calculate_prefix_sum(&mut count_vector);
+        // the array itself is a valid fill value, every element is overwritten
+        let mut sorted_array = array;
+        re_order_into(
+            array.iter(),
+            &mut count_vector,
+            min_value,
+            &mut sorted_array,
+        )?;
+        Ok(sorted_array)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+/// Sorts the rows of row-major tabular data stably by the value in the column `col`.
+///
+/// The rows are moved into their sorted position, no row is copied. Only the values of the column
+/// are counted, hence the count values vector depends on the distance `d` of this column. If there
+/// are no rows or all rows have the same value in this column, the rows are returned unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_rows_by_column;
+///
+/// let rows = vec![vec![1, 30], vec![2, 10], vec![3, 20]];
+///
+/// assert_eq!(
+///     vec![vec![2, 10], vec![3, 20], vec![1, 30]],
+///     cnt_sort_rows_by_column(rows, 1).unwrap()
+/// );
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::ColumnOutOfBounds`] when a row is
+///   shorter than `col + 1`
+pub fn cnt_sort_rows_by_column(
+    rows: Vec<Vec<u16>>,
+    col: usize,
+) -> Result<Vec<Vec<u16>>, CountingSortError> {
+    let keys = rows
+        .iter()
+        .map(|row| row.get(col).copied())
+        .collect::<Option<Vec<u16>>>()
+        .ok_or_else(CountingSortError::from_column_out_of_bounds)?;
+    let optional_tuple = get_min_max(&mut keys.iter());
+    let (min_value, max_value) = match optional_tuple {
+        Some((min_value, max_value)) if min_value != max_value => (min_value, max_value),
+        _ => return Ok(rows),
+    };
+    let mut count_vector = count_values(&mut keys.iter(), min_value, max_value)?;
+    calculate_prefix_sum(&mut count_vector);
+    // rows are moved, hence the empty vectors are only placeholders that do not allocate
+    let mut sorted_rows: Vec<Vec<u16>> = vec![Vec::new(); rows.len()];
+    for (key, row) in keys.iter().zip(rows) {
+        // all keys are between min_value and max_value, the count vector covers all indices
+        let index_count_vector = usize::from(key - min_value);
+        let index = count_vector[index_count_vector];
+        sorted_rows[index] = row;
+        count_vector[index_count_vector] = index + 1;
+    }
+    Ok(sorted_rows)
+}
+
+/// Sorts the elements of the slice with the counting sort algorithm in parallel using
+/// [rayon](https://docs.rs/rayon).
+///
+/// Available with the `rayon` feature. Both the count phase and the reconstruction are executed in
+/// parallel:
+///
+/// 1. The slice is split into one chunk per thread and each chunk is counted into its own count
+///    values vector of size `d`, which are then summed up.
+/// 2. The prefix sum of the counts gives each bucket a non-overlapping range in the output, hence
+///    the buckets are split into ranges and each thread fills the disjoint output region of its
+///    bucket range.
+///
+/// The sort is unstable, since the output is reconstructed from the counts: each bucket holds one
+/// representative value that is copied for all of its elements. For integers this makes no
+/// difference. Note that every thread allocates its own count values vector during the count phase,
+/// i.e. the memory consumption of the count phase is multiplied by the number of threads.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::par_cnt_sort_unstable;
+///
+/// let vec: Vec<u16> = vec![9, 3, 7, 3, 1];
+///
+/// assert_eq!(vec![1, 3, 3, 7, 9], par_cnt_sort_unstable(&vec).unwrap());
+/// ```
+///
+/// # Errors
+///
+/// Same as [`cnt_sort`](CountingSort::cnt_sort()).
+#[cfg(feature = "rayon")]
+pub fn par_cnt_sort_unstable<T>(slice: &[T]) -> Result<Vec<T>, CountingSortError>
+where
+    T: Ord + Copy + TryIntoIndex + Send + Sync,
+{
+    use rayon::prelude::*;
+
+    let (min_value, max_value) = match (slice.par_iter().min(), slice.par_iter().max()) {
+        (Some(min_value), Some(max_value)) => (*min_value, *max_value),
+        _ => return Err(CountingSortError::from_empty_iterator()),
+    };
+    if min_value == max_value {
+        if slice.len() == 1 {
+            // a single element is trivially sorted
+            return Ok(slice.to_vec());
+        }
+        return Err(CountingSortError::from_sorting_unnecessary());
+    }
+    let length = index_of(&max_value, &min_value)?
+        .checked_add(1)
+        .ok_or_else(CountingSortError::from_try_into_index_failed)?;
+    let number_of_threads = rayon::current_num_threads();
+
+    let chunk_size = max(slice.len() / number_of_threads, 1);
+    let buckets = slice
+        .par_chunks(chunk_size)
+        .map(|chunk| {
+            // the representative of an empty bucket is never copied
+            let mut buckets = vec![(min_value, 0_usize); length];
+            for value in chunk {
+                let index = index_of(value, &min_value)?;
+                if index >= length {
+                    return Err(CountingSortError::from_index_out_of_bounds());
+                }
+                let bucket = &mut buckets[index];
+                if bucket.1 == 0 {
+                    bucket.0 = *value;
+                }
+                // like in count_values, the count of a single bucket must not overflow
+                bucket.1 = bucket
+                    .1
+                    .checked_add(1)
+                    .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+            }
+            Ok(buckets)
+        })
+        .try_reduce_with(|mut buckets, other_buckets| {
+            for (bucket, other_bucket) in buckets.iter_mut().zip(other_buckets) {
+                if bucket.1 == 0 {
+                    bucket.0 = other_bucket.0;
+                }
+                bucket.1 = bucket
+                    .1
+                    .checked_add(other_bucket.1)
+                    .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+            }
+            Ok(buckets)
+        })
+        // the slice is not empty, hence there is at least one chunk
+        .unwrap_or_else(|| Err(CountingSortError::from_empty_iterator()))?;
+
+    // split the buckets and the output into disjoint regions, one per thread
+    let mut sorted_vector = vec![min_value; slice.len()];
+    let buckets_per_region = max(length / number_of_threads, 1);
+    let mut regions = Vec::with_capacity(number_of_threads + 1);
+    let mut remaining_output = sorted_vector.as_mut_slice();
+    for region_buckets in buckets.chunks(buckets_per_region) {
+        let region_length = region_buckets.iter().map(|(_, count)| count).sum();
+        let (region_output, rest) = remaining_output.split_at_mut(region_length);
+        regions.push((region_buckets, region_output));
+        remaining_output = rest;
+    }
+    regions
+        .into_par_iter()
+        .for_each(|(region_buckets, region_output)| {
+            let mut start = 0;
+            for (value, count) in region_buckets {
+                for element in &mut region_output[start..start + count] {
+                    *element = *value;
+                }
+                start += count;
+            }
+        });
+    Ok(sorted_vector)
+}
+
+/// Groups the elements of the [`Iterator`](std::iter::Iterator) by the key returned by `key` and
+/// reduces the values returned by `val` within each group with `reduce`.
+///
+/// Returns one tuple of key and reduced value per distinct key in ascending key order. This fuses
+/// sorting, grouping and reducing, e.g. for summing up weights per key. Only the keys have to
+/// implement [`TryIntoIndex`], the elements can be arbitrary records. The keys are bucketed like in
+/// the counting sort algorithm, hence the runtime is `O(n + d)` where `d` is the distance between
+/// the largest and the smallest key. The values of a group are reduced in input order, i.e.
+/// `reduce(reduce(first, second), third)`.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_reduce_by_key;
+///
+/// let orders = vec![(3_u8, 10_u32), (1, 5), (3, 7), (1, 1)];
+/// let totals = cnt_sort_reduce_by_key(
+///     orders.iter(),
+///     |order| order.0,
+///     |order| order.1,
+///     |sum, weight| sum + weight,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(vec![(1, 6), (3, 17)], totals);
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::IntoIndexFailed`] when
+///   converting a key into an index fails, this could happen if the distance `d` is larger than
+///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+/// * [`CountingSortError::IteratorEmpty`] when the iterator
+///   is empty
+pub fn cnt_sort_reduce_by_key<'a, ITER, T, K, V, FK, FV, R>(
+    iterator: ITER,
+    key: FK,
+    val: FV,
+    reduce: R,
+) -> Result<Vec<(K, V)>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: 'a,
+    K: Ord + Copy + TryIntoIndex,
+    FK: Fn(&T) -> K,
+    FV: Fn(&T) -> V,
+    R: Fn(V, V) -> V,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone().map(&key));
+    if let Some((min_key, max_key)) = optional_tuple {
+        let length = index_of(&max_key, &min_key)?
+            .checked_add(1)
+            .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+        // one bucket per possible key, holding the key and the values reduced so far
+        let mut buckets: Vec<Option<(K, V)>> = Vec::new();
+        buckets.resize_with(length, || None);
+        for element in iterator {
+            let element_key = key(element);
+            let bucket = buckets
+                .get_mut(index_of(&element_key, &min_key)?)
+                .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+            let value = val(element);
+            *bucket = match bucket.take() {
+                Some((bucket_key, reduced_value)) => {
+                    Some((bucket_key, reduce(reduced_value, value)))
+                }
+                None => Some((element_key, value)),
+            };
+        }
+        Ok(buckets.into_iter().flatten().collect())
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+/// Sorts the values of `(value, weight)` pairs, where the weight is the number of occurrences of
+/// the value, i.e. expands the pairs into the sorted elements.
+///
+/// This is useful for histograms whose counts are already known, e.g. from an aggregated source:
+/// the count vector is built directly from the weights, hence the counting pass over the expanded
+/// elements is skipped entirely. A value may occur in several pairs, its weights are added up.
+/// Pairs with a weight of 0 are ignored, also for determining the range. The range is handled
+/// like in [`cnt_sort`](CountingSort::cnt_sort()), i.e. a single element is returned as is and
+/// more than one element of the same value is unnecessary to sort.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_weighted;
+///
+/// let histogram = vec![(3_u8, 2), (1, 1), (2, 0), (5, 3)];
+/// let sorted_vec = cnt_sort_weighted(histogram.into_iter()).unwrap();
+///
+/// assert_eq!(vec![1, 3, 3, 5, 5, 5], sorted_vec);
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::IntoIndexFailed`] when
+///   converting into an index fails, this could happen if the distance `d` is larger than
+///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+/// * [`CountingSortError::IndexOutOfBounds`] when the sum of all weights is larger than
+///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+/// * [`CountingSortError::IteratorEmpty`] when there are no pairs with a weight larger than 0
+/// * [`CountingSortError::SortingUnnecessary`] when all pairs have the same value and the sum of
+///   their weights is larger than 1
+pub fn cnt_sort_weighted<T, ITER>(pairs: ITER) -> Result<Vec<T>, CountingSortError>
+where
+    T: Ord + Copy + TryIntoIndex,
+    ITER: Iterator<Item = (T, usize)>,
+{
+    // the pairs are consumed twice, but there are usually far fewer pairs than expanded elements
+    let weighted_values: Vec<(T, usize)> = pairs.filter(|(_, weight)| *weight > 0).collect();
+    let optional_tuple = get_min_max(&mut weighted_values.iter().map(|(value, _)| *value));
+    if let Some((min_value, max_value)) = optional_tuple {
+        // the additional 0-th element enables the re-ordering, see count_values
+        let length = index_of(&max_value, &min_value)?
+            .checked_add(2)
+            .ok_or_else(CountingSortError::from_try_into_index_failed)?;
+        let mut count_vector: Vec<usize> = vec![0; length];
+        for (value, weight) in &weighted_values {
+            let count = index_of(value, &min_value)?
+                .checked_add(1)
+                .and_then(|index| count_vector.get_mut(index))
+                .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+            *count = (*count)
+                .checked_add(*weight)
+                .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+        }
+        let number_of_elements = count_total(&count_vector)
+            .map_err(|_| CountingSortError::from_index_out_of_bounds())?;
+        if min_value == max_value && number_of_elements > 1 {
+            return Err(CountingSortError::from_sorting_unnecessary());
+        }
+        calculate_prefix_sum(&mut count_vector);
+        let mut sorted_vector = vec![min_value; number_of_elements];
+        for (value, weight) in &weighted_values {
+            // the index was already checked while counting
+            let index = index_of(value, &min_value)?;
+            let start = count_vector[index];
+            let end = start + weight;
+            for element in sorted_vector
+                .get_mut(start..end)
+                .ok_or_else(CountingSortError::from_index_out_of_bounds)?
+            {
+                *element = *value;
+            }
+            count_vector[index] = end;
+        }
+        Ok(sorted_vector)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+/// Groups the payloads of `(key, payload)` pairs by their keys in ascending key order as flat
+/// columns.
+///
+/// Returns the tuple `(keys, payloads, offsets)`: `keys` holds the distinct keys in ascending
+/// order, `payloads` all payloads ordered by their keys and `offsets` the start of each group in
+/// `payloads` followed by the number of payloads. Hence the payloads of `keys[i]` are
+/// `payloads[offsets[i]..offsets[i + 1]]`. This is a cache-friendly alternative to
+/// `Vec<(T, Vec<V>)>`, see [`cnt_sort_grouped`](CountingSort::cnt_sort_grouped()), since only
+/// three [`Vec`](std::vec::Vec)s are allocated. The payloads are scattered stably like in the
+/// counting sort algorithm, i.e. the payloads of a group keep their input order, and equal keys
+/// are represented by their first occurrence.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_group_payloads;
+///
+/// let pairs = [(3_u8, 'a'), (1, 'b'), (3, 'c'), (1, 'd'), (2, 'e')];
+/// let (keys, payloads, offsets) = cnt_sort_group_payloads(&pairs).unwrap();
+///
+/// assert_eq!(vec![1, 2, 3], keys);
+/// assert_eq!(vec!['b', 'd', 'e', 'a', 'c'], payloads);
+/// assert_eq!(vec![0, 2, 3, 5], offsets);
+/// assert_eq!(&['a', 'c'], &payloads[offsets[2]..offsets[3]]);
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::IntoIndexFailed`] when
+///   converting a key into an index fails, this could happen if the distance `d` is larger than
+///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+/// * [`CountingSortError::IteratorEmpty`] when there are no pairs
+#[allow(clippy::type_complexity)]
+pub fn cnt_sort_group_payloads<T, V>(
+    pairs: &[(T, V)],
+) -> Result<(Vec<T>, Vec<V>, Vec<usize>), CountingSortError>
+where
+    T: Ord + Copy + TryIntoIndex,
+    V: Copy,
+{
+    let optional_tuple = get_min_max(&mut pairs.iter().map(|(key, _)| *key));
+    if let Some((min_key, max_key)) = optional_tuple {
+        // the additional 0-th element enables the re-ordering, see count_values
+        let length = index_of(&max_key, &min_key)?
+            .checked_add(2)
+            .ok_or_else(CountingSortError::from_try_into_index_failed)?;
+        let mut count_vector: Vec<usize> = vec![0; length];
+        let mut representatives: Vec<Option<T>> = vec![None; length - 1];
+        for (key, _) in pairs {
+            let index = index_of(key, &min_key)?;
+            if index >= representatives.len() {
+                return Err(CountingSortError::from_index_out_of_bounds());
+            }
+            if representatives[index].is_none() {
+                representatives[index] = Some(*key);
+            }
+            // there are at most pairs.len() elements, hence no overflow
+            count_vector[index + 1] += 1;
+        }
+        calculate_prefix_sum(&mut count_vector);
+        let mut keys = Vec::new();
+        let mut offsets = Vec::new();
+        for (representative, start) in representatives.iter().zip(&count_vector) {
+            if let Some(key) = representative {
+                keys.push(*key);
+                offsets.push(*start);
+            }
+        }
+        offsets.push(pairs.len());
+        let mut payloads = vec![pairs[0].1; pairs.len()];
+        for (key, payload) in pairs {
+            // the index was already checked while counting
+            let index = index_of(key, &min_key)?;
+            // the position of the payload, see re_order_into
+            let position = count_vector[index];
+            payloads[position] = *payload;
+            count_vector[index] = position + 1;
+        }
+        Ok((keys, payloads, offsets))
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+/// Sorts the elements of an [`Iterator`](std::iter::Iterator) with the counting sort algorithm,
+/// converting them into indices with [`TryIntoIndexWith`] and the runtime context `ctx`.
+///
+/// This enables types whose index depends on external configuration, e.g. a base value or a
+/// scaling factor. Apart from the conversion it behaves like
+/// [`cnt_sort`](CountingSort::cnt_sort()), i.e. the sort is stable. It is a free function, since
+/// [`CountingSort`] is only implemented for types implementing [`TryIntoIndex`].
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::{cnt_sort_with_ctx, TryIntoIndexWith};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// struct Millis(u32);
+///
+/// struct Resolution(u32);
+///
+/// impl TryIntoIndexWith<Resolution> for Millis {
+///     type Error = &'static str;
+///
+///     fn try_into_index_with(
+///         value: &Self,
+///         min_value: &Self,
+///         ctx: &Resolution,
+///     ) -> Result<usize, Self::Error> {
+///         Ok(((value.0 - min_value.0) / ctx.0) as usize)
+///     }
+/// }
+///
+/// let durations = vec![Millis(300), Millis(100), Millis(200)];
+/// let sorted_durations = cnt_sort_with_ctx(durations.iter(), &Resolution(100)).unwrap();
+///
+/// assert_eq!(vec![Millis(100), Millis(200), Millis(300)], sorted_durations);
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::IntoIndexFailed`] when
+///   converting into an index fails, this could happen if the distance `d` is larger than
+///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+/// * [`CountingSortError::IteratorEmpty`] when the iterator
+///   is empty
+/// * [`CountingSortError::SortingUnnecessary`] when the iterator
+///   contains more than one element and all elements are equal
+pub fn cnt_sort_with_ctx<'a, ITER, T, C>(
+    iterator: ITER,
+    ctx: &C,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndexWith<C> + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        if min_value == max_value {
+            if iterator.clone().nth(1).is_none() {
+                // a single element is trivially sorted
+                return Ok(vec![*min_value]);
+            }
+            return Err(CountingSortError::from_sorting_unnecessary());
+        }
+        let index_with_ctx = |value: &T| {
+            T::try_into_index_with(value, min_value, ctx)
+                .map_err(|_| CountingSortError::from_try_into_index_failed())
+        };
+        let distance = index_with_ctx(max_value)?;
+        sort_by_bucket(iterator, *min_value, distance, index_with_ctx)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+/// Sorts the keys in place and applies the same permutation to the payload, so that both stay in
+/// sync.
+///
+/// The stable permutation is computed from the keys with the counting sort algorithm, see
+/// [`cnt_sort_permutations`](CountingSort::cnt_sort_permutations()), and then applied in place to
+/// both slices by following the cycles of the permutation. Hence the payload elements are swapped,
+/// not copied, and the payload type does not need to implement [`Copy`](std::marker::Copy).
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_with_payload;
+///
+/// let mut keys: Vec<u16> = vec![3, 1, 2, 1];
+/// let mut names = vec!["three", "one", "two", "uno"];
+/// cnt_sort_with_payload(&mut keys, &mut names).unwrap();
+///
+/// assert_eq!(vec![1, 1, 2, 3], keys);
+/// assert_eq!(vec!["one", "uno", "two", "three"], names);
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::LengthMismatch`] when the keys and
+///   the payload differ in length
+/// * [`CountingSortError::IntoIndexFailed`] when
+///   converting into an index fails, this could happen if the distance `d` is larger than
+///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+///
+/// In case of an error both slices are unchanged.
+pub fn cnt_sort_with_payload<T, P>(
+    keys: &mut [T],
+    payload: &mut [P],
+) -> Result<(), CountingSortError>
+where
+    T: Ord + Copy + TryIntoIndex,
+{
+    if keys.len() != payload.len() {
+        return Err(CountingSortError::from_length_mismatch());
+    }
+    if keys.is_empty() {
+        return Ok(());
+    }
+    let mut inverse_permutation = counting_sort_inverse_permutation(keys.iter())?;
+    for position in 0..inverse_permutation.len() {
+        // move the element at position to its sorted position until the cycle is closed
+        while inverse_permutation[position] != position {
+            let sorted_position = inverse_permutation[position];
+            keys.swap(position, sorted_position);
+            payload.swap(position, sorted_position);
+            inverse_permutation.swap(position, sorted_position);
+        }
+    }
+    Ok(())
+}
+
+/// Counts the occurrences of every byte value without any heap allocation.
+///
+/// This is the minimal primitive of the counting sort algorithm for [`u8`](https://doc.rust-lang.org/std/primitive.u8.html):
+/// the returned array is stack-allocated and contains at index `i` the number of occurrences of
+/// the byte `i`. Since the distance `d` is fixed to `256` no minimum or maximum value has to be
+/// determined and the function cannot fail, an empty slice simply returns only zeros.
+///
+/// Prefix sums, histograms or the sorting itself are left to the caller.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_u8_counts;
+///
+/// let counts = cnt_sort_u8_counts(b"hello");
+///
+/// assert_eq!(2, counts[usize::from(b'l')]);
+/// assert_eq!(1, counts[usize::from(b'h')]);
+/// assert_eq!(5, counts.iter().sum::<usize>());
+/// ```
+#[must_use]
+pub fn cnt_sort_u8_counts(slice: &[u8]) -> [usize; 256] {
+    let mut counts = [0; 256];
+    for value in slice {
+        counts[usize::from(*value)] += 1;
+    }
+    counts
+}
+
+/// Sorts the bytes read from a [`Read`](std::io::Read) stream, e.g. a file of raw bytes, without
+/// collecting them into a [`Vec`](std::vec::Vec) first.
+///
+/// The stream is read in chunks of 8 KiB into a stack buffer and the bytes are counted into a
+/// `[usize; 256]` like in [`cnt_sort_u8_counts`]. Afterwards the sorted bytes are reconstructed
+/// from the counts, hence only the sorted [`Vec`](std::vec::Vec) is allocated. Reads that are
+/// interrupted are retried.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_read;
+/// use std::io::Cursor;
+///
+/// let sorted_bytes = cnt_sort_read(Cursor::new(b"counting")).unwrap();
+///
+/// assert_eq!(b"cginnotu".to_vec(), sorted_bytes);
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::Io`] when reading from the stream fails
+/// * [`CountingSortError::IteratorEmpty`] when the stream
+///   is empty
+/// * [`CountingSortError::SortingUnnecessary`] when the stream
+///   contains more than one byte and all bytes are equal
+pub fn cnt_sort_read<R: Read>(mut reader: R) -> Result<Vec<u8>, CountingSortError> {
+    let mut counts = [0_usize; 256];
+    let mut buffer = [0_u8; 8192];
+    let mut number_of_elements: usize = 0;
+    loop {
+        let bytes_read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(bytes_read) => bytes_read,
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) => return Err(CountingSortError::from_io()),
+        };
+        for value in &buffer[..bytes_read] {
+            counts[usize::from(*value)] += 1;
+        }
+        number_of_elements = number_of_elements
+            .checked_add(bytes_read)
+            .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+    }
+    if number_of_elements == 0 {
+        return Err(CountingSortError::from_empty_iterator());
+    }
+    if number_of_elements > 1 && counts.contains(&number_of_elements) {
+        return Err(CountingSortError::from_sorting_unnecessary());
+    }
+    let mut sorted_vector = Vec::with_capacity(number_of_elements);
+    for (value, count) in (0..=u8::MAX).zip(counts.iter()) {
+        sorted_vector.resize(sorted_vector.len() + count, value);
+    }
+    Ok(sorted_vector)
+}
+
+/// Sorts a slice of [`u8`](https://doc.rust-lang.org/std/primitive.u8.html) in place without any
+/// heap allocation.
+///
+/// The occurrences are counted into a stack-allocated `[usize; 256]`, see [`cnt_sort_u8_counts`],
+/// and the slice is then rewritten in place from these counts. Hence neither a count values
+/// vector nor a vector for the sorted elements is allocated, which makes this suitable for the
+/// tightest embedded scenarios.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_u8_inplace;
+///
+/// let mut bytes = *b"counting";
+/// cnt_sort_u8_inplace(&mut bytes).unwrap();
+///
+/// assert_eq!(b"cginnotu", &bytes);
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::IteratorEmpty`] when the slice
+///   is empty
+/// * [`CountingSortError::SortingUnnecessary`] when the slice
+///   contains more than one element and all elements are equal
+///
+/// In case of an error the slice is unchanged.
+pub fn cnt_sort_u8_inplace(slice: &mut [u8]) -> Result<(), CountingSortError> {
+    if slice.is_empty() {
+        return Err(CountingSortError::from_empty_iterator());
+    }
+    let counts = cnt_sort_u8_counts(slice);
+    if counts.contains(&slice.len()) {
+        if slice.len() == 1 {
+            // a single element is trivially sorted
+            return Ok(());
+        }
+        return Err(CountingSortError::from_sorting_unnecessary());
+    }
+    let mut position = 0;
+    for (value, count) in (0..=u8::MAX).zip(counts.iter()) {
+        slice[position..position + count].fill(value);
+        position += count;
+    }
+    Ok(())
+}
+
+/// Sorts a slice in place with the counting sort algorithm, using only the count values vector as
+/// additional memory.
+///
+/// The elements are moved by the cycle-leader permutation: the prefix sums of the count values
+/// vector are the next free position of each bucket. The element at the next free position of the
+/// current bucket is swapped to the next free position of its own bucket, until an element of the
+/// current bucket arrives, which then stays. Every swap places one element finally, hence the
+/// runtime is `O(n + d)` with `O(d)` additional memory, i.e. no `O(n)` vector for the sorted
+/// elements is allocated.
+///
+/// The sort is **not** stable, equal elements may be reordered by the swaps. Keeping them in their
+/// original order would need the rank of each element among its equal elements, i.e. `O(n)`
+/// memory again. Use [`cnt_sort_in_place_stable`] if the order of equal elements matters.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_in_place;
+///
+/// let mut slice = [-5_i16, 200, 3, -5, 0];
+/// cnt_sort_in_place(&mut slice).unwrap();
+///
+/// assert_eq!([-5, -5, 0, 3, 200], slice);
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::IntoIndexFailed`] when
+///   converting into an index fails, this could happen if the distance `d` is larger than
+///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+/// * [`CountingSortError::IteratorEmpty`] when the slice
+///   is empty
+/// * [`CountingSortError::SortingUnnecessary`] when the slice
+///   contains more than one element and all elements are equal
+///
+/// In case of an error the slice is unchanged.
+pub fn cnt_sort_in_place<T>(slice: &mut [T]) -> Result<(), CountingSortError>
+where
+    T: Ord + Copy + TryIntoIndex,
+{
+    if let Some((min_value, mut count_vector)) = count_in_place(slice)? {
+        /*
+          After calculating the prefix sum the element at index is the start of the bucket of index
+          and the element at index + 1 its end. The starts are advanced as next free positions,
+          hence the ends are kept separately.
+        */
+        let bucket_ends = count_vector[1..].to_vec();
+        for (bucket, bucket_end) in bucket_ends.iter().enumerate() {
+            while count_vector[bucket] < *bucket_end {
+                let position = count_vector[bucket];
+                // all indices were validated while counting
+                let index = index_of(&slice[position], &min_value)?;
+                if index != bucket {
+                    slice.swap(position, count_vector[index]);
+                }
+                count_vector[index] += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Sorts a slice in place and stably, using only the count values vector as additional memory.
+///
+/// In contrast to [`cnt_sort_in_place`] the elements are not placed by the counting sort algorithm.
+/// The buckets are split recursively in halves and the slice is partitioned accordingly, each
+/// partition is done stably in place by rotating blocks of elements. The count values vector only
+/// validates all indices before the slice is changed. This results in a runtime of
+/// `O(n log(n) log(d))` with `O(d)` additional memory, hence it is considerably slower than
+/// [`cnt_sort_in_place`] and [`cnt_sort`](CountingSort::cnt_sort()). It trades this runtime for
+/// both stability and not allocating the `O(n)` vector for the sorted elements.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_in_place_stable;
+///
+/// let mut slice = [-5_i16, 200, 3, -5, 0];
+/// cnt_sort_in_place_stable(&mut slice).unwrap();
+///
+/// assert_eq!([-5, -5, 0, 3, 200], slice);
+/// ```
+///
+/// # Errors
+///
+/// Same as [`cnt_sort_in_place`].
+///
+/// In case of an error the slice is unchanged.
+pub fn cnt_sort_in_place_stable<T>(slice: &mut [T]) -> Result<(), CountingSortError>
+where
+    T: Ord + Copy + TryIntoIndex,
+{
+    if let Some((min_value, count_vector)) = count_in_place(slice)? {
+        /*
+          After calculating the prefix sum the element at index + 1 is the end of the bucket of
+          index, i.e. the 0-th element is skipped, so that the start of the bucket is at index.
+        */
+        let bucket_ends = &count_vector[1..];
+        sort_buckets_in_place(slice, bucket_ends, 0, bucket_ends.len() - 1, &min_value);
+    }
+    Ok(())
+}
+
+// Checks the slice like cnt_sort and returns the minimum value and the prefix sums of the count
+// values vector, or None if the slice holds a single element, which is trivially sorted.
+#[allow(clippy::type_complexity)]
+fn count_in_place<T>(slice: &[T]) -> Result<Option<(T, Vec<usize>)>, CountingSortError>
+where
+    T: Ord + Copy + TryIntoIndex,
+{
+    let (min_value, max_value) =
+        get_min_max(&mut slice.iter()).ok_or_else(CountingSortError::from_empty_iterator)?;
+    let (min_value, max_value) = (*min_value, *max_value);
+    if min_value == max_value {
+        if slice.len() == 1 {
+            return Ok(None);
+        }
+        return Err(CountingSortError::from_sorting_unnecessary());
+    }
+    let mut count_vector = count_values(&mut slice.iter(), &min_value, &max_value)?;
+    calculate_prefix_sum(&mut count_vector);
+    Ok(Some((min_value, count_vector)))
+}
+
+// Sorts the slice holding the elements of the buckets first_bucket..=last_bucket by partitioning it
+// into the lower and the upper half of these buckets.
+fn sort_buckets_in_place<T>(
+    slice: &mut [T],
+    bucket_ends: &[usize],
+    first_bucket: usize,
+    last_bucket: usize,
+    min_value: &T,
+) where
+    T: Copy + TryIntoIndex,
+{
+    if slice.len() <= 1 || first_bucket == last_bucket {
+        return;
+    }
+    let middle_bucket = first_bucket + (last_bucket - first_bucket) / 2;
+    let start = if first_bucket == 0 {
+        0
+    } else {
+        bucket_ends[first_bucket - 1]
+    };
+    // all indices were validated while counting, hence the conversion does not fail
+    let lower_length = stable_partition_in_place(
+        slice,
+        &|value: &T| matches!(T::try_into_index(value, min_value), Ok(index) if index <= middle_bucket),
+    );
+    debug_assert_eq!(bucket_ends[middle_bucket] - start, lower_length);
+    let (lower, upper) = slice.split_at_mut(lower_length);
+    sort_buckets_in_place(lower, bucket_ends, first_bucket, middle_bucket, min_value);
+    sort_buckets_in_place(
+        upper,
+        bucket_ends,
+        middle_bucket + 1,
+        last_bucket,
+        min_value,
+    );
+}
+
+// Moves all elements for which is_lower returns true before all others, keeping the order within
+// both groups, and returns the number of these elements. Both halves are partitioned recursively,
+// then the upper group of the first half and the lower group of the second half are swapped by a
+// rotation, hence the runtime is O(n log(n)) without additional memory.
+fn stable_partition_in_place<T, F>(slice: &mut [T], is_lower: &F) -> usize
+where
+    F: Fn(&T) -> bool,
+{
+    match slice.len() {
+        0 => 0,
+        1 => usize::from(is_lower(&slice[0])),
+        length => {
+            let middle = length / 2;
+            let first_lower_length = stable_partition_in_place(&mut slice[..middle], is_lower);
+            let second_lower_length = stable_partition_in_place(&mut slice[middle..], is_lower);
+            slice[first_lower_length..middle + second_lower_length]
+                .rotate_left(middle - first_lower_length);
+            first_lower_length + second_lower_length
+        }
+    }
+}
+
+/// Sorts the elements of an [`Iterator`](std::iter::Iterator) over a small-domain type by its
+/// [`u8`](https://doc.rust-lang.org/std/primitive.u8.html) representation.
+///
+/// Each element is mapped into a byte key with [`Into<u8>`](std::convert::Into), which covers e.g.
+/// `#[repr(u8)]` enums and newtypes with at most 256 distinct values without implementing
+/// [`TryIntoIndex`]. The keys are counted into a stack-allocated `[usize; 256]` like in
+/// [`cnt_sort_u8_counts`], only the sorted [`Vec`](std::vec::Vec) is allocated. The sort is
+/// stable. The conversion must preserve the order, i.e. `a <= b` must imply
+/// `a.into() <= b.into()`.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_via_u8;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// #[repr(u8)]
+/// enum Level {
+///     Low,
+///     Mid,
+///     High,
+/// }
+///
+/// impl From<Level> for u8 {
+///     fn from(level: Level) -> u8 {
+///         level as u8
+///     }
+/// }
+///
+/// let levels = vec![Level::High, Level::Low, Level::Mid, Level::Low];
+///
+/// assert_eq!(
+///     vec![Level::Low, Level::Low, Level::Mid, Level::High],
+///     cnt_sort_via_u8(levels.iter()).unwrap()
+/// );
+/// ```
+///
+/// # Errors
+///
+/// * [`CountingSortError::IteratorEmpty`] when the iterator
+///   is empty
+/// * [`CountingSortError::SortingUnnecessary`] when
+///   all elements have the same key, a single element however is returned as is
+pub fn cnt_sort_via_u8<'a, ITER, T>(iterator: ITER) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Copy + Ord + Into<u8> + 'a,
+{
+    let mut counts = [0_usize; 256];
+    let mut number_of_elements: usize = 0;
+    for value in iterator.clone() {
+        counts[usize::from((*value).into())] += 1;
+        number_of_elements += 1;
+    }
+    let first = match iterator.clone().next() {
+        Some(first) => *first,
+        None => return Err(CountingSortError::from_empty_iterator()),
+    };
+    if counts.contains(&number_of_elements) && number_of_elements > 1 {
+        return Err(CountingSortError::from_sorting_unnecessary());
+    }
+    // exclusive prefix sum, i.e. the start position of each key
+    let mut total = 0;
+    for count in &mut counts {
+        let start = total;
+        total += *count;
+        *count = start;
+    }
+    let mut sorted_vector = vec![first; number_of_elements];
+    let mut number_of_placed_elements: usize = 0;
+    for value in iterator {
+        number_of_placed_elements += 1;
+        let position = &mut counts[usize::from((*value).into())];
+        let element = sorted_vector
+            .get_mut(*position)
+            .ok_or_else(CountingSortError::from_non_deterministic_source)?;
+        *element = *value;
+        *position += 1;
+    }
+    // see re_order_into
+    if number_of_placed_elements != number_of_elements {
+        return Err(CountingSortError::from_non_deterministic_source());
+    }
+    Ok(sorted_vector)
+}
+
+/// Counts the occurrences of every [`i8`](https://doc.rust-lang.org/std/primitive.i8.html) value
+/// without any heap allocation.
+///
+/// The signed equivalent of [`cnt_sort_u8_counts`]: the returned array contains at index `i` the
+/// number of occurrences of the value `i - 128`, i.e. index `0` counts `-128` and index `255`
+/// counts `127`, so the array is ordered like the sorted values.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::cnt_sort_i8_counts;
+///
+/// let counts = cnt_sort_i8_counts(&[-128, 0, 0, 127]);
+///
+/// assert_eq!(1, counts[0]);
+/// assert_eq!(2, counts[128]);
+/// assert_eq!(1, counts[255]);
+/// ```
+#[must_use]
+pub fn cnt_sort_i8_counts(slice: &[i8]) -> [usize; 256] {
+    let mut counts = [0; 256];
+    for value in slice {
+        // shift the value range -128..=127 onto 0..=255
+        counts[usize::from(value.to_be_bytes()[0] ^ 0x80)] += 1;
+    }
+    counts
+}
+
+/// Generates `number_of_elements` pseudo-random elements within `[min_value, max_value]`.
+///
+/// The generator is deterministic: the same `seed` always produces the same elements on every
+/// platform, so counting sort benchmarks can be reproduced against own data shapes without
+/// depending on a random number generator crate. The elements are uniformly distributed over the
+/// distance `d` between `min_value` and `max_value` and converted back with
+/// [`TryFromIndex`](crate::TryFromIndex).
+///
+/// Only available with the `test-utils` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::{generate_bounded, CountingSort};
+///
+/// let vector = generate_bounded::<i16>(1000, -500, 500, 42);
+///
+/// assert_eq!(1000, vector.len());
+/// assert!(vector.iter().all(|value| (-500..=500).contains(value)));
+/// assert_eq!(vector, generate_bounded::<i16>(1000, -500, 500, 42));
+///
+/// let sorted_vector = vector.iter().cnt_sort().unwrap();
+/// ```
+///
+/// # Panics
+///
+/// When `min_value` is larger than `max_value` or when the distance `d` between them is larger than
+/// [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value).
+#[cfg(feature = "test-utils")]
+#[must_use]
+pub fn generate_bounded<T>(
+    number_of_elements: usize,
+    min_value: T,
+    max_value: T,
+    seed: u64,
+) -> Vec<T>
+where
+    T: Ord + Copy + TryIntoIndex + TryFromIndex,
+{
+    assert!(min_value <= max_value, "min_value is larger than max_value");
+    let distance = T::try_into_index(&max_value, &min_value)
+        .ok()
+        .expect("distance between min_value and max_value does not fit into usize");
+    // None if the number of possible values exceeds u64, then every random number is in range
+    let range = u64::try_from(distance)
+        .ok()
+        .and_then(|distance| distance.checked_add(1));
+    let mut state = seed;
+    let mut vector = Vec::with_capacity(number_of_elements);
+    for _ in 0..number_of_elements {
+        let random_number = next_pseudo_random(&mut state);
+        let index = range.map_or(random_number, |range| random_number % range);
+        // index <= distance, hence it fits into usize and converts back into T
+        let index = usize::try_from(index).unwrap_or(distance);
+        if let Ok(value) = T::try_from_index(index, &min_value) {
+            vector.push(value);
+        }
+    }
+    vector
+}
+
+/// Aggregate statistics of the sorted elements, returned by
+/// [`cnt_sort_with_stats`](CountingSort::cnt_sort_with_stats()).
+///
+/// The sum is an [`i128`](https://doc.rust-lang.org/std/primitive.i128.html), hence it cannot
+/// overflow for elements of up to 64 bit and fewer than `2^63` elements.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats<T> {
+    /// The smallest element.
+    pub min: T,
+    /// The largest element.
+    pub max: T,
+    /// The number of elements.
+    pub count: usize,
+    /// The sum of all elements.
+    pub sum: i128,
+    /// The arithmetic mean of all elements.
+    pub mean: f64,
+}
+
+/// An [`Iterator`](std::iter::Iterator) over the elements sorted by the counting sort algorithm.
+///
+/// Returned by [`try_sorted_by_counting`](CountingSort::try_sorted_by_counting()). It owns the
+/// sorted elements and implements [`DoubleEndedIterator`](std::iter::DoubleEndedIterator) and
+/// [`ExactSizeIterator`](std::iter::ExactSizeIterator), so all standard adapters can be used on it.
+/// The default value is an empty iterator.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::CountingSort;
+///
+/// let vec = vec![3, 1, 2];
+/// let largest: Vec<i32> = vec.iter().try_sorted_by_counting().unwrap().rev().take(2).collect();
+///
+/// assert_eq!(vec![3, 2], largest);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SortedIter<T> {
+    inner: std::vec::IntoIter<T>,
+}
+
+impl<T> Default for SortedIter<T> {
+    fn default() -> Self {
+        SortedIter {
+            inner: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<T> Iterator for SortedIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for SortedIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for SortedIter<T> {}
+
+/// An [`Iterator`](std::iter::Iterator) over the elements sorted by the counting sort algorithm,
+/// which yields the sorted elements lazily from the distinct values and their counts.
+///
+/// Returned by [`cnt_sort_peekable`](CountingSort::cnt_sort_peekable()). In contrast to
+/// [`SortedIter`] the sorted elements are never stored, only one representative per distinct
+/// value is held together with the number of its occurrences.
+#[derive(Debug, Clone)]
+pub struct SortedRuns<T> {
+    runs: std::vec::IntoIter<(T, usize)>,
+    current: Option<(T, usize)>,
+    remaining: usize,
+}
+
+impl<T: Copy> Iterator for SortedRuns<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.current {
+                Some((value, count)) if count > 0 => {
+                    self.current = Some((value, count - 1));
+                    self.remaining -= 1;
+                    return Some(value);
+                }
+                _ => self.current = Some(self.runs.next()?),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T: Copy> ExactSizeIterator for SortedRuns<T> {}
+
+/// The extension trait for lazily sorting any [`Iterator`](std::iter::Iterator) over owned values
+/// with the counting sort algorithm, see [`counting_sorted`](CountingSortedExt::counting_sorted()).
+///
+/// In contrast to [`CountingSort`], which is implemented for iterators over references, this fits
+/// at the end of iterator chains like `.filter().map()` that yield owned values.
+pub trait CountingSortedExt: Iterator + Sized {
+    /// Returns an [`Iterator`](std::iter::Iterator) adaptor that sorts the elements with the
+    /// counting sort algorithm when it is polled for the first time and then yields them in
+    /// ascending order.
+    ///
+    /// Nothing is done until the adaptor is polled, hence it can be composed with other adaptors,
+    /// e.g. `.filter().map().counting_sorted().take(10)`. On the first poll all elements are
+    /// collected and sorted. The items are of type `Result<T, CountingSortError>`, since the sort
+    /// may fail: in this case a single `Err` is yielded and the adaptor ends afterwards. An empty
+    /// source and a source of equal elements are not errors here, the adaptor yields nothing
+    /// respectively the elements as they are. If errors are impossible for the input, e.g. for
+    /// [`u8`](https://doc.rust-lang.org/std/primitive.u8.html), the items can be unwrapped; use
+    /// [`cnt_sort`](CountingSort::cnt_sort()) on the collected elements if the error must be
+    /// handled before any element is processed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use counting_sort::CountingSortedExt;
+    ///
+    /// let smallest_squares: Vec<u32> = (1..=10_u32)
+    ///     .rev()
+    ///     .filter(|value| value % 2 == 1)
+    ///     .map(|value| value * value)
+    ///     .counting_sorted()
+    ///     .take(3)
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(vec![1, 9, 25], smallest_squares);
+    /// ```
+    fn counting_sorted(self) -> CountingSorted<Self> {
+        CountingSorted {
+            state: CountingSortedState::Unsorted(self),
+        }
+    }
+}
+
+impl<ITER, T> CountingSortedExt for ITER
+where
+    ITER: Iterator<Item = T>,
+    T: Ord + Copy + TryIntoIndex,
+{
+}
+
+/// The lazy [`Iterator`](std::iter::Iterator) adaptor returned by
+/// [`counting_sorted`](CountingSortedExt::counting_sorted()).
+///
+/// The elements are sorted when the adaptor is polled for the first time.
+pub struct CountingSorted<ITER: Iterator> {
+    state: CountingSortedState<ITER, ITER::Item>,
+}
+
+enum CountingSortedState<ITER, T> {
+    Unsorted(ITER),
+    Sorted(std::vec::IntoIter<T>),
+    Failed(CountingSortError),
+    Done,
+}
+
+impl<ITER> fmt::Debug for CountingSorted<ITER>
+where
+    ITER: Iterator + fmt::Debug,
+    ITER::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("CountingSorted");
+        match &self.state {
+            CountingSortedState::Unsorted(iterator) => debug_struct.field("unsorted", iterator),
+            CountingSortedState::Sorted(sorted) => debug_struct.field("sorted", sorted),
+            CountingSortedState::Failed(error) => debug_struct.field("failed", error),
+            CountingSortedState::Done => debug_struct.field("done", &true),
+        };
+        debug_struct.finish()
+    }
+}
+
+impl<ITER, T> Iterator for CountingSorted<ITER>
+where
+    ITER: Iterator<Item = T>,
+    T: Ord + Copy + TryIntoIndex,
+{
+    type Item = Result<T, CountingSortError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let CountingSortedState::Unsorted(_) = self.state {
+            if let CountingSortedState::Unsorted(iterator) =
+                core::mem::replace(&mut self.state, CountingSortedState::Done)
+            {
+                let elements: Vec<T> = iterator.collect();
+                self.state = match counting_sort(elements.iter()) {
+                    Ok(sorted_vector) => CountingSortedState::Sorted(sorted_vector.into_iter()),
+                    // all elements are equal and therefore already sorted
+                    Err(CountingSortError::SortingUnnecessary(_)) => {
+                        CountingSortedState::Sorted(elements.into_iter())
+                    }
+                    Err(CountingSortError::IteratorEmpty(_)) => CountingSortedState::Done,
+                    Err(error) => CountingSortedState::Failed(error),
+                };
+            }
+        }
+        match core::mem::replace(&mut self.state, CountingSortedState::Done) {
+            CountingSortedState::Sorted(mut sorted) => {
+                let next = sorted.next();
+                self.state = CountingSortedState::Sorted(sorted);
+                next.map(Ok)
+            }
+            CountingSortedState::Failed(error) => Some(Err(error)),
+            _ => None,
+        }
+    }
+}
+
+/// A counting sorter for a stream of batches, which reuses its count values vector across batches.
+///
+/// Each batch is sorted with its own minimum and maximum value, but the count values vector is
+/// kept between the batches and only grows, it never shrinks. Hence when the batches have
+/// slightly different ranges, the count values vector is only reallocated when a batch spans a
+/// wider range than all batches before. Since the offsets are re-based on the minimum value of
+/// each batch, a new minimum value does not require any reallocation as long as the distance fits.
+///
+/// In contrast to [`cnt_sort`](CountingSort::cnt_sort()) an empty batch returns an empty
+/// [`Vec`](std::vec::Vec) and a batch of equal elements is returned as is, so that a stream can be
+/// sorted without special cases.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::GrowingCountingSorter;
+///
+/// let mut sorter = GrowingCountingSorter::new();
+///
+/// assert_eq!(vec![10, 11, 12], sorter.sort_batch(&[12, 10, 11]).unwrap());
+/// assert_eq!(vec![-5, 0, 20], sorter.sort_batch(&[20, -5, 0]).unwrap());
+/// assert_eq!(Some((-5, 20)), sorter.bounds());
+/// ```
+#[derive(Debug, Clone)]
+pub struct GrowingCountingSorter<T> {
+    bounds: Option<(T, T)>,
+    count_vector: Vec<usize>,
+}
+
+impl<T> Default for GrowingCountingSorter<T> {
+    fn default() -> Self {
+        GrowingCountingSorter {
+            bounds: None,
+            count_vector: Vec::new(),
+        }
+    }
+}
+
+impl<T> GrowingCountingSorter<T>
+where
+    T: Ord + Copy + TryIntoIndex,
+{
+    /// Creates a sorter without any count values vector, it is allocated by the first batch.
+    #[must_use]
+    pub fn new() -> Self {
+        GrowingCountingSorter::default()
+    }
+
+    /// Returns the smallest and the largest element of all batches sorted so far, or `None` if no
+    /// element was sorted yet.
+    #[must_use]
+    pub fn bounds(&self) -> Option<(T, T)> {
+        self.bounds
+    }
+
+    /// Sorts the batch with the counting sort algorithm and returns the sorted elements.
+    ///
+    /// The sort is stable. The count values vector of the sorter is grown if the distance `d`
+    /// between the maximum and minimum value of this batch is larger than of all previous batches.
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails, this could happen if the distance `d` is larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    /// * [`CountingSortError::IndexOutOfBounds`] when
+    ///   the count values vector cannot be sized for the distance `d`
+    pub fn sort_batch(&mut self, batch: &[T]) -> Result<Vec<T>, CountingSortError> {
+        let (min_value, max_value) = match get_min_max(&mut batch.iter()) {
+            Some((min_value, max_value)) if min_value != max_value => (*min_value, *max_value),
+            // empty or all elements are equal, i.e. already sorted
+            _ => {
+                self.widen_bounds(batch);
+                return Ok(batch.to_vec());
+            }
+        };
+        // additional 0-th element like in count_values
+        let length = index_of(&max_value, &min_value)?
+            .checked_add(2)
+            .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+        if self.count_vector.len() < length {
+            self.count_vector.resize(length, 0);
+        }
+        let count_vector = &mut self.count_vector[..length];
+        for count in count_vector.iter_mut() {
+            *count = 0;
+        }
+        for value in batch {
+            let count = index_of(value, &min_value)?
+                .checked_add(1)
+                .and_then(|index| count_vector.get_mut(index))
+                .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+            *count += 1;
+        }
+        calculate_prefix_sum(count_vector);
+        let mut sorted_vector = vec![min_value; batch.len()];
+        re_order_into(batch.iter(), count_vector, &min_value, &mut sorted_vector)?;
+        self.widen_bounds(&[min_value, max_value]);
+        Ok(sorted_vector)
+    }
+
+    fn widen_bounds(&mut self, batch: &[T]) {
+        if let Some((min_value, max_value)) = get_min_max(&mut batch.iter().copied()) {
+            self.bounds = Some(match self.bounds {
+                Some((widest_min, widest_max)) => {
+                    (min(widest_min, min_value), max(widest_max, max_value))
+                }
+                None => (min_value, max_value),
+            });
+        }
+    }
+}
+
+/// A [`Vec`](std::vec::Vec) whose elements are guaranteed to be in ascending order, returned by
+/// [`cnt_sort_to_sorted_vec`](CountingSort::cnt_sort_to_sorted_vec()).
+///
+/// It dereferences to a [`slice`](std::slice), so all non-mutating slice methods are available,
+/// but it cannot be mutated out of order. It can be iterated by reference, e.g. for repeated
+/// scans, or consumed, just like a [`Vec`](std::vec::Vec). Additionally it provides queries that exploit the
+/// sortedness, like [`rank`](SortedVec::rank()) and [`contains`](SortedVec::contains()), in
+/// `O(log n)`.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::CountingSort;
+///
+/// let vec = vec![30, 10, 20, 10];
+/// let sorted_vec = vec.iter().cnt_sort_to_sorted_vec().unwrap();
+///
+/// assert_eq!(2, sorted_vec.rank(&20));
+/// assert!(sorted_vec.contains(&30));
+/// assert!(!sorted_vec.contains(&15));
+/// assert_eq!(Some(&10), sorted_vec.first());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortedVec<T> {
+    inner: Vec<T>,
+}
+
+impl<T: Ord> SortedVec<T> {
+    /// Returns the number of elements strictly smaller than `value`, i.e. the index at which
+    /// `value` would be inserted before all equal elements.
+    #[must_use]
+    pub fn rank(&self, value: &T) -> usize {
+        self.inner.partition_point(|element| element < value)
+    }
+
+    /// Returns `true` if an element equal to `value` is contained.
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        self.inner.binary_search(value).is_ok()
+    }
+
+    /// Returns the sorted elements as [`Vec`](std::vec::Vec), which drops the guarantee of
+    /// sortedness.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<T> {
+        self.inner
+    }
+}
+
+impl<T> core::ops::Deref for SortedVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> IntoIterator for SortedVec<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SortedVec<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.iter()
+    }
+}
+
+/// A value-to-positions index into the sorted elements, returned by
+/// [`cnt_sort_with_locator`](CountingSort::cnt_sort_with_locator()).
+///
+/// It holds the bucket boundaries of the counting sort algorithm, i.e. the prefix sums of the
+/// counts, hence the positions of all elements equal to a value are found in `O(1)` without any
+/// binary search. This is useful e.g. for probing equi-joins. The memory usage is `O(d)`.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::CountingSort;
+///
+/// let vec = vec![7, 3, 7, 5];
+/// let (sorted_vec, locator) = vec.iter().cnt_sort_with_locator().unwrap();
+///
+/// assert_eq!(vec![3, 5, 7, 7], sorted_vec);
+/// assert_eq!(Some(2..4), locator.positions(&7));
+/// assert_eq!(Some(2..2), locator.positions(&6));
+/// assert_eq!(None, locator.positions(&8));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Locator<T> {
+    min_value: T,
+    prefix_sums: Vec<usize>,
+}
+
+impl<T: TryIntoIndex> Locator<T> {
+    /// Returns the range `[start, end)` of the positions of all elements equal to `value` in the
+    /// sorted elements.
+    ///
+    /// The range is empty if `value` is within the minimum and maximum value but does not occur.
+    /// `None` is returned if `value` is smaller than the minimum value or larger than the maximum
+    /// value.
+    #[must_use]
+    pub fn positions(&self, value: &T) -> Option<Range<usize>> {
+        let index = T::try_into_index(value, &self.min_value).ok()?;
+        // the bucket of index starts at index and ends at index + 1, see re_order_into
+        let start = *self.prefix_sums.get(index)?;
+        let end = *self.prefix_sums.get(index.checked_add(1)?)?;
+        Some(start..end)
+    }
+}
+
+/// A sorted multiset maintained by counting, returned by
+/// [`cnt_sort_incremental`](CountingSort::cnt_sort_incremental()).
+///
+/// Instead of re-running the complete counting sort when a single element changes its value,
+/// [`update`](IncrementalSort::update()) only decrements the count of the old value and
+/// increments the count of the new value in `O(1)`. The sorted elements are reconstructed from the
+/// counts with [`sorted`](IncrementalSort::sorted()) in `O(n+d)`.
+///
+/// Since only the counts are stored, equal elements cannot be distinguished anymore: each bucket
+/// holds one representative value that is copied for all of its elements. For integers this makes
+/// no difference.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::CountingSort;
+///
+/// let vec: Vec<u16> = vec![40, 10, 30, 20];
+/// let mut incremental_sort = vec.iter().cnt_sort_incremental(&0, &100).unwrap();
+/// incremental_sort.update(40, 5).unwrap();
+/// incremental_sort.update(10, 35).unwrap();
+///
+/// assert_eq!(vec![5, 20, 30, 35], incremental_sort.sorted());
+/// assert!(incremental_sort.update(40, 1).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalSort<T> {
+    min_value: T,
+    // the representative value of each bucket and the number of elements in this bucket
+    buckets: Vec<(T, usize)>,
+    length: usize,
+}
+
+impl<T> IncrementalSort<T>
+where
+    T: Ord + Copy + TryIntoIndex,
+{
+    fn new<'a, ITER>(
+        iterator: ITER,
+        min_value: &T,
+        max_value: &T,
+    ) -> Result<IncrementalSort<T>, CountingSortError>
+    where
+        ITER: Iterator<Item = &'a T>,
+        T: 'a,
+    {
+        if min_value > max_value {
+            return Err(CountingSortError::from_min_value_larger_max_value());
+        }
+        let distance_result = T::try_into_index(max_value, min_value);
+        if distance_result.is_err() {
+            return Err(CountingSortError::from_try_into_index_failed());
+        }
+        // distance_result is ok, unwrapping is safe
+        let length = distance_result
+            .unwrap_or(0)
+            .checked_add(1)
+            .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+        let mut incremental_sort = IncrementalSort {
+            min_value: *min_value,
+            // the representative of an empty bucket is never copied
+            buckets: vec![(*min_value, 0); length],
+            length: 0,
+        };
+        for value in iterator {
+            incremental_sort.add(*value)?;
+        }
+        Ok(incremental_sort)
+    }
+
+    /// Replaces one element with the value `old` by an element with the value `new`.
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting into an index fails
+    /// * [`CountingSortError::IndexOutOfBounds`] when `old` or
+    ///   `new` are larger than the maximum value given during construction
+    /// * [`CountingSortError::ValueNotPresent`] when there is no
+    ///   element with the value `old`
+    ///
+    /// In case of an error nothing is changed.
+    pub fn update(&mut self, old: T, new: T) -> Result<(), CountingSortError> {
+        let old_index = self.bucket_index(&old)?;
+        let new_index = self.bucket_index(&new)?;
+        if self.buckets[old_index].1 == 0 {
+            return Err(CountingSortError::from_value_not_present());
+        }
+        self.buckets[old_index].1 -= 1;
+        self.increment(new_index, new);
+        Ok(())
+    }
+
+    /// Returns the sorted elements as [`Vec`](std::vec::Vec).
+    #[must_use]
+    pub fn sorted(&self) -> Vec<T> {
+        reconstruct_from_buckets(&self.buckets, self.length)
+    }
+
+    /// Returns the number of elements.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if there are no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    fn add(&mut self, value: T) -> Result<(), CountingSortError> {
+        let index = self.bucket_index(&value)?;
+        self.increment(index, value);
+        self.length += 1;
+        Ok(())
+    }
+
+    fn increment(&mut self, index: usize, value: T) {
+        let bucket = &mut self.buckets[index];
+        if bucket.1 == 0 {
+            bucket.0 = value;
+        }
+        bucket.1 += 1;
+    }
+
+    fn bucket_index(&self, value: &T) -> Result<usize, CountingSortError> {
+        let index_result = T::try_into_index(value, &self.min_value);
+        if index_result.is_err() {
+            return Err(CountingSortError::from_try_into_index_failed());
+        }
+        // index_result is ok, unwrapping is safe
+        let index = index_result.unwrap_or(0);
+        if index >= self.buckets.len() {
+            return Err(CountingSortError::from_index_out_of_bounds());
+        }
+        Ok(index)
+    }
+}
+
+/// A collector that counts elements as they are added and sorts them once at the end.
+///
+/// [`SortedCollector`] implements [`Extend`](std::iter::Extend), therefore a dataset can be built
+/// up lazily from several sources, e.g. `collector.extend(iter1); collector.extend(iter2);`, and is
+/// sorted with [`into_sorted`](SortedCollector::into_sorted()). In contrast to
+/// [`cnt_sort`](CountingSort::cnt_sort()) the minimum and maximum value are not known in advance,
+/// so the count values vector grows whenever a new minimum or maximum value appears.
+///
+/// Growing for a new maximum value appends to the count values vector and is amortized like
+/// [`Vec::push`](std::vec::Vec::push()). Growing for a new minimum value however re-indexes all
+/// existing counts, which costs `O(d)` each time. In the worst case of strictly decreasing values
+/// this results in `O(n*d)`, so prefer adding the smallest values first if they are known.
+///
+/// Since [`Extend`](std::iter::Extend) cannot fail, the first error is stored and returned by
+/// [`into_sorted`](SortedCollector::into_sorted()), all elements added afterwards are ignored.
+///
+/// # Example
+///
+/// ```rust
+/// use counting_sort::SortedCollector;
+///
+/// let mut collector = SortedCollector::new();
+/// collector.extend(vec![5, 3, 9]);
+/// collector.extend([4, 3].iter());
+///
+/// assert_eq!(vec![3, 3, 4, 5, 9], collector.into_sorted().unwrap());
+/// ```
+#[derive(Debug)]
+pub struct SortedCollector<T> {
+    bounds: Option<(T, T)>,
+    // the representative value of each bucket and the number of elements in this bucket
+    buckets: Vec<(T, usize)>,
+    length: usize,
+    error: Option<CountingSortError>,
+}
+
+impl<T> Default for SortedCollector<T> {
+    fn default() -> Self {
+        SortedCollector {
+            bounds: None,
+            buckets: Vec::new(),
+            length: 0,
+            error: None,
+        }
+    }
+}
+
+impl<T> SortedCollector<T>
+where
+    T: Ord + Copy + TryIntoIndex,
+{
+    /// Creates an empty collector.
+    #[must_use]
+    pub fn new() -> SortedCollector<T> {
+        SortedCollector::default()
+    }
+
+    /// Adds a single element, see [`Extend`](std::iter::Extend) for adding several elements.
+    pub fn push(&mut self, value: T) {
+        if self.error.is_none() {
+            if let Err(error) = self.try_push(value) {
+                self.error = Some(error);
+            }
+        }
+    }
+
+    /// Returns the number of added elements.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if no elements were added.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns all added elements in ascending order.
+    ///
+    /// Since only the counts are stored, equal elements are reconstructed from one representative
+    /// value. For integers this makes no difference. If no elements were added, an empty
+    /// [`Vec`](std::vec::Vec) is returned.
+    ///
+    /// # Errors
+    ///
+    /// * [`CountingSortError::IntoIndexFailed`] when
+    ///   converting an added element into an index failed, this could happen if the distance `d` is
+    ///   larger than
+    ///   [`usize::max_value`](https://doc.rust-lang.org/nightly/std/primitive.usize.html#method.max_value)
+    pub fn into_sorted(self) -> Result<Vec<T>, CountingSortError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(reconstruct_from_buckets(&self.buckets, self.length)),
+        }
+    }
+
+    fn try_push(&mut self, value: T) -> Result<(), CountingSortError> {
+        let min_value = if let Some((min_value, max_value)) = self.bounds {
+            if value < min_value {
+                // re-index all existing counts by prepending empty buckets
+                let shift = index_of(&min_value, &value)?;
+                let mut buckets = vec![(value, 0); shift];
+                buckets.append(&mut self.buckets);
+                self.buckets = buckets;
+                self.bounds = Some((value, max_value));
+            } else if value > max_value {
+                let length = index_of(&value, &min_value)?
+                    .checked_add(1)
+                    .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+                self.buckets.resize(length, (value, 0));
+                self.bounds = Some((min_value, value));
+            }
+            min(min_value, value)
+        } else {
+            self.bounds = Some((value, value));
+            self.buckets.push((value, 0));
+            value
+        };
+        // the bounds were just adapted, hence the index is within the buckets
+        let index = index_of(&value, &min_value)?;
+        let bucket = &mut self.buckets[index];
+        if bucket.1 == 0 {
+            bucket.0 = value;
+        }
+        bucket.1 += 1;
+        self.length += 1;
+        Ok(())
+    }
+}
+
+impl<T> Extend<T> for SortedCollector<T>
+where
+    T: Ord + Copy + TryIntoIndex,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<'a, T> Extend<&'a T> for SortedCollector<T>
+where
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+// Converts the value into an index relative to the minimum value.
+#[inline]
+fn index_of<T>(value: &T, min_value: &T) -> Result<usize, CountingSortError>
+where
+    T: TryIntoIndex,
+{
+    T::try_into_index(value, min_value).map_err(|_| CountingSortError::from_try_into_index_failed())
+}
+
+// Reconstructs the sorted elements from buckets of representative values and their counts.
+#[inline]
+fn reconstruct_from_buckets<T>(buckets: &[(T, usize)], length: usize) -> Vec<T>
+where
+    T: Copy,
+{
+    let mut sorted_vector = Vec::with_capacity(length);
+    for (value, count) in buckets {
+        sorted_vector.resize(sorted_vector.len() + *count, *value);
+    }
+    sorted_vector
+}
+
+#[inline]
+fn counting_sort<'a, ITER, T>(iterator: ITER) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        if min_value == max_value && iterator.clone().nth(1).is_none() {
+            // a single element is trivially sorted
+            return Ok(vec![*min_value]);
+        }
+        counting_sort_min_max(iterator, min_value, max_value)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_with_fill<'a, ITER, T>(
+    iterator: ITER,
+    fill: T,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        if min_value == max_value && iterator.clone().nth(1).is_none() {
+            // a single element is trivially sorted
+            return Ok(vec![*min_value]);
+        }
+        counting_sort_min_max_with_fill(iterator, min_value, max_value, fill)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_min_max<'a, ITER, T>(
+    iterator: ITER,
+    min_value: &T,
+    max_value: &T,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    counting_sort_min_max_with_fill(iterator, min_value, max_value, *min_value)
+}
+
+#[inline]
+fn counting_sort_min_max_with_fill<'a, ITER, T>(
+    iterator: ITER,
+    min_value: &T,
+    max_value: &T,
+    fill: T,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    if min_value == max_value {
+        return Err(CountingSortError::from_sorting_unnecessary());
+    }
+    if min_value > max_value {
+        return Err(CountingSortError::from_min_value_larger_max_value());
+    }
+    count_and_re_order(iterator, min_value, max_value, fill)
+}
+
+// Executes the count, prefix sum and re-order phase without checking the minimum and maximum value.
+#[inline]
+fn count_and_re_order<'a, ITER, T>(
+    iterator: ITER,
+    min_value: &T,
+    max_value: &T,
+    fill: T,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    /*
+      The fields are recorded as soon as they are known, so that a slow count phase can already be
+      attributed to a large distance. The minimum and maximum value are not recorded, since T is
+      not required to implement Debug.
+    */
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!(
+        "counting_sort",
+        distance = tracing::field::Empty,
+        count_vector_size = tracing::field::Empty,
+        elements = tracing::field::Empty
+    );
+    #[cfg(feature = "tracing")]
+    let _span_guard = span.enter();
+
+    let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+
+    #[cfg(feature = "tracing")]
+    {
+        // the count vector has the additional 0-th element and holds d + 1 distinct values
+        span.record("distance", count_vector.len() - 2);
+        span.record("count_vector_size", count_vector.len());
+    }
+
+    let sorted_vector = prefix_sum_and_re_order(iterator, &mut count_vector, min_value, fill)?;
+
+    #[cfg(feature = "tracing")]
+    span.record("elements", sorted_vector.len());
+
+    Ok(sorted_vector)
+}
+
+// Executes the prefix sum and re-order phase on the counted values.
+#[inline]
+fn prefix_sum_and_re_order<'a, ITER, T>(
+    iterator: ITER,
+    count_vector: &mut [usize],
+    min_value: &T,
+    fill: T,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    /*
+      The sum of all counts is the number of elements n. Since it does not overflow, neither does
+      the prefix sum, i.e. its last element equals n and the sorted vector can be sized from it.
+    */
+    let number_of_elements = count_total(count_vector)?;
+    calculate_prefix_sum(count_vector);
+    re_order(iterator, count_vector, number_of_elements, min_value, fill)
+}
+
+// Sums up all counts of the count vector, which must not overflow, since the prefix sum would
+// overflow as well and the sorted elements would be placed outside of the allocated vector.
+#[inline]
+fn count_total(count_vector: &[usize]) -> Result<usize, CountingSortError> {
+    count_vector
+        .iter()
+        .try_fold(0_usize, |total, count| total.checked_add(*count))
+        .ok_or_else(CountingSortError::from_contract_violation)
+}
+
+#[inline]
+fn counting_sort_rank_of<'a, ITER, T>(
+    mut iterator: ITER,
+    target: &T,
+) -> Result<Option<usize>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        if target < min_value || target > max_value {
+            return Ok(None);
+        }
+        let mut count_vector = count_values(&mut iterator, min_value, max_value)?;
+        calculate_prefix_sum(&mut count_vector);
+        let index_result = T::try_into_index(target, min_value);
+        if index_result.is_err() {
+            return Err(CountingSortError::from_try_into_index_failed());
+        }
+        // index_result is ok, unwrapping is safe
+        let index = index_result.unwrap_or(0);
+        if index >= count_vector.len() - 1 {
+            return Err(CountingSortError::from_index_out_of_bounds());
+        }
+        /*
+          Due to the additional 0-th element, the cumulative frequency of the
+          preceeding value is stored at the index of the target. This is the
+          number of elements smaller than the target, hence its rank. The target
+          is only an element of the collection if its own cumulative frequency is
+          larger.
+        */
+        let rank = count_vector[index];
+        if count_vector[index + 1] > rank {
+            Ok(Some(rank))
+        } else {
+            Ok(None)
+        }
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_rle_bytes<'a, ITER, T>(iterator: ITER) -> Result<Vec<u8>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T>,
+    T: Copy + Into<u8> + 'a,
+{
+    let mut count_array = [0_usize; 256];
+    let mut number_of_elements = 0;
+    for value in iterator {
+        let byte: u8 = (*value).into();
+        count_array[usize::from(byte)] += 1;
+        number_of_elements += 1;
+    }
+    if number_of_elements == 0 {
+        return Err(CountingSortError::from_empty_iterator());
+    }
+    let mut encoded = Vec::new();
+    for (byte, count) in (0..=u8::MAX).zip(count_array.iter()) {
+        let mut remaining = *count;
+        while remaining > 0 {
+            // a run can hold at most u16::MAX elements, longer runs are split
+            let run_length = min(remaining, usize::from(u16::MAX));
+            remaining -= run_length;
+            let [count_lo, count_hi] = u16::try_from(run_length).unwrap_or(u16::MAX).to_le_bytes();
+            encoded.push(byte);
+            encoded.push(count_lo);
+            encoded.push(count_hi);
+        }
+    }
+    Ok(encoded)
+}
+
+#[inline]
+fn counting_sort_estimate_copies<'a, ITER, T>(
+    mut iterator: ITER,
+) -> Result<usize, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        if min_value == max_value {
+            if iterator.clone().nth(1).is_none() {
+                // cnt_sort copies the single element without any fill
+                return Ok(1);
+            }
+            return Err(CountingSortError::from_sorting_unnecessary());
+        }
+        let count_vector = count_values(&mut iterator, min_value, max_value)?;
+        // the total of all counts is the number of elements n, which is also the length of the sorted vector
+        let number_of_elements = count_total(&count_vector)?;
+        Ok(number_of_elements.saturating_add(number_of_elements))
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn count_distinct_values<'a, ITER, T>(iterator: ITER) -> Result<Vec<(T, usize)>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+        /*
+          The count vector only holds the frequencies, since an index can not be converted back
+          into a value. Therefore the first occurrence of each value is stored as the
+          representative of its bucket. The 0-th element of the count vector does not represent
+          any value and is skipped.
+        */
+        let mut representatives: Vec<Option<T>> = vec![None; count_vector.len() - 1];
+        for value in iterator {
+            let index_result = T::try_into_index(value, min_value);
+            if index_result.is_err() {
+                return Err(CountingSortError::from_try_into_index_failed());
+            }
+            // index_result is ok, unwrapping is safe
+            let index = index_result.unwrap_or(0);
+            if index >= representatives.len() {
+                return Err(CountingSortError::from_index_out_of_bounds());
+            }
+            if representatives[index].is_none() {
+                representatives[index] = Some(*value);
+            }
+        }
+        Ok(representatives
+            .into_iter()
+            .zip(count_vector.into_iter().skip(1))
+            .filter_map(|(representative, count)| representative.map(|value| (value, count)))
+            .collect())
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_peekable<'a, ITER, T>(
+    iterator: ITER,
+) -> Result<Peekable<SortedRuns<T>>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let distinct_values_with_counts = count_distinct_values(iterator)?;
+    let remaining = distinct_values_with_counts
+        .iter()
+        .try_fold(0_usize, |total, (_, count)| total.checked_add(*count))
+        .ok_or_else(CountingSortError::from_contract_violation)?;
+    if distinct_values_with_counts.len() == 1 && remaining > 1 {
+        return Err(CountingSortError::from_sorting_unnecessary());
+    }
+    Ok(SortedRuns {
+        runs: distinct_values_with_counts.into_iter(),
+        current: None,
+        remaining,
+    }
+    .peekable())
+}
+
+#[inline]
+fn counting_sort_partition<'a, ITER, T>(
+    iterator: ITER,
+    pivot: &T,
+) -> Result<(Vec<T>, Vec<T>), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+        calculate_prefix_sum(&mut count_vector);
+        let length = *count_vector.last().unwrap_or(&0);
+        let split_position = if pivot <= min_value {
+            0
+        } else if pivot > max_value {
+            length
+        } else {
+            let index_result = T::try_into_index(pivot, min_value);
+            if index_result.is_err() {
+                return Err(CountingSortError::from_try_into_index_failed());
+            }
+            // index_result is ok, unwrapping is safe
+            let index = index_result.unwrap_or(0);
+            if index >= count_vector.len() {
+                return Err(CountingSortError::from_index_out_of_bounds());
+            }
+            // cumulative frequency of the value preceeding the pivot, see counting_sort_rank_of
+            count_vector[index]
+        };
+        let mut less = re_order(iterator, &mut count_vector, length, min_value, *min_value)?;
+        let greater_or_equal = less.split_off(split_position);
+        // split_off keeps the capacity of all elements
+        less.shrink_to_fit();
+        Ok((less, greater_or_equal))
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_chunks_desc<'a, ITER, T, F>(
+    iterator: ITER,
+    mut sink: F,
+) -> Result<(), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+    F: FnMut(&[T]),
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+        calculate_prefix_sum(&mut count_vector);
+        let length = *count_vector.last().unwrap_or(&0);
+        // the chunks are slices of the completely sorted elements, see the documentation
+        let sorted_vector = re_order(iterator, &mut count_vector, length, min_value, *min_value)?;
+        /*
+          After re-ordering, each element of the count vector was incremented up to the
+          cumulative frequency of its value, i.e. it is the end of the chunk of this value.
+          The end of the preceeding chunk is the start of this chunk.
+        */
+        let mut end = length;
+        for start in count_vector.iter().rev().skip(1) {
+            if *start < end {
+                sink(&sorted_vector[*start..end]);
+            }
+            end = *start;
+        }
+        if end > 0 {
+            sink(&sorted_vector[..end]);
+        }
+        Ok(())
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_max_bucket_count<'a, ITER, T>(
+    mut iterator: ITER,
+    min_value: &T,
+    max_value: &T,
+) -> Result<usize, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T>,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    if min_value > max_value {
+        return Err(CountingSortError::from_min_value_larger_max_value());
+    }
+    let count_vector = count_values(&mut iterator, min_value, max_value)?;
+    Ok(count_vector.into_iter().max().unwrap_or(0))
+}
+
+#[inline]
+fn counting_sort_merge_into<'a, ITER, T>(
+    iterator: ITER,
+    sorted: &mut Vec<T>,
+) -> Result<(), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let new_elements = count_and_re_order(iterator, min_value, max_value, *min_value)?;
+        let mut remaining_existing = sorted.len();
+        let mut remaining_new = new_elements.len();
+        sorted.resize(remaining_existing + remaining_new, *min_value);
+        /*
+          Merge from the back, so that no existing element is overwritten before it is moved.
+          On equal elements the new element is taken first, so that it is placed after the
+          existing one, which keeps the merge stable.
+        */
+        while remaining_new > 0 {
+            let write_index = remaining_existing + remaining_new - 1;
+            if remaining_existing > 0
+                && sorted[remaining_existing - 1] > new_elements[remaining_new - 1]
+            {
+                sorted[write_index] = sorted[remaining_existing - 1];
+                remaining_existing -= 1;
+            } else {
+                sorted[write_index] = new_elements[remaining_new - 1];
+                remaining_new -= 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[inline]
+fn counting_sort_distinct_count<'a, ITER, T>(mut iterator: ITER) -> Result<usize, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let count_vector = count_values(&mut iterator, min_value, max_value)?;
+        Ok(count_vector.iter().filter(|count| **count > 0).count())
+    } else {
+        Ok(0)
+    }
+}
+
+#[inline]
+fn counting_sort_send<'a, ITER, T>(
+    iterator: ITER,
+    sender: &SyncSender<T>,
+) -> Result<(), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let sorted_vector = counting_sort(iterator)?;
+    for value in sorted_vector {
+        if sender.send(value).is_err() {
+            return Err(CountingSortError::from_channel_disconnected());
+        }
+    }
+    Ok(())
+}
+
+#[inline]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn counting_sort_trimmed_mean<'a, ITER, T>(
+    iterator: ITER,
+    trim_fraction: f64,
+) -> Result<f64, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + Into<f64> + 'a,
+{
+    if !(0.0..0.5).contains(&trim_fraction) {
+        return Err(CountingSortError::from_invalid_trim_fraction());
+    }
+    let distinct_values_with_counts = count_distinct_values(iterator)?;
+    let number_of_elements: usize = distinct_values_with_counts
+        .iter()
+        .map(|(_, count)| count)
+        .sum();
+    // trim_fraction is within [0, 0.5), so the product is non-negative and smaller than n
+    let mut remaining_to_skip = (number_of_elements as f64 * trim_fraction).floor() as usize;
+    let mut remaining_to_take = number_of_elements - 2 * remaining_to_skip;
+    let number_of_taken_elements = remaining_to_take;
+    let mut sum = 0.0;
+    for (value, count) in distinct_values_with_counts {
+        let skipped = min(count, remaining_to_skip);
+        remaining_to_skip -= skipped;
+        let taken = min(count - skipped, remaining_to_take);
+        remaining_to_take -= taken;
+        sum += value.into() * taken as f64;
+        if remaining_to_take == 0 {
+            break;
+        }
+    }
+    Ok(sum / number_of_taken_elements as f64)
+}
+
+#[inline]
+fn counting_sort_recommended<'a, ITER, T>(iterator: ITER) -> Result<bool, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T>,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let mut number_of_elements: usize = 0;
+    let optional_tuple = get_min_max(&mut iterator.inspect(|_| number_of_elements += 1));
+    if let Some((min_value, max_value)) = optional_tuple {
+        let distance_result = T::try_into_index(max_value, min_value);
+        if distance_result.is_err() {
+            return Err(CountingSortError::from_try_into_index_failed());
+        }
+        // distance_result is ok, unwrapping is safe
+        Ok(number_of_elements > distance_result.unwrap_or(0))
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_by_table<'a, ITER, T>(
+    iterator: ITER,
+    table: &[usize; 256],
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Copy + Into<u8> + 'a,
+{
+    let category = |value: &T| table[usize::from((*value).into())];
+    let optional_max_category = iterator.clone().map(category).max();
+    let optional_first_value = iterator.clone().next();
+    if let (Some(max_category), Some(first_value)) = (optional_max_category, optional_first_value) {
+        // one additional element preceeding the first category, see count_values
+        let length = max_category
+            .checked_add(2)
+            .ok_or_else(CountingSortError::from_try_into_index_failed)?;
+        let mut count_vector: Vec<usize> = vec![0; length];
+        for value in iterator.clone() {
+            count_vector[category(value) + 1] += 1;
+        }
+        calculate_prefix_sum(&mut count_vector);
+        let number_of_elements = *count_vector.last().unwrap_or(&0);
+        let mut sorted_vector = vec![*first_value; number_of_elements];
+        for value in iterator {
+            let index_count_vector = category(value);
+            let index = count_vector[index_count_vector];
+            sorted_vector[index] = *value;
+            count_vector[index_count_vector] = index + 1;
+        }
+        Ok(sorted_vector)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_into_slice<'a, ITER, T>(
+    iterator: ITER,
+    output: &mut [T],
+) -> Result<(), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        if min_value == max_value {
+            if iterator.clone().nth(1).is_none() {
+                // a single element is trivially sorted
+                if output.len() != 1 {
+                    return Err(CountingSortError::from_length_mismatch());
+                }
+                output[0] = *min_value;
+                return Ok(());
+            }
+            return Err(CountingSortError::from_sorting_unnecessary());
+        }
+        let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+        calculate_prefix_sum(&mut count_vector);
+        if *count_vector.last().unwrap_or(&0) != output.len() {
+            return Err(CountingSortError::from_length_mismatch());
+        }
+        re_order_into(iterator, &mut count_vector, min_value, output)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_gaps<'a, ITER, T>(iterator: ITER) -> Result<Vec<(T, T)>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let distinct_values_with_counts = count_distinct_values(iterator)?;
+    let mut gaps = Vec::new();
+    for pair in distinct_values_with_counts.windows(2) {
+        let (lower, upper) = (pair[0].0, pair[1].0);
+        // the lower value is the minimum value of the pair, hence the index is the distance
+        let distance_result = T::try_into_index(&upper, &lower);
+        if distance_result.is_err() {
+            return Err(CountingSortError::from_try_into_index_failed());
+        }
+        // distance_result is ok, unwrapping is safe
+        if distance_result.unwrap_or(0) > 1 {
+            gaps.push((lower, upper));
+        }
+    }
+    Ok(gaps)
+}
+
+#[inline]
+#[allow(clippy::type_complexity)]
+fn counting_sort_min_max_checked<'a, ITER, T>(
+    iterator: ITER,
+    min_value: &T,
+    max_value: &T,
+) -> Result<(Vec<T>, Option<(T, T)>), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    if min_value == max_value {
+        return Err(CountingSortError::from_sorting_unnecessary());
+    }
+    if min_value > max_value {
+        return Err(CountingSortError::from_min_value_larger_max_value());
+    }
+    let mut observed: Option<(T, T)> = None;
+    let mut count_vector = count_values(
+        &mut iterator.clone().inspect(|value| {
+            observed = match observed {
+                Some((observed_min, observed_max)) => {
+                    Some((min(observed_min, **value), max(observed_max, **value)))
+                }
+                None => Some((**value, **value)),
+            };
+        }),
+        min_value,
+        max_value,
+    )?;
+    calculate_prefix_sum(&mut count_vector);
+    let length = *count_vector.last().unwrap_or(&0);
+    let sorted_vector = re_order(iterator, &mut count_vector, length, min_value, *min_value)?;
+    Ok((sorted_vector, observed))
+}
+
+#[inline]
+fn counting_sort_shuffle_ties<'a, ITER, T>(
+    iterator: ITER,
+    seed: u64,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let mut sorted_vector = counting_sort(iterator)?;
+    let mut state = seed;
+    let mut start = 0;
+    while start < sorted_vector.len() {
+        let value = sorted_vector[start];
+        let end = sorted_vector[start..]
+            .iter()
+            .position(|other| *other != value)
+            .map_or(sorted_vector.len(), |offset| start + offset);
+        // Fisher-Yates shuffle of the group of equal elements
+        let group = &mut sorted_vector[start..end];
+        for index in (1..group.len()).rev() {
+            let random_index = next_pseudo_random(&mut state) % (index as u64 + 1);
+            // random_index <= index, hence it fits into usize
+            group.swap(index, usize::try_from(random_index).unwrap_or(index));
+        }
+        start = end;
+    }
+    Ok(sorted_vector)
+}
+
+// SplitMix64, a small and fast pseudo-random number generator sufficient for shuffling ties.
+#[inline]
+fn next_pseudo_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[inline]
+fn counting_sort_with_sentinel<'a, ITER, T>(
+    iterator: ITER,
+    sentinel: T,
+) -> Result<(Vec<T>, usize), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let length = iterator.clone().count();
+    if length == 0 {
+        return Err(CountingSortError::from_empty_iterator());
+    }
+    let values = iterator.filter(move |value| **value != sentinel);
+    // every element is either overwritten by a non-sentinel value or remains a sentinel
+    let mut sorted_vector = vec![sentinel; length];
+    let optional_tuple = get_min_max(&mut values.clone());
+    let sentinel_count = if let Some((min_value, max_value)) = optional_tuple {
+        let mut count_vector = count_values(&mut values.clone(), min_value, max_value)?;
+        calculate_prefix_sum(&mut count_vector);
+        // the non-sentinel values are a subset of all elements, hence this is at most length
+        let number_of_values = *count_vector.last().unwrap_or(&0);
+        re_order_into(
+            values,
+            &mut count_vector,
+            min_value,
+            &mut sorted_vector[..number_of_values],
+        )?;
+        length - number_of_values
+    } else {
+        length
+    };
+    Ok((sorted_vector, sentinel_count))
+}
+
+#[inline]
+fn counting_sort_adaptive<'a, ITER, T>(iterator: ITER) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    // only count up to the threshold, the iterator may be arbitrarily long
+    if iterator.clone().take(INSERTION_SORT_THRESHOLD).count() == INSERTION_SORT_THRESHOLD {
+        return counting_sort(iterator);
+    }
+    let mut sorted_vector: Vec<T> = iterator.copied().collect();
+    for index in 1..sorted_vector.len() {
+        let mut position = index;
+        // strictly greater keeps equal elements in order, hence the insertion sort is stable
+        while position > 0 && sorted_vector[position - 1] > sorted_vector[position] {
+            sorted_vector.swap(position - 1, position);
+            position -= 1;
+        }
+    }
+    // collect may leave spare capacity if the size hint of the iterator is not exact
+    sorted_vector.shrink_to_fit();
+    match (sorted_vector.first(), sorted_vector.last()) {
+        (Some(min_value), Some(max_value)) if min_value == max_value && sorted_vector.len() > 1 => {
+            Err(CountingSortError::from_sorting_unnecessary())
+        }
+        (Some(_), Some(_)) => Ok(sorted_vector),
+        _ => Err(CountingSortError::from_empty_iterator()),
+    }
+}
+
+#[inline]
+fn counting_sort_windowed<'a, ITER, T>(
+    iterator: ITER,
+    base: T,
+    window: usize,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    // additional 0-th element like in count_values
+    let length = window
+        .checked_add(1)
+        .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+    let mut count_vector: Vec<usize> = vec![0; length];
+    for value in iterator.clone() {
+        // values below the base cannot be converted into an index
+        if *value < base {
+            return Err(CountingSortError::from_index_out_of_bounds());
+        }
+        let index_result = T::try_into_index(value, &base);
+        if index_result.is_err() {
+            return Err(CountingSortError::from_try_into_index_failed());
+        }
+        // index_result is ok, unwrapping is safe
+        let index = index_result.unwrap_or(0);
+        if index >= window {
+            return Err(CountingSortError::from_index_out_of_bounds());
+        }
+        count_vector[index + 1] += 1;
+    }
+    calculate_prefix_sum(&mut count_vector);
+    let length = *count_vector.last().unwrap_or(&0);
+    if length == 0 {
+        return Err(CountingSortError::from_empty_iterator());
+    }
+    re_order(iterator, &mut count_vector, length, &base, base)
+}
+
+#[inline]
+fn counting_sort_summary<'a, ITER, T>(iterator: ITER) -> Result<String, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + Display + 'a,
+{
+    let distinct_values_with_counts = count_distinct_values(iterator)?;
+    // count_distinct_values returns at least one element for a non-empty iterator
+    let (min_value, _) = distinct_values_with_counts[0];
+    let (max_value, _) = distinct_values_with_counts[distinct_values_with_counts.len() - 1];
+    let mut length = 0;
+    let mut mode = distinct_values_with_counts[0];
+    for (value, count) in &distinct_values_with_counts {
+        length += count;
+        // strictly greater keeps the smallest of equally frequent values
+        if *count > mode.1 {
+            mode = (*value, *count);
+        }
+    }
+    Ok(format!(
+        "n={} min={} max={} distinct={} mode={}(x{})",
+        length,
+        min_value,
+        max_value,
+        distinct_values_with_counts.len(),
+        mode.0,
+        mode.1
+    ))
+}
+
+#[inline]
+#[allow(clippy::type_complexity)]
+fn counting_sort_with_duplicates<'a, ITER, T>(
+    iterator: ITER,
+) -> Result<(Vec<T>, Vec<T>), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+        // positions of the first element of all buckets with more than one element
+        let mut duplicate_positions = Vec::new();
+        let mut position = 0;
+        for count in count_vector.iter().skip(1) {
+            if *count > 1 {
+                duplicate_positions.push(position);
+            }
+            position += count;
+        }
+        calculate_prefix_sum(&mut count_vector);
+        let length = *count_vector.last().unwrap_or(&0);
+        let sorted_vector = re_order(iterator, &mut count_vector, length, min_value, *min_value)?;
+        let duplicate_values = duplicate_positions
+            .iter()
+            .map(|position| sorted_vector[*position])
+            .collect();
+        Ok((sorted_vector, duplicate_values))
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+#[allow(clippy::cast_precision_loss)]
+fn counting_sort_with_entropy<'a, ITER, T>(
+    iterator: ITER,
+) -> Result<(Vec<T>, f64), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+        let number_of_elements = count_vector.iter().sum::<usize>() as f64;
+        let entropy = count_vector
+            .iter()
+            .filter(|count| **count > 0)
+            .map(|count| {
+                let probability = *count as f64 / number_of_elements;
+                -probability * probability.log2()
+            })
+            .sum::<f64>()
+            // avoid returning -0.0 for a single distinct value
+            .abs();
+        calculate_prefix_sum(&mut count_vector);
+        let length = *count_vector.last().unwrap_or(&0);
+        let sorted_vector = re_order(iterator, &mut count_vector, length, min_value, *min_value)?;
+        Ok((sorted_vector, entropy))
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_auto_counter<'a, ITER, T>(iterator: ITER) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let mut number_of_elements = 0_usize;
+    let optional_tuple = get_min_max(&mut iterator.clone().inspect(|_| number_of_elements += 1));
+    if let Some((min_value, max_value)) = optional_tuple {
+        if min_value == max_value {
+            if number_of_elements == 1 {
+                // a single element is trivially sorted
+                return Ok(vec![*min_value]);
+            }
+            return Err(CountingSortError::from_sorting_unnecessary());
+        }
+        let distance = index_of(max_value, min_value)?;
+        if distance >= COMPACT_COUNTER_MIN_DISTANCE && u32::try_from(number_of_elements).is_ok() {
+            count_and_re_order_with_counter::<_, _, u32>(iterator, min_value, max_value)
+        } else {
+            count_and_re_order_with_counter::<_, _, usize>(iterator, min_value, max_value)
+        }
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+// The element type of a count values vector.
+trait Counter: Copy {
+    const ZERO: Self;
+
+    fn checked_increment(self) -> Option<Self>;
+
+    // only used for counts up to the number of elements, which fits into the counter
+    fn add(self, other: Self) -> Self;
+
+    fn to_index(self) -> usize;
+
+    fn from_index(index: usize) -> Option<Self>;
+}
+
+impl Counter for usize {
+    const ZERO: Self = 0;
+
+    #[inline]
+    fn checked_increment(self) -> Option<Self> {
+        self.checked_add(1)
+    }
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+
+    #[inline]
+    fn to_index(self) -> usize {
+        self
+    }
+
+    #[inline]
+    fn from_index(index: usize) -> Option<Self> {
+        Some(index)
+    }
+}
+
+impl Counter for u32 {
+    const ZERO: Self = 0;
+
+    #[inline]
+    fn checked_increment(self) -> Option<Self> {
+        self.checked_add(1)
+    }
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+
+    #[inline]
+    fn to_index(self) -> usize {
+        // usize is at least 32 bit on all platforms supported by this counter
+        usize::try_from(self).unwrap_or(usize::MAX)
+    }
+
+    #[inline]
+    fn from_index(index: usize) -> Option<Self> {
+        u32::try_from(index).ok()
+    }
+}
+
+// Same as count_and_re_order, but with a count values vector of the given counter type.
+#[inline]
+fn count_and_re_order_with_counter<'a, ITER, T, C>(
+    iterator: ITER,
+    min_value: &T,
+    max_value: &T,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+    C: Counter,
+{
+    // additional 0-th element like in count_values
+    let length = index_of(max_value, min_value)?
+        .checked_add(2)
+        .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+    let mut count_vector = vec![C::ZERO; length];
+    for value in iterator.clone() {
+        let index = index_of(value, min_value)?
+            .checked_add(1)
+            .filter(|index| *index < length)
+            .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+        count_vector[index] = count_vector[index]
+            .checked_increment()
+            .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+    }
+    let mut total = C::ZERO;
+    for count in &mut count_vector {
+        total = total.add(*count);
+        *count = total;
+    }
+    let mut sorted_vector = vec![*min_value; total.to_index()];
+    for value in iterator {
+        // all values were checked during the count phase
+        let index_count_vector = index_of(value, min_value)?;
+        let index = count_vector[index_count_vector].to_index();
+        if index >= sorted_vector.len() {
+            return Err(CountingSortError::from_index_out_of_bounds());
+        }
+        sorted_vector[index] = *value;
+        count_vector[index_count_vector] =
+            C::from_index(index + 1).ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+    }
+    Ok(sorted_vector)
+}
+
+#[inline]
+#[allow(clippy::type_complexity)]
+fn counting_sort_clamped_histogram<'a, ITER, T>(
+    iterator: ITER,
+    min_value: T,
+    max_value: T,
+) -> Result<(Vec<usize>, usize, usize), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T>,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    if min_value > max_value {
+        return Err(CountingSortError::from_min_value_larger_max_value());
+    }
+    let length = index_of(&max_value, &min_value)?
+        .checked_add(1)
+        .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+    let mut counts = vec![0; length];
+    let mut underflow_count = 0;
+    let mut overflow_count = 0;
+    for value in iterator {
+        let index = if *value < min_value {
+            underflow_count += 1;
+            0
+        } else if *value > max_value {
+            overflow_count += 1;
+            length - 1
+        } else {
+            index_of(value, &min_value)?
+        };
+        if index >= length {
+            return Err(CountingSortError::from_index_out_of_bounds());
+        }
+        counts[index] += 1;
+    }
+    Ok((counts, underflow_count, overflow_count))
+}
+
+#[inline]
+fn counting_sort_range_slice<'a, ITER, T>(
+    iterator: ITER,
+    lo: usize,
+    hi: usize,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+        calculate_prefix_sum(&mut count_vector);
+        let hi = min(hi, *count_vector.last().unwrap_or(&0));
+        if lo >= hi {
+            return Ok(Vec::new());
+        }
+        let mut range_vector = vec![*min_value; hi - lo];
+        for value in iterator {
+            let index_count_vector = index_of(value, min_value)?;
+            if index_count_vector >= count_vector.len() {
+                return Err(CountingSortError::from_index_out_of_bounds());
+            }
+            // the rank of the element in the sorted vector, see re_order_into
+            let rank = count_vector[index_count_vector];
+            if (lo..hi).contains(&rank) {
+                range_vector[rank - lo] = *value;
+            }
+            count_vector[index_count_vector] = rank + 1;
+        }
+        Ok(range_vector)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+#[allow(clippy::type_complexity)]
+fn counting_sort_permutations<'a, ITER, T>(
+    iterator: ITER,
+) -> Result<(Vec<usize>, Vec<usize>), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let inverse_permutation = counting_sort_inverse_permutation(iterator)?;
+    let mut sort_permutation = vec![0; inverse_permutation.len()];
+    for (original_index, position) in inverse_permutation.iter().enumerate() {
+        sort_permutation[*position] = original_index;
+    }
+    Ok((sort_permutation, inverse_permutation))
+}
+
+// Returns the sorted position of every element, i.e. the inverse permutation only.
+#[inline]
+fn counting_sort_inverse_permutation<'a, ITER, T>(
+    iterator: ITER,
+) -> Result<Vec<usize>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+        calculate_prefix_sum(&mut count_vector);
+        let length = *count_vector.last().unwrap_or(&0);
+        let mut inverse_permutation = vec![0; length];
+        for (original_index, value) in iterator.enumerate() {
+            let index_count_vector = index_of(value, min_value)?;
+            if index_count_vector >= count_vector.len() || original_index >= length {
+                return Err(CountingSortError::from_index_out_of_bounds());
+            }
+            // the sorted position of the element, see re_order_into
+            let position = count_vector[index_count_vector];
+            if position >= length {
+                return Err(CountingSortError::from_index_out_of_bounds());
+            }
+            inverse_permutation[original_index] = position;
+            count_vector[index_count_vector] = position + 1;
+        }
+        Ok(inverse_permutation)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_tiebreak_by<'a, ITER, T, K2, F>(
+    iterator: ITER,
+    secondary: F,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+    F: Fn(&T) -> K2,
+    K2: Ord,
+{
+    let mut sorted_vector = counting_sort(iterator)?;
+    let mut start = 0;
+    while start < sorted_vector.len() {
+        let value = sorted_vector[start];
+        let end = sorted_vector[start..]
+            .iter()
+            .position(|other| *other != value)
+            .map_or(sorted_vector.len(), |offset| start + offset);
+        // groups of a single element are already in order
+        if end - start > 1 {
+            sorted_vector[start..end].sort_by_key(|element| secondary(element));
+        }
+        start = end;
+    }
+    Ok(sorted_vector)
+}
+
+#[inline]
+fn counting_sort_longest_run<'a, ITER, T>(iterator: ITER) -> Result<(T, usize), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let distinct_values_with_counts = count_distinct_values(iterator)?;
+    // count_distinct_values returns at least one element for a non-empty iterator
+    let mut longest_run = distinct_values_with_counts[0];
+    for (value, count) in distinct_values_with_counts.into_iter().skip(1) {
+        // strictly greater keeps the smallest of equally long runs
+        if count > longest_run.1 {
+            longest_run = (value, count);
+        }
+    }
+    Ok(longest_run)
+}
+
+#[inline]
+#[allow(clippy::type_complexity)]
+fn counting_sort_grouped<'a, ITER, T>(iterator: ITER) -> Result<Vec<(T, Vec<T>)>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let (min_value, max_value) =
+        get_min_max(&mut iterator.clone()).ok_or_else(CountingSortError::from_empty_iterator)?;
+    if min_value == max_value {
+        if iterator.clone().nth(1).is_none() {
+            // a single element is trivially sorted
+            return Ok(vec![(*min_value, vec![*min_value])]);
+        }
+        return Err(CountingSortError::from_sorting_unnecessary());
+    }
+    let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+    let number_of_groups = count_vector.iter().filter(|count| **count > 0).count();
+    calculate_prefix_sum(&mut count_vector);
+    let length = *count_vector.last().unwrap_or(&0);
+    let sorted_vector = re_order(iterator, &mut count_vector, length, min_value, *min_value)?;
+    /*
+      After re-ordering, each element of the count vector except the last one was incremented up to
+      the end of the bucket of its value, which is the start of the following bucket. The sorted
+      vector is stable, hence each bucket holds equal elements in input order.
+    */
+    let mut groups: Vec<(T, Vec<T>)> = Vec::with_capacity(number_of_groups);
+    let mut start = 0;
+    for end in &count_vector[..count_vector.len() - 1] {
+        if *end > start {
+            groups.push((sorted_vector[start], sorted_vector[start..*end].to_vec()));
+        }
+        start = *end;
+    }
+    Ok(groups)
+}
+
+// An iterator that stops yielding elements once the cancel flag is set. The flag is only checked
+// every CANCEL_CHECK_INTERVAL elements to keep the atomic load out of the hot loops.
+#[derive(Debug, Clone)]
+struct CancellableIter<'c, ITER> {
+    iterator: ITER,
+    cancel: &'c AtomicBool,
+    position: usize,
+}
+
+const CANCEL_CHECK_INTERVAL: usize = 4096;
+
+impl<ITER: Iterator> Iterator for CancellableIter<'_, ITER> {
+    type Item = ITER::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // the interval is a power of two, hence masking is a cheap modulo
+        if self.position & (CANCEL_CHECK_INTERVAL - 1) == 0
+            && self.cancel.load(std::sync::atomic::Ordering::Relaxed)
+        {
+            return None;
+        }
+        self.position = self.position.wrapping_add(1);
+        self.iterator.next()
+    }
+}
+
+// The 64-bit FNV-1a hash. Integers are hashed in little endian byte order and usize as u64, so
+// that the hash of the same elements is identical on every platform.
+#[derive(Debug, Clone, Copy)]
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl core::hash::Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, value: u128) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        // usize is at most 64 bit on all supported platforms
+        self.write_u64(value as u64);
+    }
+
+    fn write_i16(&mut self, value: i16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_i32(&mut self, value: i32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_i64(&mut self, value: i64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_i128(&mut self, value: i128) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_isize(&mut self, value: isize) {
+        self.write_i64(value as i64);
+    }
+}
+
+// A char ordered by its ASCII lowercase key, so that chars can be sorted case-insensitively by the
+// generic implementation.
+#[derive(Debug, Clone, Copy)]
+struct CaseInsensitiveChar {
+    key: char,
+    value: char,
+}
+
+impl Ord for CaseInsensitiveChar {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl PartialOrd for CaseInsensitiveChar {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for CaseInsensitiveChar {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for CaseInsensitiveChar {}
+
+impl TryIntoIndex for CaseInsensitiveChar {
+    type Error = CountingSortError;
+
+    #[inline]
+    fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+        char::try_into_index(&value.key, &min_value.key)
+    }
+}
+
+#[inline]
+fn counting_sort_chars_ci<'a, ITER, T>(iterator: ITER) -> Result<Vec<char>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Copy + Into<char> + 'a,
+{
+    let case_insensitive_chars: Vec<CaseInsensitiveChar> = iterator
+        .map(|value| {
+            let value: char = (*value).into();
+            CaseInsensitiveChar {
+                key: value.to_ascii_lowercase(),
+                value,
+            }
+        })
+        .collect();
+    let first_value = case_insensitive_chars.first().map(|first| first.value);
+    let is_single_bucket = matches!(
+        get_min_max(&mut case_insensitive_chars.iter()),
+        Some((min_value, max_value)) if min_value == max_value
+    );
+    let sorted_vector = if is_single_bucket
+        && case_insensitive_chars
+            .iter()
+            .any(|case_insensitive_char| Some(case_insensitive_char.value) != first_value)
+    {
+        // chars that differ only in case, the stable order is the input order
+        case_insensitive_chars
+    } else {
+        counting_sort(case_insensitive_chars.iter())?
+    };
+    // collect would reuse the allocation of the larger elements and leave spare capacity
+    let mut chars = Vec::with_capacity(sorted_vector.len());
+    chars.extend(
+        sorted_vector
+            .into_iter()
+            .map(|case_insensitive_char| case_insensitive_char.value),
+    );
+    Ok(chars)
+}
+
+#[inline]
+fn counting_sort_with_stability<'a, ITER, T>(
+    iterator: ITER,
+    stability: Stability,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    if stability == Stability::Stable {
+        return counting_sort(iterator);
+    }
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        if min_value == max_value {
+            if iterator.clone().nth(1).is_none() {
+                // a single element is trivially sorted
+                return Ok(vec![*min_value]);
+            }
+            return Err(CountingSortError::from_sorting_unnecessary());
+        }
+        let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+        calculate_prefix_sum(&mut count_vector);
+        // last element of the count vector depicts the index-1 of the largest element, hence it is its length
+        let length = *count_vector.last().unwrap_or(&0);
+        /*
+          After the prefix sum the element idx holds the start of the bucket idx, which is the end
+          of the bucket idx-1. Shifting all elements by one, each element holds the end of its own
+          bucket, the last element is not used anymore.
+        */
+        count_vector.copy_within(1.., 0);
+        let mut sorted_vector = vec![*min_value; length];
+        let mut number_of_elements: usize = 0;
+        for value in iterator {
+            number_of_elements += 1;
+            let bucket_end = count_vector
+                .get_mut(index_of(value, min_value)?)
+                .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+            // the bucket is filled from its end, hence later elements are placed before earlier ones
+            *bucket_end = bucket_end
+                .checked_sub(1)
+                .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+            let element = sorted_vector
+                .get_mut(*bucket_end)
+                .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+            *element = *value;
+        }
+        // see re_order_into, fewer elements than counted leave parts of the sorted vector unwritten
+        if number_of_elements != length {
+            return Err(CountingSortError::from_non_deterministic_source());
+        }
+        Ok(sorted_vector)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_with_origins<'a, ITER, T>(
+    iterator: ITER,
+) -> Result<Vec<(T, usize)>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+        calculate_prefix_sum(&mut count_vector);
+        let length = *count_vector.last().unwrap_or(&0);
+        let mut sorted_vector = vec![(*min_value, 0); length];
+        for (original_index, value) in iterator.enumerate() {
+            let index_count_vector = index_of(value, min_value)?;
+            if index_count_vector >= count_vector.len() {
+                return Err(CountingSortError::from_index_out_of_bounds());
+            }
+            // the sorted position of the element, see re_order_into
+            let position = count_vector[index_count_vector];
+            if position >= length {
+                return Err(CountingSortError::from_index_out_of_bounds());
+            }
+            sorted_vector[position] = (*value, original_index);
+            count_vector[index_count_vector] = position + 1;
+        }
+        Ok(sorted_vector)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+#[allow(clippy::cast_precision_loss)]
+fn counting_sort_with_stats<'a, ITER, T>(
+    iterator: ITER,
+) -> Result<(Vec<T>, Stats<T>), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + Into<i128> + 'a,
+{
+    let mut elements = iterator.clone();
+    if let Some(first) = elements.next() {
+        // the scan for minimum and maximum value, which additionally counts and sums up
+        let mut stats = elements.fold(
+            Stats {
+                min: *first,
+                max: *first,
+                count: 1,
+                sum: (*first).into(),
+                mean: 0.0,
+            },
+            |stats, value| Stats {
+                min: min(stats.min, *value),
+                max: max(stats.max, *value),
+                count: stats.count + 1,
+                // elements of up to 64 bit cannot saturate the sum, see Stats
+                sum: stats.sum.saturating_add((*value).into()),
+                mean: 0.0,
+            },
+        );
+        stats.mean = stats.sum as f64 / stats.count as f64;
+        let sorted_vector = if stats.count == 1 {
+            // a single element is trivially sorted
+            vec![stats.min]
+        } else {
+            counting_sort_min_max(iterator, &stats.min, &stats.max)?
+        };
+        Ok((sorted_vector, stats))
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_with_locator<'a, ITER, T>(
+    iterator: ITER,
+) -> Result<(Vec<T>, Locator<T>), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+        let length = count_total(&count_vector)?;
+        calculate_prefix_sum(&mut count_vector);
+        // the re-order phase moves the bucket starts, hence the boundaries are kept beforehand
+        let prefix_sums = count_vector.clone();
+        let sorted_vector = re_order(iterator, &mut count_vector, length, min_value, *min_value)?;
+        Ok((
+            sorted_vector,
+            Locator {
+                min_value: *min_value,
+                prefix_sums,
+            },
+        ))
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_distinct<'a, ITER, T>(iterator: ITER) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + TryFromIndex + 'a,
+{
+    const BITS: usize = 64;
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let distance = T::try_into_index(max_value, min_value)
+            .map_err(|_| CountingSortError::from_try_into_index_failed())?;
+        // one bit for each of the d + 1 possible values, without overflowing for d = usize::MAX
+        let mut bitset = vec![0_u64; distance / BITS + 1];
+        let mut number_of_elements: usize = 0;
+        for value in iterator {
+            let index = T::try_into_index(value, min_value)
+                .map_err(|_| CountingSortError::from_try_into_index_failed())?;
+            if index > distance {
+                return Err(CountingSortError::from_index_out_of_bounds());
+            }
+            let bit = 1_u64 << (index % BITS);
+            let word = &mut bitset[index / BITS];
+            if *word & bit != 0 {
+                return Err(CountingSortError::from_duplicate_found());
+            }
+            *word |= bit;
+            // every index is set at most once, hence there are at most d + 1 elements
+            number_of_elements += 1;
+        }
+        let mut sorted_vector = Vec::with_capacity(number_of_elements);
+        for (word_index, word) in bitset.iter().enumerate() {
+            let mut remaining_bits = *word;
+            while remaining_bits != 0 {
+                let index = word_index * BITS + remaining_bits.trailing_zeros() as usize;
+                // clear the lowest set bit
+                remaining_bits &= remaining_bits - 1;
+                let value = T::try_from_index(index, min_value)
+                    .map_err(|_| CountingSortError::from_try_from_index_failed())?;
+                sorted_vector.push(value);
+            }
+        }
+        Ok(sorted_vector)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_cancellable<'a, ITER, T>(
+    iterator: ITER,
+    cancel: &AtomicBool,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    /*
+      A cancelled iterator stops yielding elements, hence every pass ends early. The result of the
+      truncated passes is meaningless, e.g. a single element or a NonDeterministicSource error,
+      therefore the flag decides about the result.
+    */
+    let result = counting_sort(CancellableIter {
+        iterator,
+        cancel,
+        position: 0,
+    });
+    if cancel.load(std::sync::atomic::Ordering::Relaxed) {
+        Err(CountingSortError::from_cancelled())
+    } else {
+        result
+    }
+}
+
+#[inline]
+fn counting_first_occurrences<'a, ITER, T>(iterator: ITER) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    const BITS: usize = 64;
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let distance = index_of(max_value, min_value)?;
+        // one bit for each of the d + 1 possible values, see counting_sort_distinct
+        let mut seen = vec![0_u64; distance / BITS + 1];
+        let mut first_occurrences = Vec::new();
+        for value in iterator {
+            let index = index_of(value, min_value)?;
+            if index > distance {
+                return Err(CountingSortError::from_index_out_of_bounds());
+            }
+            let bit = 1_u64 << (index % BITS);
+            let word = &mut seen[index / BITS];
+            if *word & bit == 0 {
+                *word |= bit;
+                first_occurrences.push(*value);
+            }
+        }
+        Ok(first_occurrences)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_ordered<'a, O, ITER, T>(iterator: ITER) -> Result<Vec<T>, CountingSortError>
+where
+    O: Order,
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        if min_value == max_value {
+            if iterator.clone().nth(1).is_none() {
+                // a single element is trivially sorted
+                return Ok(vec![*min_value]);
+            }
+            return Err(CountingSortError::from_sorting_unnecessary());
+        }
+        let distance = index_of(max_value, min_value)?;
+        sort_by_bucket(iterator, *min_value, distance, |value| {
+            let index = index_of(value, min_value)?;
+            if index > distance {
+                return Err(CountingSortError::from_index_out_of_bounds());
+            }
+            Ok(O::bucket(index, distance))
+        })
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+// Counts the elements into the buckets 0..=largest_bucket returned by bucket_of and re-orders them
+// stably, like count_and_re_order but with an arbitrary mapping of elements to buckets.
+#[inline]
+fn sort_by_bucket<'a, ITER, T, F>(
+    iterator: ITER,
+    fill: T,
+    largest_bucket: usize,
+    bucket_of: F,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Copy + 'a,
+    F: Fn(&T) -> Result<usize, CountingSortError>,
+{
+    // the additional 0-th element enables the re-ordering, see count_values
+    let length = largest_bucket
+        .checked_add(2)
+        .ok_or_else(CountingSortError::from_try_into_index_failed)?;
+    let mut count_vector: Vec<usize> = vec![0; length];
+    for value in iterator.clone() {
+        let count = bucket_of(value)?
+            .checked_add(1)
+            .and_then(|index| count_vector.get_mut(index))
+            .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+        *count = count
+            .checked_add(1)
+            .ok_or_else(CountingSortError::from_index_out_of_bounds)?;
+    }
+    let number_of_elements = count_total(&count_vector)?;
+    calculate_prefix_sum(&mut count_vector);
+    let mut sorted_vector = vec![fill; number_of_elements];
+    let mut number_of_placed_elements: usize = 0;
+    for value in iterator {
+        let bucket = bucket_of(value)?;
+        if bucket > largest_bucket {
+            return Err(CountingSortError::from_index_out_of_bounds());
+        }
+        // the position of the element in the sorted vector, see re_order_into
+        let position = count_vector[bucket];
+        match sorted_vector.get_mut(position) {
+            Some(element) => *element = *value,
+            None => return Err(CountingSortError::from_non_deterministic_source()),
+        }
+        count_vector[bucket] = position + 1;
+        number_of_placed_elements += 1;
+    }
+    if number_of_placed_elements != number_of_elements {
+        return Err(CountingSortError::from_non_deterministic_source());
+    }
+    Ok(sorted_vector)
+}
+
+#[inline]
+fn counting_sort_check<'a, ITER, T>(iterator: &ITER) -> Result<(), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        if min_value == max_value {
+            if iterator.clone().nth(1).is_none() {
+                // a single element is trivially sorted
+                return Ok(());
+            }
+            return Err(CountingSortError::from_sorting_unnecessary());
+        }
+        // the length of the count values vector, see count_values
+        index_of(max_value, min_value)?
+            .checked_add(2)
+            .ok_or_else(CountingSortError::from_try_into_index_failed)?;
+        Ok(())
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_with_collision_check<'a, ITER, T>(
+    iterator: ITER,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        /*
+          The first element of each bucket is kept, so that unequal elements which are converted
+          into the same index, i.e. a lossy TryIntoIndex implementation, are detected instead of
+          silently being treated as equal.
+        */
+        let length = index_of(max_value, min_value)?
+            .checked_add(1)
+            .ok_or_else(CountingSortError::from_try_into_index_failed)?;
+        let mut representatives: Vec<Option<T>> = vec![None; length];
+        for value in iterator.clone() {
+            let index = index_of(value, min_value)?;
+            match representatives.get(index) {
+                Some(Some(representative)) if representative != value => {
+                    return Err(CountingSortError::from_index_collision());
+                }
+                Some(Some(_)) => {}
+                Some(None) => representatives[index] = Some(*value),
+                None => return Err(CountingSortError::from_index_out_of_bounds()),
+            }
+        }
+    }
+    counting_sort(iterator)
+}
+
+#[inline]
+fn counting_sort_dense<'a, ITER, T>(mut iterator: ITER) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + TryFromIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let count_vector = count_values(&mut iterator, min_value, max_value)?;
+        // the 0-th element of the count vector does not represent any value
+        if count_vector[1..].iter().any(|count| *count != 1) {
+            return Err(CountingSortError::from_not_a_permutation());
+        }
+        // collecting into a Result does not know the exact length and may leave spare capacity
+        let length = count_vector.len() - 1;
+        let mut sorted_vector = Vec::with_capacity(length);
+        for index in 0..length {
+            let value = T::try_from_index(index, min_value)
+                .map_err(|_| CountingSortError::from_try_from_index_failed())?;
+            sorted_vector.push(value);
+        }
+        Ok(sorted_vector)
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+#[allow(clippy::cast_precision_loss)]
+fn counting_sortedness<'a, ITER, T>(mut iterator: ITER) -> f64
+where
+    ITER: Iterator<Item = &'a T>,
+    T: Ord + 'a,
+{
+    if let Some(first_value) = iterator.next() {
+        let (_, number_of_pairs, number_of_sorted_pairs) = iterator.fold(
+            (first_value, 0_usize, 0_usize),
+            |(previous_value, number_of_pairs, number_of_sorted_pairs), value| {
+                (
+                    value,
+                    number_of_pairs + 1,
+                    number_of_sorted_pairs + usize::from(previous_value <= value),
+                )
+            },
+        );
+        if number_of_pairs > 0 {
+            return number_of_sorted_pairs as f64 / number_of_pairs as f64;
+        }
+    }
+    1.0
+}
+
+#[cfg(feature = "rayon")]
+#[inline]
+fn counting_sort_parallel_merge<'a, ITER, T>(iterator: ITER) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + Send + Sync + 'a,
+{
+    use rayon::prelude::*;
+
+    let references: Vec<&T> = iterator.clone().collect();
+    let (min_value, max_value) = match (references.par_iter().min(), references.par_iter().max()) {
+        (Some(min_value), Some(max_value)) => (**min_value, **max_value),
+        _ => return Err(CountingSortError::from_empty_iterator()),
+    };
+    if min_value == max_value {
+        if references.len() == 1 {
+            // a single element is trivially sorted
+            return Ok(vec![min_value]);
+        }
+        return Err(CountingSortError::from_sorting_unnecessary());
+    }
+    let number_of_threads = rayon::current_num_threads();
+    let chunk_size = max(references.len() / number_of_threads, 1);
+    let mut count_vector = references
+        .par_chunks(chunk_size)
+        .map(|chunk| count_values(&mut chunk.iter().copied(), &min_value, &max_value))
+        .try_reduce_with(|mut count_vector, other_count_vector| {
+            for (count, other_count) in count_vector.iter_mut().zip(other_count_vector) {
+                // the counts sum up to the number of elements, hence no overflow
+                *count += other_count;
+            }
+            Ok(count_vector)
+        })
+        // there is at least one element, hence there is at least one chunk
+        .unwrap_or_else(|| Err(CountingSortError::from_empty_iterator()))?;
+    let number_of_elements = count_total(&count_vector)?;
+    calculate_prefix_sum(&mut count_vector);
+    re_order(
+        iterator,
+        &mut count_vector,
+        number_of_elements,
+        &min_value,
+        min_value,
+    )
+}
+
+#[inline]
+fn counting_sort_hashed<'a, ITER, T>(
+    iterator: ITER,
+    expected_distinct: usize,
+) -> Result<Vec<T>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T>,
+    T: Ord + Copy + core::hash::Hash + 'a,
+{
+    let count_map = count_hashed(iterator, expected_distinct);
+    let number_of_elements = count_map.values().sum();
+    match count_map.len() {
+        0 => return Err(CountingSortError::from_empty_iterator()),
+        1 if number_of_elements > 1 => {
+            return Err(CountingSortError::from_sorting_unnecessary());
+        }
+        _ => {}
+    }
+    let mut buckets: Vec<(T, usize)> = count_map.into_iter().collect();
+    buckets.sort_unstable_by_key(|(value, _)| *value);
+    Ok(reconstruct_from_buckets(&buckets, number_of_elements))
+}
+
+// Counts the elements into a hash map keyed by their first occurrence.
+#[inline]
+fn count_hashed<'a, ITER, T>(iterator: ITER, expected_distinct: usize) -> HashMap<T, usize>
+where
+    ITER: Iterator<Item = &'a T>,
+    T: Eq + Copy + core::hash::Hash + 'a,
+{
+    let mut count_map = HashMap::with_capacity(expected_distinct);
+    for value in iterator {
+        *count_map.entry(*value).or_insert(0) += 1;
+    }
+    count_map
+}
+
+#[inline]
+fn counting_sort_and_ranks<'a, ITER, T>(
+    iterator: ITER,
+) -> Result<(Vec<T>, Vec<usize>), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let optional_tuple = get_min_max(&mut iterator.clone());
+    if let Some((min_value, max_value)) = optional_tuple {
+        let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+        let length = count_total(&count_vector)?;
+        calculate_prefix_sum(&mut count_vector);
+        let mut sorted_vector = vec![*min_value; length];
+        let mut ranks = Vec::with_capacity(length);
+        for value in iterator {
+            let index_count_vector = index_of(value, min_value)?;
+            if index_count_vector >= count_vector.len() {
+                return Err(CountingSortError::from_index_out_of_bounds());
+            }
+            // the sorted position of the element, see re_order_into
+            let position = count_vector[index_count_vector];
+            if position >= length {
+                return Err(CountingSortError::from_index_out_of_bounds());
+            }
+            sorted_vector[position] = *value;
+            ranks.push(position);
+            count_vector[index_count_vector] = position + 1;
+        }
+        if ranks.len() != length {
+            return Err(CountingSortError::from_non_deterministic_source());
+        }
+        Ok((sorted_vector, ranks))
+    } else {
+        Err(CountingSortError::from_empty_iterator())
+    }
+}
+
+#[inline]
+fn counting_sort_chars_block<'a, ITER, T>(
+    iterator: ITER,
+    block_start: char,
+    block_end: char,
+) -> Result<Vec<char>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Copy + Into<char> + 'a,
+{
+    if block_start > block_end {
+        return Err(CountingSortError::from_min_value_larger_max_value());
+    }
+    let chars: Vec<char> = iterator.map(|value| (*value).into()).collect();
+    if chars
+        .iter()
+        .any(|value| *value < block_start || *value > block_end)
+    {
+        return Err(CountingSortError::from_index_out_of_bounds());
+    }
+    counting_sort_min_max(chars.iter(), &block_start, &block_end)
+}
+
+#[inline]
+fn counting_sort_with_working_set<'a, ITER, T>(
+    iterator: ITER,
+) -> Result<(Vec<T>, usize), CountingSortError>
+where
+    ITER: Iterator<Item = &'a T> + Clone,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let (min_value, max_value) =
+        get_min_max(&mut iterator.clone()).ok_or_else(CountingSortError::from_empty_iterator)?;
+    if min_value == max_value {
+        if iterator.clone().nth(1).is_none() {
+            // a single element is trivially sorted and touches a single bucket
+            return Ok((vec![*min_value], 1));
+        }
+        return Err(CountingSortError::from_sorting_unnecessary());
+    }
+    let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
+    let working_set = count_vector.iter().filter(|count| **count > 0).count();
+    calculate_prefix_sum(&mut count_vector);
+    let length = *count_vector.last().unwrap_or(&0);
+    let sorted_vector = re_order(iterator, &mut count_vector, length, min_value, *min_value)?;
+    Ok((sorted_vector, working_set))
+}
+
+#[inline]
+fn re_order<'a, T, ITER>(
+    iterator: ITER,
+    count_vector: &mut [usize],
+    length: usize,
+    min_value: &T,
+    fill: T,
+) -> Result<Vec<T>, CountingSortError>
+where
+    T: Ord + Copy + TryIntoIndex + 'a,
+    ITER: Iterator<Item = &'a T>,
+{
+    // every element of the sorted vector is overwritten, fill is only used for the allocation
+    let mut sorted_vector: Vec<T> = vec![fill; length];
+    re_order_into(iterator, count_vector, min_value, &mut sorted_vector)?;
+    Ok(sorted_vector)
+}
+
+// Re-orders the elements of the iterator into the given slice, writing exclusively via indexed
+// assignment.
+#[inline]
+fn re_order_into<'a, T, ITER>(
+    iterator: ITER,
+    count_vector: &mut [usize],
+    min_value: &T,
+    sorted_slice: &mut [T],
+) -> Result<(), CountingSortError>
+where
+    T: Ord + Copy + TryIntoIndex + 'a,
+    ITER: Iterator<Item = &'a T>,
+{
+    let mut number_of_elements: usize = 0;
+    for value in iterator {
+        number_of_elements = match number_of_elements.checked_add(1) {
+            Some(number_of_elements) => number_of_elements,
+            None => return Err(CountingSortError::from_index_out_of_bounds()),
+        };
+        let index_count_vector_result = T::try_into_index(value, min_value);
+        if index_count_vector_result.is_err() {
+            return Err(CountingSortError::from_try_into_index_failed());
+        }
+        // index_count_vector_result is ok, unwrapping is safe
+        let index_count_vector = index_count_vector_result.unwrap_or(0);
+        if index_count_vector >= count_vector.len() {
+            return Err(CountingSortError::from_index_out_of_bounds());
+        }
+        //
+        /*
+          Get the cumulative frequency of the value before this.
+          The cumulative frequency of the preceeding value is the index of
+          the first element with this value.
+
+          In order to avoid checks for the index to be 0 (and therefore
+          not to try to access the -1-th element) we allocated the 0-the
+          element additionally so that we can now safely access it.
+          Additionally it holds the index of the next element which
+          equals the minimum value.
+        */
+        let index = count_vector[index_count_vector];
+        if index >= sorted_slice.len() {
+            return Err(CountingSortError::from_index_out_of_bounds());
+        }
+        sorted_slice[index] = *value;
+        /*
+          Increment the index so that successive elements with the same value
+          do not override this one.
+          This additionally ensures that the sort is stable.
+          This actually increments the cumulative frequency of the preceeding
+          value. However at the end of the sorting process this frequency will
+          be the cumulative frequency of this value.
+        */
+        count_vector[index_count_vector] = index + 1; // index < sorted_slice.len(), hence no overflow
+    }
+    /*
+      The iterator was cloned and iterated for counting before, if it yields fewer elements now
+      (e.g. it is backed by a random number generator) some elements of the sorted slice were
+      never written. More elements are caught by the index checks above.
+    */
+    if number_of_elements != sorted_slice.len() {
+        return Err(CountingSortError::from_non_deterministic_source());
+    }
+    Ok(())
+}
+
+#[inline]
+fn count_values<'a, ITER, T>(
+    iterator: &mut ITER,
+    min_value: &T,
+    max_value: &T,
+) -> Result<Vec<usize>, CountingSortError>
+where
+    ITER: Iterator<Item = &'a T>,
+    T: Ord + Copy + TryIntoIndex + 'a,
+{
+    let distance_result = T::try_into_index(max_value, min_value);
+    if distance_result.is_ok() {
+        /*
+          Length must hold all possible distinct values of the collection,
+          this means the complete distance + 1. E.g. the distance of 0 and 255
+          is 255, but there 256 distinct values between 0 and 255.
+
+          We allocate another value in our vector to represent the value that preceeds
+          the minimum value. This value actually does not exist in the collection but
+          is introduced as an optimization for enabling the stable sort of this algorithm
+          without the need of a DoubleEndedIterator and the reverse iteration of the
+          collection when the given collection is re-ordered.
+        */
+        // distance_result is okay so unwrapping is safe, the addition may still overflow for a
+        // distance close to usize::MAX, e.g. the full u16 range on a 16-bit target
+        let length = distance_result
+            .unwrap_or(0)
+            .checked_add(2)
+            .ok_or_else(CountingSortError::from_try_into_index_failed)?;
+        let mut count_vector: Vec<usize> = vec![0; length];
+
+        for value in iterator {
+            let index_result = T::try_into_index(value, min_value);
+            if index_result.is_err() {
+                return Err(CountingSortError::from_try_into_index_failed());
+            }
+            /*
+              Always add + 1 to not use the 0-the element in the vector.
+              This element is just allocated to optimize the re-ordering
+              of the given collection later on.
+              The 0-the element does in a way represent the value that preceeds
+              the minimum value, i.e. this value does not exist in the given
+              collection.
+            */
+            // index_result is ok, unwrapping is safe
+            let optional_index = index_result.unwrap_or(0).checked_add(1);
+            let index = match optional_index {
+                Some(index) if index < count_vector.len() => index,
+                _ => return Err(CountingSortError::from_index_out_of_bounds()),
+            };
+            // an iterator may yield more than usize::MAX references, e.g. on a 16-bit target
+            count_vector[index] = match count_vector[index].checked_add(1) {
+                Some(new_count_value) => new_count_value,
+                None => return Err(CountingSortError::from_index_out_of_bounds()),
+            };
+        }
+        return Ok(count_vector);
+    }
+    Err(CountingSortError::from_try_into_index_failed())
+}
+
+#[inline]
+fn calculate_prefix_sum(count_vector: &mut [usize]) {
+    let mut iterator = count_vector.iter_mut();
+    // skip first element
+    let optional_first_element = iterator.next();
+    if let Some(first_element) = optional_first_element {
+        let mut total = *first_element;
+        for value in iterator {
+            /*
+              The total is at most the number of elements, which is checked by count_total.
+              Where this check is not done, saturating ensures a too large total ends up
+              out of bounds during the re-ordering instead of wrapping around into a valid index.
+            */
+            total = total.saturating_add(*value);
+            *value = total;
+        }
+    }
+}
+
+#[inline]
+fn get_min_max<T, ITER>(iterator: &mut ITER) -> Option<(T, T)>
+where
+    T: Ord + Copy,
+    ITER: Iterator<Item = T>,
+{
+    // consume first element to initialize as min and max value
+    let min_value_optional = iterator.next();
+    if let Some(min_value) = min_value_optional {
+        let tuple = iterator.fold((min_value, min_value), |(min_val, max_val), value| {
+            (min(min_val, value), max(max_val, value))
+        });
+        return Some(tuple);
+    }
+    None
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod unit_tests {
+
+    use super::*;
+
+    const TEST_ARRAY_MIN_VALUE: u8 = 1;
+
+    const TEST_ARRAY_MAX_VALUE: u8 = 30;
+
+    const TEST_ARRAY_UNSORTED: [u8; 30] = [
+        13, 24, 27, 3, 10, 1, 9, 17, 6, 7, 3, 30, 14, 15, 2, 3, 7, 11, 21, 16, 7, 11, 21, 5, 23,
+        25, 26, 28, 28, 4,
+    ];
+
+    const TEST_ARRAY_SORTED: [u8; 30] = [
+        1, 2, 3, 3, 3, 4, 5, 6, 7, 7, 7, 9, 10, 11, 11, 13, 14, 15, 16, 17, 21, 21, 23, 24, 25, 26,
+        27, 28, 28, 30,
+    ];
+
+    const TEST_COUNT_VALUES_ARRAY: [usize; 31] = [
+        0, 1, 1, 3, 1, 1, 1, 3, 0, 1, 1, 2, 0, 1, 1, 1, 1, 1, 0, 0, 0, 2, 0, 1, 1, 1, 1, 1, 2, 0, 1,
+    ];
+
+    const TEST_PREFIX_SUM_ARRAY: [usize; 31] = [
+        0, 1, 2, 5, 6, 7, 8, 11, 11, 12, 13, 15, 15, 16, 17, 18, 19, 20, 20, 20, 20, 22, 22, 23,
+        24, 25, 26, 27, 29, 29, 30,
+    ];
+
+    #[test]
+    fn test_cnt_sort_i8_vector() {
+        let test_vector: Vec<i8> = vec![2, -2, 1, -6];
+        let sorted_vector = counting_sort(test_vector.iter()).unwrap();
+        assert_eq!(vec![-6, -2, 1, 2], sorted_vector);
+    }
+
+    #[test]
+    fn test_cnt_sort_i8_vector_with_overflow() {
+        let test_vector: Vec<i8> = vec![2, -100, 50, -6];
+        let sorted_vector = counting_sort(test_vector.iter()).unwrap();
+        assert_eq!(vec![-100, -6, 2, 50], sorted_vector);
+    }
+
+    #[test]
+    fn test_cnt_sort_u8_vector() {
+        let mut test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        test_vector = test_vector.iter().cnt_sort().unwrap();
+        let sorted_vector = TEST_ARRAY_SORTED.to_vec();
+        assert_eq!(sorted_vector, test_vector);
+    }
+
+    #[test]
+    fn test_cnt_sort_min_max_u8_vector() {
+        let mut test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        test_vector = test_vector
+            .iter()
+            .cnt_sort_min_max(&TEST_ARRAY_MIN_VALUE, &TEST_ARRAY_MAX_VALUE)
+            .unwrap();
+        let sorted_vector = TEST_ARRAY_SORTED.to_vec();
+        assert_eq!(sorted_vector, test_vector);
+    }
+
+    #[test]
+    fn test_into_index_i8() {
+        assert_eq!(255, i8::try_into_index(&127, &-128).unwrap());
+        assert_eq!(0, i8::try_into_index(&-128, &-128).unwrap());
+        assert_eq!(150, i8::try_into_index(&50, &-100).unwrap());
+        assert_eq!(50, i8::try_into_index(&-50, &-100).unwrap());
+        assert_eq!(27, i8::try_into_index(&127, &100).unwrap());
+    }
+
+    #[test]
+    fn test_into_index_i16() {
+        assert_eq!(0xFFFF, i16::try_into_index(&32767, &-32768).unwrap());
+        assert_eq!(0, i16::try_into_index(&-32768, &-32768).unwrap());
+        assert_eq!(0, i16::try_into_index(&32767, &32767).unwrap());
+    }
+
+    #[test]
+    fn test_into_index_i32() {
+        assert_eq!(
+            0xFFFF_FFFF,
+            i32::try_into_index(&2_147_483_647, &-2_147_483_648).unwrap()
+        );
+        assert_eq!(
+            0,
+            i32::try_into_index(&-2_147_483_648, &-2_147_483_648).unwrap()
+        );
+        assert_eq!(
+            1,
+            i32::try_into_index(&-2_147_483_647, &-2_147_483_648).unwrap()
+        );
+        assert_eq!(
+            0,
+            i32::try_into_index(&2_147_483_647, &2_147_483_647).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_into_index_u8() {
+        assert_eq!(255, u8::try_into_index(&255, &0).unwrap());
+        assert_eq!(0, u8::try_into_index(&0, &0).unwrap());
+        assert_eq!(0, u8::try_into_index(&255, &255).unwrap());
+        assert_eq!(50, u8::try_into_index(&150, &100).unwrap());
+        assert_eq!(50, u8::try_into_index(&100, &50).unwrap());
+        assert_eq!(27, i8::try_into_index(&127, &100).unwrap());
+        assert!(u8::try_into_index(&0, &1).is_err());
+    }
+
+    #[test]
+    fn test_into_index_u16() {
+        assert_eq!(0xFFFF, u16::try_into_index(&0xFFFF, &0).unwrap());
+        assert_eq!(0, u16::try_into_index(&0, &0).unwrap());
+        assert_eq!(0, u16::try_into_index(&0xFFFF, &0xFFFF).unwrap());
+        assert_eq!(1, u16::try_into_index(&0xFFFF, &0xFFFE).unwrap());
+        assert!(u16::try_into_index(&0xFFFE, &0xFFFF).is_err());
+    }
+
+    #[test]
+    fn test_into_index_u32() {
+        assert_eq!(0xFFFF_FFFF, u32::try_into_index(&0xFFFF_FFFF, &0).unwrap());
+        assert_eq!(0, u32::try_into_index(&0, &0).unwrap());
+        assert_eq!(50, u32::try_into_index(&1_000_000, &999_950).unwrap());
+        // smaller than the minimum value
+        assert!(usize::try_into_index(&0, &1).is_err());
+        assert!(u32::try_into_index(&0, &1).is_err());
+        assert_eq!(50, u8::try_into_index(&100, &50).unwrap());
+        assert_eq!(27, i8::try_into_index(&127, &100).unwrap());
+    }
+
+    #[test]
+    fn test_counting_sort() {
+        let test_vector: Vec<u8> = TEST_ARRAY_UNSORTED.to_vec();
+        let sorted_vector = counting_sort(test_vector.iter()).unwrap();
+        let expected_vector = TEST_ARRAY_SORTED.to_vec();
+        assert_eq!(expected_vector, sorted_vector);
+    }
+
+    #[test]
+    fn test_counting_sort_min_max() {
+        let test_vector: Vec<u8> = TEST_ARRAY_UNSORTED.to_vec();
+        let sorted_vector = counting_sort_min_max(
+            test_vector.iter(),
+            &TEST_ARRAY_MIN_VALUE,
+            &TEST_ARRAY_MAX_VALUE,
+        )
+        .unwrap();
+        let expected_vector = TEST_ARRAY_SORTED.to_vec();
+        assert_eq!(expected_vector, sorted_vector);
+    }
+
+    #[test]
+    fn test_count_values() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let result_count_value_vector = count_values(
+            &mut test_vector.iter(),
+            &TEST_ARRAY_MIN_VALUE,
+            &TEST_ARRAY_MAX_VALUE,
+        );
+        assert!(result_count_value_vector.is_ok());
+        let count_values_vector = result_count_value_vector.unwrap();
+        let expected_vector = TEST_COUNT_VALUES_ARRAY.to_vec();
+        assert_eq!(expected_vector, count_values_vector);
+    }
+
+    #[test]
+    fn test_get_min_max_unsigned() {
+        let test_vector: Vec<u8> = vec![1, 2, 3, 4];
+        let tuple = get_min_max(&mut test_vector.iter());
+        assert!(tuple.is_some());
+        let (min_value, max_value) = tuple.unwrap();
+        assert_eq!(1, *min_value);
+        assert_eq!(4, *max_value);
+    }
+
+    #[test]
+    fn test_get_min_max_without_min() {
+        let test_vector: Vec<u8> = Vec::new();
+        let tuple = get_min_max(&mut test_vector.iter());
+        assert!(tuple.is_none());
+    }
+
+    #[test]
+    fn test_get_min_max_signed() {
+        let test_vector: Vec<i8> = vec![-128, 2, 3, 127];
+        let tuple = get_min_max(&mut test_vector.iter());
+        assert!(tuple.is_some());
+        let (min_value, max_value) = tuple.unwrap();
+        assert_eq!(-128, *min_value);
+        assert_eq!(127, *max_value);
+    }
+
+    #[test]
+    fn test_calculate_prefix_sum_1() {
+        let mut test_vector: Vec<usize> = vec![1; 4];
+        calculate_prefix_sum(&mut test_vector);
+        assert_eq!(vec![1, 2, 3, 4], test_vector);
+    }
+
+    #[test]
+    fn test_calculate_prefix_sum_2() {
+        let mut test_vector: Vec<usize> = vec![1, 2, 3, 4, 5];
+        calculate_prefix_sum(&mut test_vector);
+        assert_eq!(vec![1, 3, 6, 10, 15], test_vector);
+    }
+
+    #[test]
+    fn test_calculate_prefix_sum_3() {
+        let mut test_vector = TEST_COUNT_VALUES_ARRAY.to_vec();
+        calculate_prefix_sum(&mut test_vector);
+        assert_eq!(TEST_PREFIX_SUM_ARRAY.to_vec(), test_vector);
+    }
+
+    #[test]
+    fn test_re_order() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let mut test_count_vector = TEST_PREFIX_SUM_ARRAY.to_vec();
+        let result_sorted_vector = re_order(
+            test_vector.iter(),
+            &mut test_count_vector,
+            test_vector.len(),
+            &TEST_ARRAY_MIN_VALUE,
+            TEST_ARRAY_MIN_VALUE,
+        );
+        assert!(result_sorted_vector.is_ok());
+        let sorted_vector = result_sorted_vector.unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector);
+    }
+
+    #[test]
+    fn test_rank_of_present_value() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        assert_eq!(Some(0), test_vector.iter().cnt_sort_rank_of(&1).unwrap());
+        assert_eq!(Some(2), test_vector.iter().cnt_sort_rank_of(&3).unwrap());
+        assert_eq!(Some(8), test_vector.iter().cnt_sort_rank_of(&7).unwrap());
+        assert_eq!(Some(29), test_vector.iter().cnt_sort_rank_of(&30).unwrap());
+    }
+
+    #[test]
+    fn test_rank_of_absent_value() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        assert_eq!(None, test_vector.iter().cnt_sort_rank_of(&8).unwrap());
+        assert_eq!(None, test_vector.iter().cnt_sort_rank_of(&29).unwrap());
+        // out of range
+        assert_eq!(None, test_vector.iter().cnt_sort_rank_of(&0).unwrap());
+        assert_eq!(None, test_vector.iter().cnt_sort_rank_of(&31).unwrap());
+    }
+
+    #[test]
+    fn test_rank_of_empty_iterator_error() {
+        let test_vector: Vec<u8> = vec![];
+        let result = test_vector.iter().cnt_sort_rank_of(&1);
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_with_fill() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        // 0 is smaller than the minimum value and must not be part of the result
+        let sorted_vector = test_vector.iter().cnt_sort_with_fill(0).unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector);
+        assert!(!sorted_vector.contains(&0));
+    }
+
+    #[test]
+    fn test_min_value_larger_max_value_error() {
+        let test_vector = [1];
+        let result = counting_sort_min_max(test_vector.iter(), &1, &0);
+        assert!(result.is_err());
+        assert_eq!(
+            "Minimum value is larger than maximum value",
+            format!("{}", result.unwrap_err())
+        );
+    }
+
+    #[test]
+    fn test_sorting_unnecessary_error() {
+        let test_vector = [1];
+        let result = counting_sort_min_max(test_vector.iter(), &1, &1);
+        assert!(result.is_err());
+        assert_eq!(
+            "Minimum value is identical to maximum value, therefore no sorting is necessary",
+            format!("{}", result.unwrap_err())
+        );
+    }
+
+    #[test]
+    fn test_empty_iterator_error() {
+        let test_vector: Vec<u8> = vec![];
+        let result = counting_sort(test_vector.iter());
+        assert!(result.is_err());
+        assert_eq!(
+            "There are no element available in the iterator",
+            format!("{}", result.unwrap_err())
+        );
+        let test_vector: Vec<u8> = vec![];
+        let result = counting_sort_min_max(test_vector.iter(), &0, &1);
+        assert!(result.is_ok());
+        assert_eq!(test_vector, result.unwrap());
+    }
+
+    #[test]
+    fn test_incorrect_given_min_max_values() {
+        let vec = [4, 3, 2, 1];
+
+        let error = vec.iter().cnt_sort_min_max(&2, &4);
+        assert!(error.is_err());
+        assert_eq!(
+            "Conversion into index failed",
+            format!("{}", error.unwrap_err())
+        );
+
+        let error = vec.iter().cnt_sort_min_max(&1, &3);
+        assert!(error.is_err());
+        assert_eq!(
+            "Index is out of bounds, most likely the given maximum value is too small",
+            format!("{}", error.unwrap_err())
+        );
+    }
+
+    #[test]
+    fn test_try_into_error() {
+        #[derive(Ord, PartialOrd, PartialEq, Eq, Copy, Clone, Debug)]
+        struct ValueWithTryIntoError {
+            value: u8,
+        }
+
+        impl TryIntoIndex for ValueWithTryIntoError {
+            type Error = String;
+            fn try_into_index(_value: &Self, _min_value: &Self) -> Result<usize, Self::Error> {
+                Err(String::from("TryInto always fails"))
+            }
+        }
+
+        let min_value = ValueWithTryIntoError { value: 0 };
+        let max_value = ValueWithTryIntoError { value: u8::MAX };
+
+        let test_vector: Vec<ValueWithTryIntoError> = Vec::new();
+        let result = counting_sort_min_max(test_vector.iter(), &min_value, &max_value);
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_try_into_index_failed().to_string(),
+            result.unwrap_err().to_string()
+        );
+
+        let mut count_vector = vec![0, 0];
+        let test_vector = [max_value, min_value];
+        let result = re_order(
+            test_vector.iter(),
+            &mut count_vector,
+            2,
+            &min_value,
+            min_value,
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_try_into_index_failed().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_empty_count_values_vector_is_impossible() {
+        #[derive(Ord, PartialOrd, PartialEq, Eq, Copy, Clone, Debug)]
+        struct ValueWithWrongSubstraction {
+            value: usize,
+        }
+
+        impl TryIntoIndex for ValueWithWrongSubstraction {
+            type Error = String;
+            fn try_into_index(_value: &Self, _min_value: &Self) -> Result<usize, Self::Error> {
+                Ok(0)
+            }
+        }
+
+        let min_value = ValueWithWrongSubstraction { value: 0 };
+        let max_value = ValueWithWrongSubstraction { value: usize::MAX };
+
+        let test_vector: Vec<ValueWithWrongSubstraction> = Vec::new();
+        let result = counting_sort_min_max(test_vector.iter(), &min_value, &max_value);
+        assert!(result.is_ok());
+        assert_eq!(test_vector, result.unwrap());
+    }
+
+    #[test]
+    fn test_re_order_index_out_of_bounds_error() {
+        let vec = [1, 2];
+        let mut count_vector = vec![1];
+        let result = re_order(vec.iter(), &mut count_vector, 2, &1, 1);
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_index_out_of_bounds().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    fn decode_rle_bytes(encoded: &[u8]) -> Vec<u8> {
+        let mut decoded = Vec::new();
+        for run in encoded.chunks(3) {
+            let count = usize::from(u16::from_le_bytes([run[1], run[2]]));
+            decoded.extend(vec![run[0]; count]);
+        }
+        decoded
+    }
+
+    #[test]
+    fn test_cnt_sort_rle_bytes() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let encoded = test_vector.iter().cnt_sort_rle_bytes().unwrap();
+        assert_eq!(0, encoded.len() % 3);
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), decode_rle_bytes(&encoded));
+    }
+
+    #[test]
+    fn test_cnt_sort_rle_bytes_long_run() {
+        let mut test_vector = vec![200_u8; 70000];
+        test_vector.push(3);
+        let encoded = test_vector.iter().cnt_sort_rle_bytes().unwrap();
+        assert_eq!(vec![3, 1, 0, 200, 0xFF, 0xFF, 200, 0x71, 0x11], encoded);
+        assert_eq!(
+            test_vector.iter().cnt_sort().unwrap(),
+            decode_rle_bytes(&encoded)
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_rle_bytes_empty_iterator_error() {
+        let test_vector: Vec<u8> = vec![];
+        let result = test_vector.iter().cnt_sort_rle_bytes();
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_estimate_copies() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        assert_eq!(
+            2 * TEST_ARRAY_UNSORTED.len(),
+            test_vector.iter().cnt_sort_estimate_copies().unwrap()
+        );
+        let test_vector = [1, 1];
+        let result = test_vector.iter().cnt_sort_estimate_copies();
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_multiset_eq_equal_multisets() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        assert!(multiset_eq(test_vector.iter(), TEST_ARRAY_SORTED.iter()).unwrap());
+        let empty_vector: Vec<u8> = vec![];
+        assert!(multiset_eq(empty_vector.iter(), empty_vector.iter()).unwrap());
+    }
+
+    #[test]
+    fn test_multiset_eq_different_counts() {
+        let a = [1, 2, 2, 3];
+        let b = [1, 2, 3, 3];
+        assert!(!multiset_eq(a.iter(), b.iter()).unwrap());
+        let c = [1, 2, 3];
+        assert!(!multiset_eq(a.iter(), c.iter()).unwrap());
+    }
+
+    #[test]
+    fn test_multiset_eq_disjoint_sets() {
+        let a = [1, 2, 3];
+        let b = [4, 5, 6];
+        assert!(!multiset_eq(a.iter(), b.iter()).unwrap());
+        let empty_vector: Vec<i32> = vec![];
+        assert!(!multiset_eq(a.iter(), empty_vector.iter()).unwrap());
+    }
+
+    #[test]
+    fn test_cnt_sort_split_counts() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let (distinct_values, counts) = test_vector.iter().cnt_sort_split_counts().unwrap();
+        assert_eq!(distinct_values.len(), counts.len());
+        assert!(distinct_values.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(TEST_ARRAY_UNSORTED.len(), counts.iter().sum::<usize>());
+        let expected_counts: Vec<usize> = TEST_COUNT_VALUES_ARRAY
+            .iter()
+            .copied()
+            .filter(|count| *count > 0)
+            .collect();
+        assert_eq!(expected_counts, counts);
+    }
+
+    #[test]
+    fn test_cnt_sort_split_counts_single_value() {
+        let test_vector = [5, 5, 5];
+        let (distinct_values, counts) = test_vector.iter().cnt_sort_split_counts().unwrap();
+        assert_eq!(vec![5], distinct_values);
+        assert_eq!(vec![3], counts);
+    }
+
+    #[derive(Ord, PartialOrd, PartialEq, Eq, Copy, Clone, Debug)]
+    struct ValueWithMaxIndex {
+        value: usize,
+    }
+
+    // Maps the value 7 to usize::MAX, all other values are mapped correctly.
+    impl TryIntoIndex for ValueWithMaxIndex {
+        type Error = String;
+        fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+            if value.value == 7 {
+                Ok(usize::MAX)
+            } else {
+                Ok(value.value - min_value.value)
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_values_index_overflow_error() {
+        let min_value = ValueWithMaxIndex { value: 0 };
+        let max_value = ValueWithMaxIndex { value: 10 };
+        let test_vector = [
+            ValueWithMaxIndex { value: 3 },
+            ValueWithMaxIndex { value: 7 },
+        ];
+        let result = count_values(&mut test_vector.iter(), &min_value, &max_value);
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_index_out_of_bounds().to_string(),
+            result.unwrap_err().to_string()
+        );
+        let result = test_vector.iter().cnt_sort_min_max(&min_value, &max_value);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_re_order_index_overflow_error() {
+        let min_value = ValueWithMaxIndex { value: 0 };
+        let test_vector = [ValueWithMaxIndex { value: 1 }];
+        let mut count_vector = vec![0, usize::MAX, 1];
+        let result = re_order(
+            test_vector.iter(),
+            &mut count_vector,
+            1,
+            &min_value,
+            min_value,
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_index_out_of_bounds().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_partition() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let (less, greater_or_equal) = test_vector.iter().cnt_sort_partition(&15).unwrap();
+        assert!(less.iter().all(|value| *value < 15));
+        assert!(greater_or_equal.iter().all(|value| *value >= 15));
+        let mut combined = less;
+        combined.extend(greater_or_equal);
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), combined);
+    }
+
+    #[test]
+    fn test_cnt_sort_partition_pivot_out_of_range() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let (less, greater_or_equal) = test_vector.iter().cnt_sort_partition(&0).unwrap();
+        assert!(less.is_empty());
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), greater_or_equal);
+        let (less, greater_or_equal) = test_vector.iter().cnt_sort_partition(&31).unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), less);
+        assert!(greater_or_equal.is_empty());
+    }
+
+    #[test]
+    fn test_cnt_sort_chunks_desc() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let mut chunks: Vec<Vec<u8>> = Vec::new();
+        test_vector
+            .iter()
+            .cnt_sort_chunks_desc(|chunk| chunks.push(chunk.to_vec()))
+            .unwrap();
+        assert!(chunks
+            .iter()
+            .all(|chunk| !chunk.is_empty() && chunk.iter().all(|value| *value == chunk[0])));
+        let concatenated: Vec<u8> = chunks.into_iter().flatten().collect();
+        let mut expected_vector = TEST_ARRAY_SORTED.to_vec();
+        expected_vector.reverse();
+        assert_eq!(expected_vector, concatenated);
+    }
+
+    #[test]
+    fn test_cnt_sort_lines() {
+        let input =
+            std::io::Cursor::new("3\n -1\n\n9223372036854775807\n3\n-9223372036854775808\n");
+        let result = cnt_sort_lines(input);
+        // distance of i64::MIN and i64::MAX does not fit into the count values vector
+        assert!(result.is_err());
+
+        let input = std::io::Cursor::new("3\n -1\n\n2\n3\n-5\n");
+        let sorted_vector = cnt_sort_lines(input).unwrap();
+        assert_eq!(vec![-5, -1, 2, 3, 3], sorted_vector);
+    }
+
+    #[test]
+    fn test_cnt_sort_lines_parse_failed_error() {
+        let input = std::io::Cursor::new("3\nthree\n");
+        let result = cnt_sort_lines(input);
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_parse_failed().to_string(),
+            result.unwrap_err().to_string()
+        );
+        let result = cnt_sort_lines(std::io::Cursor::new("\n"));
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_lines_io_error() {
+        // reads like the inner cursor, but can not seek
+        struct UnseekableReader {
+            inner: std::io::Cursor<&'static str>,
+        }
+
+        impl Read for UnseekableReader {
+            fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+                self.inner.read(buffer)
+            }
+        }
+
+        impl BufRead for UnseekableReader {
+            fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+                self.inner.fill_buf()
+            }
+
+            fn consume(&mut self, amount: usize) {
+                self.inner.consume(amount);
+            }
+        }
+
+        impl Seek for UnseekableReader {
+            fn seek(&mut self, _position: SeekFrom) -> std::io::Result<u64> {
+                Err(std::io::Error::from(std::io::ErrorKind::Other))
+            }
+        }
+
+        let result = cnt_sort_lines(UnseekableReader {
+            inner: std::io::Cursor::new("3\n1\n"),
+        });
+        assert_eq!(
+            CountingSortError::from_io().to_string(),
+            result.unwrap_err().to_string()
+        );
+        // a line that is not valid UTF-8 can not be read into a String
+        let result = cnt_sort_lines(std::io::Cursor::new(&b"3\n\xFF\n"[..]));
+        assert_eq!(
+            CountingSortError::from_io().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_max_bucket_count() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let max_bucket_count = test_vector
+            .iter()
+            .max_bucket_count(&TEST_ARRAY_MIN_VALUE, &TEST_ARRAY_MAX_VALUE)
+            .unwrap();
+        // 3 and 7 are the most frequent values
+        assert_eq!(3, max_bucket_count);
+        assert_eq!(
+            *TEST_COUNT_VALUES_ARRAY.iter().max().unwrap(),
+            max_bucket_count
+        );
+
+        let result = test_vector.iter().max_bucket_count(&1, &0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cnt_sort_merge_into() {
+        let mut sorted_vector = vec![2, 5, 5, 30];
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        test_vector
+            .iter()
+            .cnt_sort_merge_into(&mut sorted_vector)
+            .unwrap();
+        let mut expected_vector = TEST_ARRAY_SORTED.to_vec();
+        expected_vector.extend_from_slice(&[2, 5, 5, 30]);
+        expected_vector.sort_unstable();
+        assert_eq!(expected_vector, sorted_vector);
+
+        let empty_vector: Vec<u8> = vec![];
+        empty_vector
+            .iter()
+            .cnt_sort_merge_into(&mut sorted_vector)
+            .unwrap();
+        assert_eq!(expected_vector, sorted_vector);
+    }
+
+    #[test]
+    fn test_cnt_sort_array() {
+        let test_array: [u8; 8] = [200, 3, 17, 3, 0, 255, 42, 17];
+        let sorted_array = cnt_sort_array(test_array).unwrap();
+        assert_eq!([0, 3, 3, 17, 17, 42, 200, 255], sorted_array);
+        assert_eq!(
+            TEST_ARRAY_SORTED,
+            cnt_sort_array(TEST_ARRAY_UNSORTED).unwrap()
+        );
+        let empty_array: [u8; 0] = [];
+        assert!(cnt_sort_array(empty_array).is_err());
+    }
+
+    #[test]
+    fn test_cnt_sort_distinct_count() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        assert_eq!(23, test_vector.iter().cnt_sort_distinct_count().unwrap());
+        let empty_vector: Vec<u8> = vec![];
+        assert_eq!(0, empty_vector.iter().cnt_sort_distinct_count().unwrap());
+        let single_value_vector = [-7, -7, -7];
+        assert_eq!(
+            1,
+            single_value_vector
+                .iter()
+                .cnt_sort_distinct_count()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_send() {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(2);
+        let consumer = std::thread::spawn(move || receiver.iter().collect::<Vec<u8>>());
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        test_vector.iter().cnt_sort_send(&sender).unwrap();
+        drop(sender);
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), consumer.join().unwrap());
+    }
+
+    #[test]
+    fn test_cnt_sort_send_disconnected_error() {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(2);
+        drop(receiver);
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let result = test_vector.iter().cnt_sort_send(&sender);
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_channel_disconnected().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_try_sorted_by_counting() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let sorted_iter = test_vector.iter().try_sorted_by_counting().unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.len(), sorted_iter.len());
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_iter.collect::<Vec<u8>>());
+
+        let mut reversed: Vec<u8> = test_vector
+            .iter()
+            .try_sorted_by_counting()
+            .unwrap()
+            .rev()
+            .collect();
+        reversed.reverse();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), reversed);
+
+        let empty_vector: Vec<u8> = vec![];
+        let mut sorted_iter = empty_vector
+            .iter()
+            .try_sorted_by_counting()
+            .unwrap_or_default();
+        assert_eq!(None, sorted_iter.next());
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_trimmed_mean_excludes_outliers() {
+        let test_vector: Vec<i32> = vec![5, -1000, 4, 6, 5, 5, 4, 6, 5, 1000];
+        assert_eq!(5.0, test_vector.iter().trimmed_mean(0.1).unwrap());
+        assert_eq!(4.0, test_vector.iter().trimmed_mean(0.0).unwrap());
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        // trims 1, 2, 3 and 28, 28, 30
+        let expected_mean = f64::from(
+            TEST_ARRAY_SORTED[3..27]
+                .iter()
+                .map(|v| u32::from(*v))
+                .sum::<u32>(),
+        ) / 24.0;
+        assert_eq!(expected_mean, test_vector.iter().trimmed_mean(0.1).unwrap());
+    }
+
+    #[test]
+    fn test_trimmed_mean_invalid_trim_fraction_error() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        for trim_fraction in &[-0.1, 0.5, 1.0, f64::NAN] {
+            let result = test_vector.iter().trimmed_mean(*trim_fraction);
+            assert!(result.is_err());
+            assert_eq!(
+                CountingSortError::from_invalid_trim_fraction().to_string(),
+                result.unwrap_err().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_cnt_sort_recommended() {
+        // n = 30, d = 29
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        assert!(test_vector.iter().cnt_sort_recommended().unwrap());
+        // n = 30, d = 30
+        let mut test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        test_vector[5] = 0;
+        assert!(!test_vector.iter().cnt_sort_recommended().unwrap());
+        let empty_vector: Vec<u8> = vec![];
+        assert!(empty_vector.iter().cnt_sort_recommended().is_err());
+    }
+
+    #[test]
+    fn test_cnt_sort_by_table() {
+        // vowels are category 0, consonants category 1, all other bytes category 2
+        let mut table = [2; 256];
+        for consonant in b'a'..=b'z' {
+            table[usize::from(consonant)] = 1;
+        }
+        for vowel in b"aeiou" {
+            table[usize::from(*vowel)] = 0;
+        }
+        let test_vector = b"counting sort".to_vec();
+        let sorted_vector = test_vector.iter().cnt_sort_by_table(&table).unwrap();
+        assert_eq!(b"ouiocntngsrt ".to_vec(), sorted_vector);
+
+        let empty_vector: Vec<u8> = vec![];
+        assert!(empty_vector.iter().cnt_sort_by_table(&table).is_err());
+    }
+
+    #[test]
+    fn test_cnt_sort_into_slice_does_not_reallocate() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let mut output: Vec<u8> = vec![0; TEST_ARRAY_UNSORTED.len()];
+        let capacity = output.capacity();
+        let pointer = output.as_ptr();
+        test_vector
+            .iter()
+            .cnt_sort_into_slice(output.as_mut_slice())
+            .unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), output);
+        assert_eq!(capacity, output.capacity());
+        assert_eq!(pointer, output.as_ptr());
+    }
+
+    #[test]
+    fn test_cnt_sort_into_slice_length_mismatch_error() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let mut output = [0_u8; 29];
+        let result = test_vector.iter().cnt_sort_into_slice(&mut output);
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_length_mismatch().to_string(),
+            result.unwrap_err().to_string()
+        );
+        assert_eq!([0_u8; 29], output);
+    }
+
+    #[test]
+    fn test_cnt_sort_gaps() {
+        let test_vector = [1, 2, 5, 6];
+        assert_eq!(vec![(2, 5)], test_vector.iter().cnt_sort_gaps().unwrap());
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        assert_eq!(
+            vec![(7, 9), (11, 13), (17, 21), (21, 23), (28, 30)],
+            test_vector.iter().cnt_sort_gaps().unwrap()
+        );
+        let test_vector = [3, 1, 2];
+        assert!(test_vector.iter().cnt_sort_gaps().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cnt_sort_min_max_checked() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let (sorted_vector, observed) = test_vector
+            .iter()
+            .cnt_sort_min_max_checked(&0, &200)
+            .unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector);
+        assert_eq!(Some((TEST_ARRAY_MIN_VALUE, TEST_ARRAY_MAX_VALUE)), observed);
+
+        let empty_vector: Vec<u8> = vec![];
+        let (sorted_vector, observed) = empty_vector
+            .iter()
+            .cnt_sort_min_max_checked(&0, &200)
+            .unwrap();
+        assert!(sorted_vector.is_empty());
+        assert_eq!(None, observed);
+    }
+
+    #[test]
+    fn test_cnt_sort_bool() {
+        let test_vector = [true, false, true, false, false];
+        assert_eq!((3, 2), test_vector.iter().cnt_sort_bool_counts());
+        assert_eq!(
+            vec![false, false, false, true, true],
+            test_vector.iter().cnt_sort().unwrap()
+        );
+        let empty_vector: Vec<bool> = vec![];
+        assert_eq!((0, 0), empty_vector.iter().cnt_sort_bool_counts());
+    }
+
+    #[test]
+    fn test_into_index_bool() {
+        assert_eq!(1, bool::try_into_index(&true, &false).unwrap());
+        assert_eq!(0, bool::try_into_index(&false, &false).unwrap());
+        assert_eq!(0, bool::try_into_index(&true, &true).unwrap());
+        assert!(bool::try_into_index(&false, &true).is_err());
+    }
+
+    #[test]
+    fn test_cnt_sort_with_sentinel() {
+        let mut test_vector: Vec<u16> = TEST_ARRAY_UNSORTED.iter().map(|v| u16::from(*v)).collect();
+        test_vector.insert(3, u16::MAX);
+        test_vector.push(u16::MAX);
+        test_vector.insert(0, u16::MAX);
+        let (sorted_vector, sentinel_count) =
+            test_vector.iter().cnt_sort_with_sentinel(u16::MAX).unwrap();
+        let mut expected_vector: Vec<u16> =
+            TEST_ARRAY_SORTED.iter().map(|v| u16::from(*v)).collect();
+        expected_vector.extend_from_slice(&[u16::MAX; 3]);
+        assert_eq!(expected_vector, sorted_vector);
+        assert_eq!(3, sentinel_count);
+
+        let only_sentinels = [0_u16, 0];
+        assert_eq!(
+            (vec![0, 0], 2),
+            only_sentinels.iter().cnt_sort_with_sentinel(0).unwrap()
+        );
+
+        let empty_vector: Vec<u16> = vec![];
+        let result = empty_vector.iter().cnt_sort_with_sentinel(0);
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_adaptive() {
+        let test_vector = [TEST_ARRAY_UNSORTED, TEST_ARRAY_UNSORTED].concat();
+        assert!(test_vector.len() >= INSERTION_SORT_THRESHOLD);
+        assert_eq!(
+            test_vector.iter().cnt_sort().unwrap(),
+            test_vector.iter().cnt_sort_adaptive().unwrap()
+        );
+        let tiny_vector = TEST_ARRAY_UNSORTED;
+        assert!(tiny_vector.len() < INSERTION_SORT_THRESHOLD);
+        assert_eq!(
+            tiny_vector.iter().cnt_sort().unwrap(),
+            tiny_vector.iter().cnt_sort_adaptive().unwrap()
+        );
+        let equal_vector = [7, 7];
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            equal_vector
+                .iter()
+                .cnt_sort_adaptive()
+                .unwrap_err()
+                .to_string()
+        );
+        let empty_vector: Vec<u8> = vec![];
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            empty_vector
+                .iter()
+                .cnt_sort_adaptive()
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_incremental_sort_update() {
+        let mut test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let mut incremental_sort = test_vector
+            .iter()
+            .cnt_sort_incremental(&0, &u8::MAX)
+            .unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), incremental_sort.sorted());
+        for (position, new) in &[(0, 255), (5, 0), (17, 100), (29, 3), (0, 42)] {
+            let old = test_vector[*position];
+            test_vector[*position] = *new;
+            incremental_sort.update(old, *new).unwrap();
+            assert_eq!(
+                test_vector.iter().cnt_sort().unwrap(),
+                incremental_sort.sorted()
+            );
+        }
+        assert_eq!(test_vector.len(), incremental_sort.len());
+        assert!(!incremental_sort.is_empty());
+    }
+
+    #[test]
+    fn test_incremental_sort_errors() {
+        let test_vector: Vec<u8> = vec![10, 20];
+        let mut incremental_sort = test_vector.iter().cnt_sort_incremental(&10, &30).unwrap();
+        assert_eq!(
+            CountingSortError::from_value_not_present().to_string(),
+            incremental_sort.update(15, 20).unwrap_err().to_string()
+        );
+        assert_eq!(
+            CountingSortError::from_index_out_of_bounds().to_string(),
+            incremental_sort.update(10, 31).unwrap_err().to_string()
+        );
+        assert_eq!(vec![10, 20], incremental_sort.sorted());
+
+        assert!(test_vector.iter().cnt_sort_incremental(&10, &15).is_err());
+        assert!(test_vector.iter().cnt_sort_incremental(&30, &10).is_err());
+        let empty_vector: Vec<u8> = vec![];
+        assert!(empty_vector
+            .iter()
+            .cnt_sort_incremental(&0, &1)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_cnt_sort_windowed() {
+        let test_vector: Vec<u32> = TEST_ARRAY_UNSORTED.iter().map(|v| u32::from(*v)).collect();
+        let expected_vector: Vec<u32> = TEST_ARRAY_SORTED.iter().map(|v| u32::from(*v)).collect();
+        assert_eq!(
+            expected_vector,
+            test_vector.iter().cnt_sort_windowed(0, 150).unwrap()
+        );
+        assert_eq!(
+            expected_vector,
+            test_vector
+                .iter()
+                .cnt_sort_windowed(u32::from(TEST_ARRAY_MIN_VALUE), 30)
+                .unwrap()
+        );
+        let signed_vector = [-3, 5, -1, 0];
+        assert_eq!(
+            vec![-3, -1, 0, 5],
+            signed_vector.iter().cnt_sort_windowed(-5, 11).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_windowed_errors() {
+        let test_vector: Vec<u32> = vec![10, 20, 30];
+        for (base, window) in &[(0, 30), (11, 100), (10, 20)] {
+            assert_eq!(
+                CountingSortError::from_index_out_of_bounds().to_string(),
+                test_vector
+                    .iter()
+                    .cnt_sort_windowed(*base, *window)
+                    .unwrap_err()
+                    .to_string()
+            );
+        }
+        assert!(test_vector.iter().cnt_sort_windowed(10, 21).is_ok());
+        let empty_vector: Vec<u32> = vec![];
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            empty_vector
+                .iter()
+                .cnt_sort_windowed(0, 150)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_rows_by_column() {
+        let rows = vec![vec![1, 7, 100], vec![2, 3, 200], vec![3, 7, 300]];
+        assert_eq!(
+            vec![vec![2, 3, 200], vec![1, 7, 100], vec![3, 7, 300]],
+            cnt_sort_rows_by_column(rows, 1).unwrap()
+        );
+        let rows = vec![vec![5, 1], vec![4, 1]];
+        assert_eq!(rows.clone(), cnt_sort_rows_by_column(rows, 1).unwrap());
+        assert!(cnt_sort_rows_by_column(vec![], 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cnt_sort_rows_by_column_short_row_error() {
+        let rows = vec![vec![1, 7], vec![2]];
+        assert_eq!(
+            CountingSortError::from_column_out_of_bounds().to_string(),
+            cnt_sort_rows_by_column(rows, 1).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_sorted_collector() {
+        let mut collector = SortedCollector::new();
+        assert!(collector.is_empty());
+        collector.extend(TEST_ARRAY_UNSORTED[..10].iter());
+        collector.extend(TEST_ARRAY_UNSORTED[10..].iter().rev().copied());
+        assert_eq!(TEST_ARRAY_UNSORTED.len(), collector.len());
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), collector.into_sorted().unwrap());
+
+        let mut collector = SortedCollector::new();
+        collector.extend(vec![-1, -5, 10, -20, 3, -20]);
+        assert_eq!(
+            vec![-20, -20, -5, -1, 3, 10],
+            collector.into_sorted().unwrap()
+        );
+
+        let collector: SortedCollector<u8> = SortedCollector::default();
+        assert!(collector.into_sorted().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sorted_collector_into_index_failed_error() {
+        #[derive(Ord, PartialOrd, PartialEq, Eq, Copy, Clone, Debug)]
+        struct ValueWithTryIntoError {
+            value: u8,
+        }
+
+        impl TryIntoIndex for ValueWithTryIntoError {
+            type Error = String;
+            fn try_into_index(_value: &Self, _min_value: &Self) -> Result<usize, Self::Error> {
+                Err(String::from("TryInto always fails"))
+            }
+        }
+
+        let mut collector = SortedCollector::new();
+        collector.push(ValueWithTryIntoError { value: 1 });
+        collector.extend(vec![
+            ValueWithTryIntoError { value: 2 },
+            ValueWithTryIntoError { value: 0 },
+        ]);
+        assert!(collector.is_empty());
+        assert_eq!(
+            CountingSortError::from_try_into_index_failed().to_string(),
+            collector.into_sorted().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_summary() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        assert_eq!(
+            "n=30 min=1 max=30 distinct=23 mode=3(x3)",
+            test_vector.iter().cnt_sort_summary().unwrap()
+        );
+        let test_vector = [-4, -4];
+        assert_eq!(
+            "n=2 min=-4 max=-4 distinct=1 mode=-4(x2)",
+            test_vector.iter().cnt_sort_summary().unwrap()
+        );
+        let empty_vector: Vec<u8> = vec![];
+        assert!(empty_vector.iter().cnt_sort_summary().is_err());
+    }
+
+    #[test]
+    fn test_error_is_send_sync() {
+        let handle = std::thread::spawn(|| {
+            let empty_vector: Vec<u8> = vec![];
+            empty_vector.iter().cnt_sort()
+        });
+        let error = handle.join().unwrap().unwrap_err();
+        let boxed_error: Box<dyn Error + Send + Sync> = Box::new(error);
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            boxed_error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_with_duplicates() {
+        let test_vector = [9, 4, 1, 4, 6, 9, 2];
+        let (sorted_vector, duplicate_values) =
+            test_vector.iter().cnt_sort_with_duplicates().unwrap();
+        assert_eq!(vec![1, 2, 4, 4, 6, 9, 9], sorted_vector);
+        assert_eq!(vec![4, 9], duplicate_values);
+
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let (sorted_vector, duplicate_values) =
+            test_vector.iter().cnt_sort_with_duplicates().unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector);
+        assert_eq!(vec![3, 7, 11, 21, 28], duplicate_values);
+
+        let test_vector = [5, 5];
+        assert_eq!(
+            (vec![5, 5], vec![5]),
+            test_vector.iter().cnt_sort_with_duplicates().unwrap()
+        );
+        let empty_vector: Vec<u8> = vec![];
+        assert!(empty_vector.iter().cnt_sort_with_duplicates().is_err());
+    }
+
+    #[test]
+    fn test_sorted_vector_capacity_equals_length() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let sorted_vectors = vec![
+            test_vector.iter().cnt_sort().unwrap(),
+            test_vector.iter().cnt_sort_min_max(&0, &100).unwrap(),
+            test_vector.iter().cnt_sort_with_fill(0).unwrap(),
+            test_vector.iter().cnt_sort_adaptive().unwrap(),
+            test_vector
+                .iter()
+                .filter(|v| **v > 3)
+                .cnt_sort_adaptive()
+                .unwrap(),
+            test_vector.iter().cnt_sort_shuffle_ties(1).unwrap(),
+            test_vector.iter().cnt_sort_windowed(0, 31).unwrap(),
+            test_vector.iter().cnt_sort_with_sentinel(3).unwrap().0,
+            test_vector.iter().cnt_sort_with_duplicates().unwrap().0,
+            test_vector.iter().cnt_sort_partition(&10).unwrap().0,
+            test_vector.iter().cnt_sort_partition(&10).unwrap().1,
+            test_vector
+                .iter()
+                .cnt_sort_incremental(&0, &100)
+                .unwrap()
+                .sorted(),
+        ];
+        for sorted_vector in &sorted_vectors {
+            assert_eq!(sorted_vector.len(), sorted_vector.capacity());
+        }
+    }
+
+    #[test]
+    fn test_single_element() {
+        let test_vector = [42];
+        assert_eq!(vec![42], test_vector.iter().cnt_sort().unwrap());
+        assert_eq!(vec![42], test_vector.iter().cnt_sort_with_fill(0).unwrap());
+        assert_eq!(vec![42], test_vector.iter().cnt_sort_adaptive().unwrap());
+        assert_eq!([42], cnt_sort_array([42]).unwrap());
+        assert_eq!(1, test_vector.iter().cnt_sort_estimate_copies().unwrap());
+        let mut output = [0];
+        test_vector.iter().cnt_sort_into_slice(&mut output).unwrap();
+        assert_eq!([42], output);
+        let mut output = [0, 0];
+        assert_eq!(
+            CountingSortError::from_length_mismatch().to_string(),
+            test_vector
+                .iter()
+                .cnt_sort_into_slice(&mut output)
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            vec![42],
+            cnt_sort_lines(std::io::Cursor::new("\n42\n\n")).unwrap()
+        );
+        assert!(cnt_sort_lines(std::io::Cursor::new("42\n42\n")).is_err());
+        let result = [42, 42].iter().cnt_sort();
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_cnt_sort_unstable() {
+        let mut rng = oorandom::Rand32::new(42);
+        let test_vector: Vec<i16> = (0..100_000)
+            .map(|_| i16::try_from(rng.rand_range(0..2000)).unwrap() - 1000)
+            .collect();
+        assert_eq!(
+            test_vector.iter().cnt_sort().unwrap(),
+            par_cnt_sort_unstable(&test_vector).unwrap()
+        );
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        assert_eq!(
+            TEST_ARRAY_SORTED.to_vec(),
+            par_cnt_sort_unstable(&test_vector).unwrap()
+        );
+        assert_eq!(vec![7], par_cnt_sort_unstable(&[7]).unwrap());
+        assert!(par_cnt_sort_unstable(&[7, 7]).is_err());
+        let empty_vector: Vec<u8> = vec![];
+        assert!(par_cnt_sort_unstable(&empty_vector).is_err());
+        // the distance can not be converted into a count vector length, like for cnt_sort
+        let test_vector = [0, usize::MAX];
+        assert_eq!(
+            test_vector.iter().cnt_sort().unwrap_err().to_string(),
+            par_cnt_sort_unstable(&test_vector).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_with_entropy() {
+        let test_vector = [7_u8; 10];
+        let (sorted_vector, entropy) = test_vector.iter().cnt_sort_with_entropy().unwrap();
+        assert_eq!(test_vector.to_vec(), sorted_vector);
+        assert!(entropy.abs() < f64::EPSILON);
+
+        let test_vector: Vec<u8> = (0..=255).rev().chain(0..=255).collect();
+        let (sorted_vector, entropy) = test_vector.iter().cnt_sort_with_entropy().unwrap();
+        assert_eq!(test_vector.iter().cnt_sort().unwrap(), sorted_vector);
+        assert!((entropy - 8.0).abs() < 1e-12);
+
+        let empty_vector: Vec<u8> = vec![];
+        assert!(empty_vector.iter().cnt_sort_with_entropy().is_err());
+    }
+
+    #[test]
+    fn test_offset_index() {
+        assert_eq!(5, offset_index(i128::MIN + 5, i128::MIN).unwrap());
+        assert_eq!(0, offset_index(i128::MAX, i128::MAX).unwrap());
+        assert!(offset_index(i128::MAX, i128::MIN).is_err());
+        assert!(offset_index(i128::MAX, -1).is_err());
+        assert!(offset_index(i128::MIN, i128::MAX).is_err());
+        assert!(offset_index(0, 1).is_err());
+        assert_eq!(
+            usize::try_from(u64::MAX).ok(),
+            offset_index(i64::MAX, i64::MIN).ok()
+        );
+        assert_eq!(255, offset_index(u8::MAX, 0).unwrap());
+        assert_eq!(
+            usize::try_from(u64::MAX).ok(),
+            offset_index(u64::MAX, 0).ok()
+        );
+    }
+
+    #[cfg(feature = "ordered-float")]
+    #[test]
+    fn test_cnt_sort_ordered_float() {
+        use ordered_float::OrderedFloat;
+
+        // neighbouring floats, since every representable float needs its own index
+        let test_vector = [
+            OrderedFloat(f32::from_bits(0x3f80_0005)),
+            OrderedFloat(1.0_f32),
+            OrderedFloat(f32::from_bits(0x3f80_0002)),
+            OrderedFloat(f32::from_bits(0x3f7f_fffe)),
+        ];
+        let mut expected_vector = test_vector.to_vec();
+        expected_vector.sort();
+        assert_eq!(expected_vector, test_vector.iter().cnt_sort().unwrap());
+
+        let smallest_positive = f32::from_bits(1);
+        let test_vector = [
+            OrderedFloat(smallest_positive),
+            OrderedFloat(0.0_f32),
+            OrderedFloat(-smallest_positive),
+            OrderedFloat(-0.0),
+        ];
+        let sorted_vector = test_vector.iter().cnt_sort().unwrap();
+        assert_eq!(
+            vec![-smallest_positive, 0.0, -0.0, smallest_positive],
+            sorted_vector
+                .iter()
+                .map(|value| value.into_inner())
+                .collect::<Vec<f32>>()
+        );
+        // -0.0 equals 0.0, hence the sort is stable
+        assert!(sorted_vector[2].into_inner().is_sign_negative());
+
+        assert_eq!(
+            1,
+            OrderedFloat::try_into_index(&OrderedFloat(f32::NAN), &OrderedFloat(f32::INFINITY))
+                .unwrap()
+        );
+    }
+
+    #[cfg(feature = "ordered-float")]
+    #[test]
+    fn test_cnt_sort_quantized() {
+        use ordered_float::OrderedFloat;
+
+        let test_vector: Vec<OrderedFloat<f32>> = TEST_ARRAY_UNSORTED
+            .iter()
+            .map(|value| OrderedFloat(f32::from(*value) / 10.0))
+            .collect();
+        let mut expected_vector = test_vector.clone();
+        expected_vector.sort();
+        assert_eq!(
+            expected_vector,
+            cnt_sort_quantized(test_vector.iter(), 0.0, 3.0, 300).unwrap()
+        );
+        // two buckets: only separated into values below and above 1.5
+        let approximately_sorted = cnt_sort_quantized(test_vector.iter(), 0.0, 3.0, 2).unwrap();
+        let split_position = approximately_sorted
+            .iter()
+            .position(|value| value.into_inner() >= 1.5)
+            .unwrap();
+        assert!(approximately_sorted[..split_position]
+            .iter()
+            .all(|value| value.into_inner() < 1.5));
+        assert!(approximately_sorted[split_position..]
+            .iter()
+            .all(|value| value.into_inner() >= 1.5));
+
+        assert!(cnt_sort_quantized(test_vector.iter(), 0.0, 2.0, 10).is_err());
+        assert!(cnt_sort_quantized(test_vector.iter(), 3.0, 0.0, 10).is_err());
+        assert!(cnt_sort_quantized(test_vector.iter(), 0.0, 3.0, 0).is_err());
+        assert!(cnt_sort_quantized([OrderedFloat(f32::NAN)].iter(), 0.0, 3.0, 10).is_err());
+        assert!(cnt_sort_quantized([].iter(), 0.0, 3.0, 10).is_err());
+    }
+
+    #[test]
+    fn test_cnt_sort_auto_counter() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        assert_eq!(
+            TEST_ARRAY_SORTED.to_vec(),
+            test_vector.iter().cnt_sort_auto_counter().unwrap()
+        );
+        let test_vector: Vec<u16> = (0..=u16::MAX).rev().step_by(3).collect();
+        assert!(u16::try_from(COMPACT_COUNTER_MIN_DISTANCE).is_ok());
+        assert_eq!(
+            test_vector.iter().cnt_sort().unwrap(),
+            test_vector.iter().cnt_sort_auto_counter().unwrap()
+        );
+        assert_eq!(vec![9], [9].iter().cnt_sort_auto_counter().unwrap());
+        assert!([9, 9].iter().cnt_sort_auto_counter().is_err());
+        let empty_vector: Vec<u8> = vec![];
+        assert!(empty_vector.iter().cnt_sort_auto_counter().is_err());
+    }
+
+    #[test]
+    fn test_count_and_re_order_with_counter() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let min_value = TEST_ARRAY_MIN_VALUE;
+        let max_value = TEST_ARRAY_MAX_VALUE;
+        assert_eq!(
+            TEST_ARRAY_SORTED.to_vec(),
+            count_and_re_order_with_counter::<_, _, u32>(
+                test_vector.iter(),
+                &min_value,
+                &max_value
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            TEST_ARRAY_SORTED.to_vec(),
+            count_and_re_order_with_counter::<_, _, usize>(
+                test_vector.iter(),
+                &min_value,
+                &max_value
+            )
+            .unwrap()
+        );
+        let result =
+            count_and_re_order_with_counter::<_, _, u32>(test_vector.iter(), &min_value, &10);
+        assert_eq!(
+            CountingSortError::from_index_out_of_bounds().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_clamped_histogram() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let (counts, underflow_count, overflow_count) = test_vector
+            .iter()
+            .cnt_sort_clamped_histogram(3, 28)
+            .unwrap();
+        assert_eq!(26, counts.len());
+        assert_eq!(2, underflow_count);
+        assert_eq!(1, overflow_count);
+        // 1 and 2 are clamped into the bucket of 3, 30 into the bucket of 28
+        assert_eq!(5, counts[0]);
+        assert_eq!(3, counts[25]);
+        assert_eq!(TEST_ARRAY_UNSORTED.len(), counts.iter().sum::<usize>());
+
+        let (counts, underflow_count, overflow_count) = test_vector
+            .iter()
+            .cnt_sort_clamped_histogram(0, 100)
+            .unwrap();
+        assert_eq!(&TEST_COUNT_VALUES_ARRAY[..], &counts[..31]);
+        assert_eq!((0, 0), (underflow_count, overflow_count));
+
+        assert!(test_vector.iter().cnt_sort_clamped_histogram(5, 4).is_err());
+        let empty_vector: Vec<u8> = vec![];
+        assert_eq!(
+            (vec![0, 0], 0, 0),
+            empty_vector
+                .iter()
+                .cnt_sort_clamped_histogram(4, 5)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_cnt_sort_floats() {
+        let test_vector = [
+            f32::from_bits(0x3f80_0005),
+            f32::NAN,
+            1.0,
+            f32::from_bits(0x3f80_0002),
+            -f32::NAN,
+            f32::from_bits(0x3f7f_fffe),
+        ];
+        let expected_vector = [
+            f32::from_bits(0x3f7f_fffe),
+            1.0,
+            f32::from_bits(0x3f80_0002),
+            f32::from_bits(0x3f80_0005),
+        ];
+
+        let sorted_vector = cnt_sort_floats(test_vector.iter(), NanPolicy::First).unwrap();
+        assert!(sorted_vector[..2].iter().all(|value| value.is_nan()));
+        assert_eq!(expected_vector, sorted_vector[2..]);
+
+        let sorted_vector = cnt_sort_floats(test_vector.iter(), NanPolicy::Last).unwrap();
+        assert_eq!(expected_vector, sorted_vector[..4]);
+        assert!(sorted_vector[4..].iter().all(|value| value.is_nan()));
+        // NaNs keep their input order
+        assert!(sorted_vector[5].is_sign_negative());
+
+        assert_eq!(
+            CountingSortError::from_nan_encountered().to_string(),
+            cnt_sort_floats(test_vector.iter(), NanPolicy::Error)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_cnt_sort_floats_zero_and_edge_cases() {
+        let smallest_positive = f32::from_bits(1);
+        let test_vector = [0.0, smallest_positive, -0.0, -smallest_positive];
+        let sorted_vector = cnt_sort_floats(test_vector.iter(), NanPolicy::Error).unwrap();
+        assert_eq!(
+            vec![-smallest_positive, 0.0, -0.0, smallest_positive],
+            sorted_vector
+        );
+        // -0.0 equals 0.0, hence the sort is stable
+        assert!(sorted_vector[2].is_sign_negative());
+
+        assert_eq!(
+            3,
+            cnt_sort_floats([f32::NAN; 3].iter(), NanPolicy::Last)
+                .unwrap()
+                .len()
+        );
+        assert_eq!(
+            vec![2.5],
+            cnt_sort_floats([2.5].iter(), NanPolicy::Error).unwrap()
+        );
+        assert!(cnt_sort_floats([].iter(), NanPolicy::First).is_err());
+    }
+
+    #[test]
+    fn test_cnt_sort_range_slice() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        assert_eq!(
+            TEST_ARRAY_SORTED[10..20].to_vec(),
+            test_vector.iter().cnt_sort_range_slice(10, 20).unwrap()
+        );
+        assert_eq!(
+            TEST_ARRAY_SORTED[25..].to_vec(),
+            test_vector.iter().cnt_sort_range_slice(25, 100).unwrap()
+        );
+        assert_eq!(
+            TEST_ARRAY_SORTED.to_vec(),
+            test_vector.iter().cnt_sort_range_slice(0, 30).unwrap()
+        );
+        assert!(test_vector
+            .iter()
+            .cnt_sort_range_slice(30, 40)
+            .unwrap()
+            .is_empty());
+        assert!(test_vector
+            .iter()
+            .cnt_sort_range_slice(5, 2)
+            .unwrap()
+            .is_empty());
+        let empty_vector: Vec<u8> = vec![];
+        assert!(empty_vector.iter().cnt_sort_range_slice(0, 1).is_err());
+    }
+
+    #[test]
+    fn test_cnt_sort_permutations() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let (sort_permutation, inverse_permutation) =
+            test_vector.iter().cnt_sort_permutations().unwrap();
+        let gathered: Vec<u8> = sort_permutation
+            .iter()
+            .map(|original_index| test_vector[*original_index])
+            .collect();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), gathered);
+        for (original_index, position) in inverse_permutation.iter().enumerate() {
+            assert_eq!(original_index, sort_permutation[*position]);
+        }
+        for (position, original_index) in sort_permutation.iter().enumerate() {
+            assert_eq!(position, inverse_permutation[*original_index]);
+        }
+
+        let test_vector = [4, 4, 4];
+        assert_eq!(
+            (vec![0, 1, 2], vec![0, 1, 2]),
+            test_vector.iter().cnt_sort_permutations().unwrap()
+        );
+        let empty_vector: Vec<u8> = vec![];
+        assert!(empty_vector.iter().cnt_sort_permutations().is_err());
+    }
+
+    #[test]
+    fn test_reconstruct_sorted_round_trip() {
+        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
+        let (counts, _, _) = test_vector
+            .iter()
+            .cnt_sort_clamped_histogram(TEST_ARRAY_MIN_VALUE, TEST_ARRAY_MAX_VALUE)
+            .unwrap();
+        let sorted_vector = reconstruct_sorted(&counts, &TEST_ARRAY_MIN_VALUE).unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector);
+        assert_eq!(sorted_vector.len(), sorted_vector.capacity());
+
+        let test_vector = [-100_i16, 300, -100, 0];
+        let (counts, _, _) = test_vector
+            .iter()
+            .cnt_sort_clamped_histogram(-100, 300)
+            .unwrap();
+        assert_eq!(
+            test_vector.iter().cnt_sort().unwrap(),
+            reconstruct_sorted(&counts, &-100).unwrap()
+        );
+        assert!(reconstruct_sorted::<u8>(&[], &0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reconstruct_sorted_errors() {
+        assert_eq!(
+            CountingSortError::from_try_from_index_failed().to_string(),
+            reconstruct_sorted(&[1, 0, 1], &u8::MAX)
+                .unwrap_err()
+                .to_string()
+        );
+        // empty buckets out of range are never converted
+        assert_eq!(
+            vec![u8::MAX],
+            reconstruct_sorted(&[1, 0], &u8::MAX).unwrap()
+        );
+        assert_eq!(
+            CountingSortError::from_index_out_of_bounds().to_string(),
+            reconstruct_sorted(&[usize::MAX, 1], &0_u32)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_try_from_index() {
+        assert_eq!(
+            i32::MAX,
+            i32::try_from_index(u32::MAX as usize, &i32::MIN).unwrap()
+        );
+        assert!(i32::try_from_index(u32::MAX as usize + 1, &i32::MIN).is_err());
+        assert_eq!(usize::MAX, usize::try_from_index(usize::MAX, &0).unwrap());
+        assert!(usize::try_from_index(usize::MAX, &1).is_err());
+        assert!(bool::try_from_index(1, &false).unwrap());
+        assert!(!bool::try_from_index(0, &false).unwrap());
+        assert!(bool::try_from_index(1, &true).is_err());
+        for value in -128..=127_i8 {
+            let index = i8::try_into_index(&value, &i8::MIN).unwrap();
+            assert_eq!(value, i8::try_from_index(index, &i8::MIN).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_non_deterministic_source_error() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        // yields one element less on every new pass
+        #[derive(Clone)]
+        struct ShrinkingIter<'a> {
+            slice: &'a [u8],
+            position: usize,
+            passes: Rc<Cell<usize>>,
+        }
+
+        impl<'a> Iterator for ShrinkingIter<'a> {
+            type Item = &'a u8;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.position == 0 {
+                    self.passes.set(self.passes.get() + 1);
+                }
+                let length = self.slice.len().saturating_sub(self.passes.get());
+                let item = self.slice[..length].get(self.position);
+                self.position += 1;
+                item
+            }
+        }
+
+        let iterator = ShrinkingIter {
+            slice: &TEST_ARRAY_UNSORTED,
+            position: 0,
+            passes: Rc::new(Cell::new(0)),
+        };
+        assert_eq!(
+            CountingSortError::from_non_deterministic_source().to_string(),
+            iterator.cnt_sort().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_with_payload() {
+        let mut keys = TEST_ARRAY_UNSORTED.to_vec();
+        let mut payload: Vec<usize> = (0..keys.len()).collect();
+        cnt_sort_with_payload(&mut keys, &mut payload).unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), keys);
+        let (sort_permutation, _) = TEST_ARRAY_UNSORTED.iter().cnt_sort_permutations().unwrap();
+        assert_eq!(sort_permutation, payload);
+
+        let mut empty_keys: Vec<u8> = vec![];
+        let mut empty_payload: Vec<u8> = vec![];
+        assert!(cnt_sort_with_payload(&mut empty_keys, &mut empty_payload).is_ok());
+    }
+
+    #[test]
+    fn test_cnt_sort_u8_counts() {
+        let counts = cnt_sort_u8_counts(&TEST_ARRAY_UNSORTED);
+        assert_eq!(TEST_COUNT_VALUES_ARRAY, counts[..31]);
+        assert!(counts[31..].iter().all(|count| *count == 0));
+        assert_eq!([0; 256], cnt_sort_u8_counts(&[]));
+    }
+
+    #[test]
+    fn test_cnt_sort_i8_counts() {
+        let slice: Vec<i8> = vec![-128, -1, -1, 0, 1, 127, 127, 127];
+        let counts = cnt_sort_i8_counts(&slice);
+        assert_eq!(1, counts[0]);
+        assert_eq!(2, counts[127]);
+        assert_eq!(1, counts[128]);
+        assert_eq!(1, counts[129]);
+        assert_eq!(3, counts[255]);
+        assert_eq!(slice.len(), counts.iter().sum::<usize>());
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_generate_bounded() {
+        let vector = generate_bounded::<u8>(1000, 1, 30, 7);
+        assert_eq!(1000, vector.len());
+        assert!(vector.iter().all(|value| (1..=30).contains(value)));
+        assert_eq!(vector, generate_bounded::<u8>(1000, 1, 30, 7));
+        assert_ne!(vector, generate_bounded::<u8>(1000, 1, 30, 8));
+
+        let vector = generate_bounded::<i32>(100, i32::MIN, i32::MAX, 0);
+        assert_eq!(vector, generate_bounded::<i32>(100, i32::MIN, i32::MAX, 0));
+
+        assert_eq!(vec![5, 5], generate_bounded::<u16>(2, 5, 5, 1));
+        assert!(generate_bounded::<u16>(0, 0, 10, 1).is_empty());
+    }
+
+    #[test]
+    fn test_cnt_sort_longest_run() {
+        // 3 and 7 both appear three times, the smaller value wins
+        let result = TEST_ARRAY_UNSORTED.iter().cnt_sort_longest_run();
+        assert_eq!((3, 3), result.unwrap());
+
+        let vector: Vec<i16> = vec![-5, 100, -5, 42, -5, 100, -5, 7, -5];
+        assert_eq!((-5, 5), vector.iter().cnt_sort_longest_run().unwrap());
+
+        assert_eq!((9, 1), [9u8].iter().cnt_sort_longest_run().unwrap());
+
+        let empty: Vec<u8> = vec![];
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            empty.iter().cnt_sort_longest_run().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_grouped() {
+        let groups = TEST_ARRAY_UNSORTED.iter().cnt_sort_grouped().unwrap();
+        assert_eq!(23, groups.len());
+        let flattened: Vec<u8> = groups
+            .iter()
+            .flat_map(|(_, group)| group.iter().copied())
+            .collect();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), flattened);
+        for (value, group) in &groups {
+            assert!(group.iter().all(|element| element == value));
+            assert_eq!(TEST_COUNT_VALUES_ARRAY[usize::from(*value)], group.len());
+            assert_eq!(group.len(), group.capacity());
+        }
+        assert_eq!(groups.len(), groups.capacity());
+    }
+
+    #[test]
+    fn test_try_into_index_for_char() {
+        assert_eq!(0, char::try_into_index(&'a', &'a').unwrap());
+        assert_eq!(25, char::try_into_index(&'z', &'a').unwrap());
+        assert!(char::try_into_index(&'a', &'z').is_err());
+
+        let vector = ['é', 'c', 'a', 'Z', 'b'];
+        assert_eq!(
+            vec!['Z', 'a', 'b', 'c', 'é'],
+            vector.iter().cnt_sort().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_chars_ci() {
+        let vector = ['b', 'A', 'a', 'B'];
+        assert_eq!(
+            vec!['A', 'a', 'b', 'B'],
+            vector.iter().cnt_sort_chars_ci().unwrap()
+        );
+
+        let vector: Vec<char> = "Hello, World!".chars().collect();
+        let sorted_vector: String = vector
+            .iter()
+            .cnt_sort_chars_ci()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(" !,deHllloorW", sorted_vector);
+
+        let vector: Vec<char> = "The quick brown fox jumps over the lazy dog"
+            .chars()
+            .collect();
+        let sorted_vector = vector.iter().cnt_sort_chars_ci().unwrap();
+        assert_eq!(vector.len(), sorted_vector.len());
+        assert_eq!(sorted_vector.len(), sorted_vector.capacity());
+
+        // chars that differ only in case keep their input order
+        let vector = ['x', 'X'];
+        assert_eq!(vec!['x', 'X'], vector.iter().cnt_sort_chars_ci().unwrap());
+        let vector = ['X', 'x', 'X'];
+        assert_eq!(
+            vec!['X', 'x', 'X'],
+            vector.iter().cnt_sort_chars_ci().unwrap()
+        );
+
+        let vector = ['x', 'x'];
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            vector.iter().cnt_sort_chars_ci().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_with_stability_reverse_stable() {
+        let result = TEST_ARRAY_UNSORTED
+            .iter()
+            .cnt_sort_with_stability(Stability::ReverseStable);
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), result.unwrap());
+
+        let result = [7u8]
+            .iter()
+            .cnt_sort_with_stability(Stability::ReverseStable);
+        assert_eq!(vec![7], result.unwrap());
+
+        let result = [7u8, 7]
+            .iter()
+            .cnt_sort_with_stability(Stability::ReverseStable);
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_with_origins() {
+        let sorted_with_origins = TEST_ARRAY_UNSORTED.iter().cnt_sort_with_origins().unwrap();
+        let (values, origins): (Vec<u8>, Vec<usize>) = sorted_with_origins.into_iter().unzip();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), values);
+        for (value, origin) in values.iter().zip(origins.iter()) {
+            assert_eq!(*value, TEST_ARRAY_UNSORTED[*origin]);
+        }
+        let (sort_permutation, _) = TEST_ARRAY_UNSORTED.iter().cnt_sort_permutations().unwrap();
+        assert_eq!(sort_permutation, origins);
+
+        let empty: Vec<u8> = vec![];
+        assert!(empty.iter().cnt_sort_with_origins().is_err());
+    }
+
+    #[test]
+    fn test_cnt_sort_u8_inplace() {
+        let mut slice = TEST_ARRAY_UNSORTED;
+        assert!(cnt_sort_u8_inplace(&mut slice).is_ok());
+        assert_eq!(TEST_ARRAY_SORTED, slice);
+
+        let mut slice = [255, 0, 128, 0, 255];
+        assert!(cnt_sort_u8_inplace(&mut slice).is_ok());
+        assert_eq!([0, 0, 128, 255, 255], slice);
+
+        let mut slice = [42];
+        assert!(cnt_sort_u8_inplace(&mut slice).is_ok());
+        assert_eq!([42], slice);
+
+        let mut slice = [42, 42];
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            cnt_sort_u8_inplace(&mut slice).unwrap_err().to_string()
+        );
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            cnt_sort_u8_inplace(&mut []).unwrap_err().to_string()
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_counting_sort_tracing_span() {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        // records all span names and recorded fields as "name" and "field=value"
+        struct RecordingSubscriber(Arc<Mutex<Vec<String>>>);
+
+        impl Visit for RecordingSubscriber {
+            fn record_debug(&mut self, field: &Field, value: &dyn core::fmt::Debug) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("{}={:?}", field.name(), value));
+            }
+        }
+
+        impl Subscriber for RecordingSubscriber {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(span.metadata().name().to_string());
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, values: &Record<'_>) {
+                values.record(&mut RecordingSubscriber(Arc::clone(&self.0)));
+            }
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = RecordingSubscriber(Arc::clone(&recorded));
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(TEST_ARRAY_UNSORTED.iter().cnt_sort().is_ok());
+        });
+        assert_eq!(
+            vec![
+                "counting_sort",
+                "distance=29",
+                "count_vector_size=31",
+                "elements=30"
+            ],
+            *recorded.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_with_collision_check() {
+        // lossy, since two neighbouring values are converted into the same index
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct HalvedValue(u16);
+
+        impl TryIntoIndex for HalvedValue {
             type Error = CountingSortError;
-            #[inline]
+
             fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
-                // u8 and u16 should always fit into an usize. Therefore no TryInto is needed.
-                Ok(usize::from(*value - *min_value))
+                Ok(usize::from(value.0 / 2) - usize::from(min_value.0 / 2))
             }
         }
-    };
-}
 
-// macro instances for signed integer implementation of TryIntoIndex
-try_into_index_impl_for_signed!(i8, i16);
-try_into_index_impl_for_signed!(i16, i32);
-try_into_index_impl_for_signed!(i32, i64);
-// i64 was not added, since i64 could be larger than usize and can also
-// result in huge memory consumption if the distance between max_value and
-// min_value of the collection is huge.
+        let vector = [HalvedValue(10), HalvedValue(4), HalvedValue(11)];
+        let result = vector.iter().cnt_sort_with_collision_check();
+        assert_eq!(
+            CountingSortError::from_index_collision().to_string(),
+            result.unwrap_err().to_string()
+        );
+        // without the opt-in check both colliding elements are treated as equal
+        assert_eq!(
+            vec![HalvedValue(4), HalvedValue(10), HalvedValue(11)],
+            vector.iter().cnt_sort().unwrap()
+        );
 
-// macro instances for small unsigned integer implementation of TryIntoIndex
-try_into_index_impl_for_small_unsigned!(u8);
-try_into_index_impl_for_small_unsigned!(u16);
+        // equal elements do not collide
+        let vector = [HalvedValue(10), HalvedValue(4), HalvedValue(10)];
+        let result = vector.iter().cnt_sort_with_collision_check();
+        assert_eq!(
+            vec![HalvedValue(4), HalvedValue(10), HalvedValue(10)],
+            result.unwrap()
+        );
+    }
 
-// macro instances for unsigned integer implementation of TryIntoIndex
-try_into_index_impl_for_unsigned!(u32);
-try_into_index_impl_for_unsigned!(usize);
-// u64 was not added, since i64 could be larger than usize and can also
-// result in huge memory consumption if the distance between max_value and
-// min_value of the collection is huge.
+    #[test]
+    fn test_count_total() {
+        assert_eq!(30, count_total(&TEST_COUNT_VALUES_ARRAY).unwrap());
+        assert_eq!(0, count_total(&[]).unwrap());
+        assert_eq!(
+            CountingSortError::from_contract_violation().to_string(),
+            count_total(&[0, usize::MAX, 1]).unwrap_err().to_string()
+        );
+    }
 
-#[inline]
-fn counting_sort<'a, ITER, T>(iterator: ITER) -> Result<Vec<T>, CountingSortError>
-where
-    ITER: Iterator<Item = &'a T> + Clone,
-    T: Ord + Copy + TryIntoIndex + 'a,
-{
-    let optional_tuple = get_min_max(&mut iterator.clone());
-    if let Some((min_value, max_value)) = optional_tuple {
-        counting_sort_min_max(iterator, min_value, max_value)
-    } else {
-        Err(CountingSortError::from_empty_iterator())
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_cnt_sort_with_stats() {
+        let (sorted_vector, stats) = TEST_ARRAY_UNSORTED.iter().cnt_sort_with_stats().unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector);
+        let sum: i128 = TEST_ARRAY_UNSORTED
+            .iter()
+            .map(|value| i128::from(*value))
+            .sum();
+        assert_eq!(
+            Stats {
+                min: TEST_ARRAY_MIN_VALUE,
+                max: TEST_ARRAY_MAX_VALUE,
+                count: 30,
+                sum,
+                mean: 417.0 / 30.0,
+            },
+            stats
+        );
+        assert_eq!(417, sum);
+
+        let vector: Vec<i32> = vec![i32::MIN, i32::MIN + 2];
+        let (_, stats) = vector.iter().cnt_sort_with_stats().unwrap();
+        assert_eq!(2 * i128::from(i32::MIN) + 2, stats.sum);
+        assert_eq!(f64::from(i32::MIN) + 1.0, stats.mean);
+
+        let (sorted_vector, stats) = [5u8].iter().cnt_sort_with_stats().unwrap();
+        assert_eq!(vec![5], sorted_vector);
+        assert_eq!((5, 5, 1, 5), (stats.min, stats.max, stats.count, stats.sum));
+
+        let empty: Vec<u8> = vec![];
+        assert!(empty.iter().cnt_sort_with_stats().is_err());
     }
-}
 
-#[inline]
-fn counting_sort_min_max<'a, ITER, T>(
-    iterator: ITER,
-    min_value: &T,
-    max_value: &T,
-) -> Result<Vec<T>, CountingSortError>
-where
-    ITER: Iterator<Item = &'a T> + Clone,
-    T: Ord + Copy + TryIntoIndex + 'a,
-{
-    if min_value == max_value {
-        return Err(CountingSortError::from_sorting_unnecessary());
+    #[test]
+    fn test_growing_counting_sorter() {
+        let mut sorter = GrowingCountingSorter::new();
+        assert_eq!(None, sorter.bounds());
+        assert!(sorter.sort_batch(&[]).unwrap().is_empty());
+
+        let mut expected_capacity = 0;
+        for width in &[10_i16, 5, 40, 40, 100] {
+            let base = 1000 - 3 * width;
+            let batch: Vec<i16> = (0..=*width).map(|i| base + (i * 7) % (width + 1)).collect();
+            let mut expected = batch.clone();
+            expected.sort_unstable();
+            assert_eq!(expected, sorter.sort_batch(&batch).unwrap());
+            // the count vector only grows with the widest distance
+            expected_capacity = max(expected_capacity, usize::try_from(*width).unwrap() + 2);
+            assert_eq!(expected_capacity, sorter.count_vector.len());
+        }
+        assert_eq!(Some((700, 990)), sorter.bounds());
+
+        assert_eq!(vec![2000, 2000], sorter.sort_batch(&[2000, 2000]).unwrap());
+        assert_eq!(Some((700, 2000)), sorter.bounds());
+
+        let sorted_vector = sorter
+            .sort_batch(&TEST_ARRAY_UNSORTED.map(i16::from))
+            .unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.map(i16::from).to_vec(), sorted_vector);
+        assert_eq!(Some((1, 2000)), sorter.bounds());
+    }
+
+    #[test]
+    fn test_cnt_sort_to_sorted_vec() {
+        let sorted_vec = TEST_ARRAY_UNSORTED.iter().cnt_sort_to_sorted_vec().unwrap();
+        assert_eq!(TEST_ARRAY_SORTED, *sorted_vec);
+        assert_eq!(0, sorted_vec.rank(&TEST_ARRAY_MIN_VALUE));
+        assert_eq!(2, sorted_vec.rank(&3));
+        assert_eq!(5, sorted_vec.rank(&4));
+        assert_eq!(30, sorted_vec.rank(&31));
+        assert!(sorted_vec.contains(&7));
+        assert!(!sorted_vec.contains(&8));
+        assert!(!sorted_vec.contains(&0));
+        assert_eq!(30, sorted_vec.len());
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vec.into_vec());
+
+        let empty: Vec<u8> = vec![];
+        assert!(empty.iter().cnt_sort_to_sorted_vec().is_err());
+    }
+
+    #[test]
+    fn test_check_try_into_index_monotonic() {
+        // reverses the order of the values within [0, 100]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Reversed(u8);
+
+        impl TryIntoIndex for Reversed {
+            type Error = CountingSortError;
+
+            fn try_into_index(value: &Self, _min_value: &Self) -> Result<usize, Self::Error> {
+                Ok(usize::from(100 - value.0))
+            }
+        }
+
+        // the order ignores the tag, but the index does not
+        #[derive(Copy, Clone, Debug)]
+        struct Tagged {
+            value: u8,
+            tag: u8,
+        }
+
+        impl PartialEq for Tagged {
+            fn eq(&self, other: &Self) -> bool {
+                self.value == other.value
+            }
+        }
+
+        impl Eq for Tagged {}
+
+        impl PartialOrd for Tagged {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Tagged {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.value.cmp(&other.value)
+            }
+        }
+
+        impl TryIntoIndex for Tagged {
+            type Error = CountingSortError;
+
+            fn try_into_index(value: &Self, _min_value: &Self) -> Result<usize, Self::Error> {
+                Ok(usize::from(value.value) + usize::from(value.tag))
+            }
+        }
+
+        assert!(
+            check_try_into_index_monotonic(&TEST_ARRAY_UNSORTED, &TEST_ARRAY_MIN_VALUE).is_ok()
+        );
+        assert!(check_try_into_index_monotonic(&[i32::MAX, 0, i32::MIN], &i32::MIN).is_ok());
+        assert!(check_try_into_index_monotonic::<u16>(&[], &0).is_ok());
+
+        let samples = [Reversed(10), Reversed(50), Reversed(20)];
+        assert_eq!(
+            "samples[0] <= samples[2] but their indices 90 > 80 are not monotonic",
+            check_try_into_index_monotonic(&samples, &Reversed(0)).unwrap_err()
+        );
+        assert_eq!(
+            "converting samples[1] into an index failed, is it smaller than min_value?",
+            check_try_into_index_monotonic(&[5_u8, 1], &2).unwrap_err()
+        );
+
+        let a = Tagged { value: 1, tag: 0 };
+        let b = Tagged { value: 1, tag: 10 };
+        assert_eq!(
+            "samples[0] == samples[1] but their indices 1 != 11 differ",
+            check_try_into_index_monotonic(&[a, b], &a).unwrap_err()
+        );
+        assert_eq!(
+            "samples[1] == samples[0] but their indices 1 != 11 differ",
+            check_try_into_index_monotonic(&[b, a], &a).unwrap_err()
+        );
+        assert!(check_try_into_index_monotonic(&[b, b, a], &a).is_err());
+        assert!(check_try_into_index_monotonic(&[a, a], &a).is_ok());
+    }
+
+    #[test]
+    fn test_cnt_sort_with_locator() {
+        let (sorted_vector, locator) = TEST_ARRAY_UNSORTED.iter().cnt_sort_with_locator().unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector);
+        for value in TEST_ARRAY_MIN_VALUE..=TEST_ARRAY_MAX_VALUE {
+            let positions = locator.positions(&value).unwrap();
+            assert_eq!(TEST_COUNT_VALUES_ARRAY[usize::from(value)], positions.len());
+            assert!(sorted_vector[positions]
+                .iter()
+                .all(|element| *element == value));
+        }
+        assert_eq!(Some(2..5), locator.positions(&3));
+        assert_eq!(Some(29..29), locator.positions(&29));
+        assert_eq!(None, locator.positions(&0));
+        assert_eq!(None, locator.positions(&31));
+
+        let (sorted_vector, locator) = [4_u8, 4].iter().cnt_sort_with_locator().unwrap();
+        assert_eq!(vec![4, 4], sorted_vector);
+        assert_eq!(Some(0..2), locator.positions(&4));
+
+        let empty: Vec<u8> = vec![];
+        assert!(empty.iter().cnt_sort_with_locator().is_err());
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_cnt_sort_i32_biased() {
+        // order preserving at the extremes of the i32 range
+        assert_eq!(0, bias_i32(i32::MIN));
+        assert_eq!(1, bias_i32(i32::MIN + 1));
+        assert_eq!(0x7FFF_FFFF, bias_i32(-1));
+        assert_eq!(0x8000_0000, bias_i32(0));
+        assert_eq!(u32::MAX, bias_i32(i32::MAX));
+
+        for base in &[i32::MIN, -1000, -5, 0, i32::MAX - 2000] {
+            let vector: Vec<i32> = TEST_ARRAY_UNSORTED
+                .iter()
+                .map(|value| base + i32::from(*value) * 66)
+                .collect();
+            assert_eq!(
+                vector.iter().cnt_sort().unwrap(),
+                cnt_sort_i32_biased(vector.iter()).unwrap()
+            );
+        }
+        let vector = [i32::MAX, i32::MAX - 1, i32::MAX];
+        assert_eq!(
+            vec![i32::MAX - 1, i32::MAX, i32::MAX],
+            cnt_sort_i32_biased(vector.iter()).unwrap()
+        );
+        let vector = [i32::MIN + 1, i32::MIN, i32::MIN];
+        assert_eq!(
+            vec![i32::MIN, i32::MIN, i32::MIN + 1],
+            cnt_sort_i32_biased(vector.iter()).unwrap()
+        );
+
+        assert_eq!(vec![-3], cnt_sort_i32_biased([-3].iter()).unwrap());
+        assert!(cnt_sort_i32_biased([-3, -3].iter()).is_err());
+        assert!(cnt_sort_i32_biased([].iter()).is_err());
+    }
+
+    #[test]
+    fn test_cnt_sort_via_u8() {
+        let sorted_vector = cnt_sort_via_u8(TEST_ARRAY_UNSORTED.iter()).unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector);
+        assert_eq!(vec![0, 255], cnt_sort_via_u8([255_u8, 0].iter()).unwrap());
+    }
+
+    #[test]
+    fn test_cnt_sort_min_max_or() {
+        let vector: Vec<u8> = vec![9, 9, 9];
+        let mut called = false;
+        let result = vector.iter().cnt_sort_min_max_or(&9, &9, || {
+            called = true;
+            vector.clone()
+        });
+        assert_eq!(vector, result.unwrap());
+        assert!(called);
+
+        let result = TEST_ARRAY_UNSORTED.iter().cnt_sort_min_max_or(
+            &TEST_ARRAY_MIN_VALUE,
+            &TEST_ARRAY_MAX_VALUE,
+            || unreachable!(),
+        );
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), result.unwrap());
+
+        let result = TEST_ARRAY_UNSORTED.iter().cnt_sort_min_max_or(
+            &TEST_ARRAY_MAX_VALUE,
+            &TEST_ARRAY_MIN_VALUE,
+            Vec::new,
+        );
+        assert_eq!(
+            CountingSortError::from_min_value_larger_max_value().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_count_values_distance_overflow_error() {
+        // a distance of usize::MAX, e.g. the full u16 range on a 16-bit target, overflows d + 2
+        let min_value = ValueWithMaxIndex { value: 0 };
+        let max_value = ValueWithMaxIndex { value: 7 };
+        let test_vector = [min_value, max_value];
+        let result = count_values(&mut test_vector.iter(), &min_value, &max_value);
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_try_into_index_failed().to_string(),
+            result.unwrap_err().to_string()
+        );
+        let result = test_vector.iter().cnt_sort();
+        assert_eq!(
+            CountingSortError::from_try_into_index_failed().to_string(),
+            result.unwrap_err().to_string()
+        );
+        let result = test_vector.iter().cnt_sort_min_max(&min_value, &max_value);
+        assert_eq!(
+            CountingSortError::from_try_into_index_failed().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_calculate_prefix_sum_saturates() {
+        let mut test_vector = [0, usize::MAX, 1, 2];
+        calculate_prefix_sum(&mut test_vector);
+        assert_eq!([0, usize::MAX, usize::MAX, usize::MAX], test_vector);
+    }
+
+    #[cfg(target_pointer_width = "16")]
+    #[test]
+    fn test_cnt_sort_full_u16_range_on_16_bit_target() {
+        let test_vector = [u16::MIN, u16::MAX];
+        let result = test_vector.iter().cnt_sort();
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_try_into_index_failed().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[cfg(target_pointer_width = "16")]
+    #[test]
+    fn test_cnt_sort_more_than_u16_max_elements_on_16_bit_target() {
+        let iterator = core::iter::repeat(&1_u8)
+            .take(usize::MAX)
+            .chain(core::iter::once(&2_u8));
+        let result = iterator.cnt_sort();
+        assert!(result.is_err());
+        // the count of each value fits, but their sum overflows
+        assert_eq!(
+            CountingSortError::from_contract_violation().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_prefix_sum_and_re_order_count_total_overflow_error() {
+        // simulates the counts of usize::MAX times 1 and once 2, which can not be iterated
+        let test_vector = [1_u8, 2];
+        let mut count_vector = [0, usize::MAX, 1];
+        let result = prefix_sum_and_re_order(test_vector.iter(), &mut count_vector, &1, 1);
+        assert!(result.is_err());
+        assert_eq!(
+            CountingSortError::from_contract_violation().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_distinct() {
+        let test_vector: Vec<u32> = (0..200).rev().map(|value| value * 7).collect();
+        let sorted_vector = test_vector.iter().cnt_sort_distinct().unwrap();
+        let expected_vector: Vec<u32> = (0..200).map(|value| value * 7).collect();
+        assert_eq!(expected_vector, sorted_vector);
+
+        let sorted_vector = [-64_i8, 63, 0, -1, 64].iter().cnt_sort_distinct().unwrap();
+        assert_eq!(vec![-64, -1, 0, 63, 64], sorted_vector);
+
+        assert_eq!(vec![9], [9_u8].iter().cnt_sort_distinct().unwrap());
+
+        let empty: Vec<u8> = vec![];
+        let result = empty.iter().cnt_sort_distinct();
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_distinct_duplicate_found() {
+        let result = TEST_ARRAY_UNSORTED.iter().cnt_sort_distinct();
+        assert_eq!(
+            CountingSortError::from_duplicate_found().to_string(),
+            result.unwrap_err().to_string()
+        );
+        let result = [5_u16, 5].iter().cnt_sort_distinct();
+        assert_eq!(
+            CountingSortError::from_duplicate_found().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_sorted_vec_into_iterator() {
+        let sorted_vec = TEST_ARRAY_UNSORTED.iter().cnt_sort_to_sorted_vec().unwrap();
+        let mut sum: u32 = 0;
+        for value in &sorted_vec {
+            sum += u32::from(*value);
+        }
+        assert_eq!(417, sum);
+        // iterating by reference does not consume the sorted elements
+        assert_eq!(TEST_ARRAY_SORTED.len(), (&sorted_vec).into_iter().count());
+        assert_eq!(Some(&TEST_ARRAY_MAX_VALUE), sorted_vec.iter().last());
+        let collected_vector: Vec<u8> = sorted_vec.into_iter().collect();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), collected_vector);
+    }
+
+    #[test]
+    fn test_cnt_sort_cancellable() {
+        let cancel = AtomicBool::new(false);
+        let sorted_vector = TEST_ARRAY_UNSORTED
+            .iter()
+            .cnt_sort_cancellable(&cancel)
+            .unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector);
+
+        let test_vector: Vec<u16> = (0..10_000).rev().collect();
+        let sorted_vector = test_vector.iter().cnt_sort_cancellable(&cancel).unwrap();
+        assert_eq!(
+            test_vector.iter().rev().copied().collect::<Vec<u16>>(),
+            sorted_vector
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_cancellable_cancelled() {
+        let cancel = AtomicBool::new(true);
+        let result = TEST_ARRAY_UNSORTED.iter().cnt_sort_cancellable(&cancel);
+        assert_eq!(
+            CountingSortError::from_cancelled().to_string(),
+            result.unwrap_err().to_string()
+        );
+        // a cancelled sort of an empty iterator is cancelled as well
+        let empty: Vec<u8> = vec![];
+        let result = empty.iter().cnt_sort_cancellable(&cancel);
+        assert_eq!(
+            CountingSortError::from_cancelled().to_string(),
+            result.unwrap_err().to_string()
+        );
+        // the iterator stops yielding elements once cancelled
+        let mut iterator = CancellableIter {
+            iterator: TEST_ARRAY_UNSORTED.iter(),
+            cancel: &cancel,
+            position: 0,
+        };
+        assert_eq!(None, iterator.next());
+    }
+
+    #[test]
+    fn test_cnt_sort_weighted() {
+        let pairs = TEST_COUNT_VALUES_ARRAY
+            .iter()
+            .enumerate()
+            .map(|(value, count)| (u8::try_from(value).unwrap(), *count));
+        assert_eq!(
+            TEST_ARRAY_SORTED.to_vec(),
+            cnt_sort_weighted(pairs).unwrap()
+        );
+
+        // weights of the same value are added up
+        let sorted_vector = cnt_sort_weighted([(-2_i8, 1), (4, 1), (-2, 2)].iter().copied());
+        assert_eq!(vec![-2, -2, -2, 4], sorted_vector.unwrap());
+
+        assert_eq!(
+            vec![7],
+            cnt_sort_weighted([(7_u8, 1), (9, 0)].iter().copied()).unwrap()
+        );
     }
-    if min_value > max_value {
-        return Err(CountingSortError::from_min_value_larger_max_value());
+
+    #[test]
+    fn test_cnt_sort_weighted_errors() {
+        let result = cnt_sort_weighted([(7_u8, 0)].iter().copied());
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            result.unwrap_err().to_string()
+        );
+        let result = cnt_sort_weighted([(7_u8, 2), (9, 0)].iter().copied());
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            result.unwrap_err().to_string()
+        );
+        let result = cnt_sort_weighted([(1_u8, usize::MAX), (2, 1)].iter().copied());
+        assert_eq!(
+            CountingSortError::from_index_out_of_bounds().to_string(),
+            result.unwrap_err().to_string()
+        );
     }
-    let mut count_vector = count_values(&mut iterator.clone(), min_value, max_value)?;
 
-    calculate_prefix_sum(&mut count_vector);
-    // last element of the count vector depicts the index-1 of the largest element, hence it is its length
-    let length = *count_vector.last().unwrap(); // it's safe to unwrap, since vector has at least one element
-    re_order(iterator, &mut count_vector, length, &min_value)
-}
+    #[test]
+    fn test_cnt_first_occurrences() {
+        let first_occurrences = [3, 1, 3, 2, 1].iter().cnt_first_occurrences().unwrap();
+        assert_eq!(vec![3, 1, 2], first_occurrences);
 
-#[inline]
-fn re_order<'a, T, ITER>(
-    iterator: ITER,
-    count_vector: &mut Vec<usize>,
-    length: usize,
-    min_value: &T,
-) -> Result<Vec<T>, CountingSortError>
-where
-    T: Ord + Copy + TryIntoIndex + 'a,
-    ITER: Iterator<Item = &'a T>,
-{
-    let mut sorted_vector: Vec<T> = vec![*min_value; length];
-    for value in iterator {
-        let index_count_vector_result = T::try_into_index(value, min_value);
-        if index_count_vector_result.is_err() {
-            return Err(CountingSortError::from_try_into_index_failed());
-        } else {
-            // index_count_vector_result is ok, unwrapping is safe
-            let index_count_vector = index_count_vector_result.unwrap_or(0);
-            if index_count_vector >= count_vector.len() {
-                return Err(CountingSortError::from_index_out_of_bounds());
+        let first_occurrences = TEST_ARRAY_UNSORTED.iter().cnt_first_occurrences().unwrap();
+        let mut expected_vector: Vec<u8> = Vec::new();
+        for value in &TEST_ARRAY_UNSORTED {
+            if !expected_vector.contains(value) {
+                expected_vector.push(*value);
             }
-            //
-            /*
-              Get the cumulative frequency of the value before this.
-              The cumulative frequency of the preceeding value is the index of
-              the first element with this value.
-
-              In order to avoid checks for the index to be 0 (and therefore
-              not to try to access the -1-th element) we allocated the 0-the
-              element additionally so that we can now safely access it.
-              Additionally it holds the index of the next element which
-              equals the minimum value.
-            */
-            let mut index = count_vector[index_count_vector];
-            sorted_vector[index] = *value;
-            /*
-              Increment the index so that successive elements with the same value
-              do not override this one.
-              This additionally ensures that the sort is stable.
-              This actually increments the cumulative frequency of the preceeding
-              value. However at the end of the sorting process this frequency will
-              be the cumulative frequency of this value.
-            */
-            index += 1;
-            count_vector[index_count_vector] = index;
         }
-    }
-    Ok(sorted_vector)
-}
+        assert_eq!(expected_vector, first_occurrences);
 
-#[inline]
-fn count_values<'a, ITER, T>(
-    iterator: &mut ITER,
-    min_value: &T,
-    max_value: &T,
-) -> Result<Vec<usize>, CountingSortError>
-where
-    ITER: Iterator<Item = &'a T>,
-    T: Ord + Copy + TryIntoIndex + 'a,
-{
-    let distance_result = T::try_into_index(max_value, min_value);
-    if distance_result.is_ok() {
-        /*
-          Length must hold all possible distinct values of the collection,
-          this means the complete distance + 1. E.g. the distance of 0 and 255
-          is 255, but there 256 distinct values between 0 and 255.
+        assert_eq!(
+            vec![-4],
+            [-4_i32, -4].iter().cnt_first_occurrences().unwrap()
+        );
 
-          We allocate another value in our vector to represent the value that preceeds
-          the minimum value. This value actually does not exist in the collection but
-          is introduced as an optimization for enabling the stable sort of this algorithm
-          without the need of a DoubleEndedIterator and the reverse iteration of the
-          collection when the given collection is re-ordered.
-        */
-        let length = distance_result.unwrap_or(0) + 2; // distance_result is okay so unwrapping is safe
-        let mut count_vector: Vec<usize> = vec![0; length];
+        let empty: Vec<u8> = vec![];
+        let result = empty.iter().cnt_first_occurrences();
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            result.unwrap_err().to_string()
+        );
+    }
 
-        for value in iterator {
-            let index_result = T::try_into_index(value, min_value);
-            if index_result.is_err() {
-                return Err(CountingSortError::from_try_into_index_failed());
-            } else {
-                /*
-                  Always add + 1 to not use the 0-the element in the vector.
-                  This element is just allocated to optimize the re-ordering
-                  of the given collection later on.
-                  The 0-the element does in a way represent the value that preceeds
-                  the minimum value, i.e. this value does not exist in the given
-                  collection.
-                */
-                let index = index_result.unwrap_or(0) + 1; // index_result is ok, unwrapping is safe
-                if index >= count_vector.len() {
-                    return Err(CountingSortError::from_index_out_of_bounds());
-                }
-                let new_count_value = count_vector[index] + 1;
-                count_vector[index] = new_count_value;
+    #[test]
+    fn test_cnt_sort_dictionary() {
+        let (dictionary, start_offsets) = TEST_ARRAY_UNSORTED.iter().cnt_sort_dictionary().unwrap();
+        assert_eq!(dictionary.len(), start_offsets.len());
+        for (index, (value, start_offset)) in dictionary.iter().zip(&start_offsets).enumerate() {
+            assert_eq!(*value, TEST_ARRAY_SORTED[*start_offset]);
+            if *start_offset > 0 {
+                assert!(TEST_ARRAY_SORTED[*start_offset - 1] < *value);
             }
+            let end_offset = start_offsets
+                .get(index + 1)
+                .copied()
+                .unwrap_or(TEST_ARRAY_SORTED.len());
+            assert_eq!(
+                TEST_COUNT_VALUES_ARRAY[usize::from(*value)],
+                end_offset - start_offset
+            );
         }
-        return Ok(count_vector);
-    }
-    Err(CountingSortError::from_try_into_index_failed())
-}
 
-#[inline]
-fn calculate_prefix_sum(count_vector: &mut Vec<usize>) {
-    let mut iterator = count_vector.iter_mut();
-    // skip first element
-    let optional_first_element = iterator.next();
-    if let Some(first_element) = optional_first_element {
-        let mut total = *first_element;
-        for value in iterator {
-            total += *value;
-            *value = total;
-        }
+        let (dictionary, start_offsets) = [5_u8, 5, 5].iter().cnt_sort_dictionary().unwrap();
+        assert_eq!(vec![5], dictionary);
+        assert_eq!(vec![0], start_offsets);
+
+        let empty: Vec<u8> = vec![];
+        assert!(empty.iter().cnt_sort_dictionary().is_err());
     }
-}
 
-#[inline]
-fn get_min_max<T, ITER>(iterator: &mut ITER) -> Option<(T, T)>
-where
-    T: Ord + Copy,
-    ITER: Iterator<Item = T>,
-{
-    // consume first element to initialize as min and max value
-    let min_value_optional = iterator.next();
-    if let Some(min_value) = min_value_optional {
-        let tuple = iterator.fold((min_value, min_value), |(min_val, max_val), value| {
-            (min(min_val, value), max(max_val, value))
-        });
-        return Some(tuple);
+    #[test]
+    fn test_cnt_sort_ordered() {
+        let sorted_vector = TEST_ARRAY_UNSORTED
+            .iter()
+            .cnt_sort_ordered::<Ascending>()
+            .unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector);
+
+        let sorted_vector = TEST_ARRAY_UNSORTED
+            .iter()
+            .cnt_sort_ordered::<Descending>()
+            .unwrap();
+        let mut expected_vector = TEST_ARRAY_SORTED.to_vec();
+        expected_vector.reverse();
+        assert_eq!(expected_vector, sorted_vector);
+
+        assert_eq!(
+            vec![-3],
+            [-3_i8].iter().cnt_sort_ordered::<Descending>().unwrap()
+        );
+        let result = [2_u8, 2].iter().cnt_sort_ordered::<Descending>();
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            result.unwrap_err().to_string()
+        );
+        let empty: Vec<u8> = vec![];
+        assert!(empty.iter().cnt_sort_ordered::<Ascending>().is_err());
     }
-    None
-}
 
-#[cfg(test)]
-#[cfg(not(tarpaulin_include))]
-mod unit_tests {
+    #[test]
+    fn test_cnt_sort_ordered_is_stable() {
+        // pairs ordered by their first element only
+        #[derive(Copy, Clone, Debug)]
+        struct Pair(u8, usize);
 
-    use super::*;
+        impl PartialEq for Pair {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
 
-    const TEST_ARRAY_MIN_VALUE: u8 = 1;
+        impl Eq for Pair {}
 
-    const TEST_ARRAY_MAX_VALUE: u8 = 30;
+        impl PartialOrd for Pair {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
 
-    const TEST_ARRAY_UNSORTED: [u8; 30] = [
-        13, 24, 27, 3, 10, 1, 9, 17, 6, 7, 3, 30, 14, 15, 2, 3, 7, 11, 21, 16, 7, 11, 21, 5, 23,
-        25, 26, 28, 28, 4,
-    ];
+        impl Ord for Pair {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
 
-    const TEST_ARRAY_SORTED: [u8; 30] = [
-        1, 2, 3, 3, 3, 4, 5, 6, 7, 7, 7, 9, 10, 11, 11, 13, 14, 15, 16, 17, 21, 21, 23, 24, 25, 26,
-        27, 28, 28, 30,
-    ];
+        impl TryIntoIndex for Pair {
+            type Error = CountingSortError;
 
-    const TEST_COUNT_VALUES_ARRAY: [usize; 31] = [
-        0, 1, 1, 3, 1, 1, 1, 3, 0, 1, 1, 2, 0, 1, 1, 1, 1, 1, 0, 0, 0, 2, 0, 1, 1, 1, 1, 1, 2, 0, 1,
-    ];
+            fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+                u8::try_into_index(&value.0, &min_value.0)
+            }
+        }
 
-    const TEST_PREFIX_SUM_ARRAY: [usize; 31] = [
-        0, 1, 2, 5, 6, 7, 8, 11, 11, 12, 13, 15, 15, 16, 17, 18, 19, 20, 20, 20, 20, 22, 22, 23,
-        24, 25, 26, 27, 29, 29, 30,
-    ];
+        let test_vector: Vec<Pair> = TEST_ARRAY_UNSORTED
+            .iter()
+            .enumerate()
+            .map(|(position, value)| Pair(*value, position))
+            .collect();
+        for sorted_vector in &[
+            test_vector.iter().cnt_sort_ordered::<Ascending>().unwrap(),
+            test_vector.iter().cnt_sort_ordered::<Descending>().unwrap(),
+        ] {
+            for window in sorted_vector.windows(2) {
+                if window[0] == window[1] {
+                    assert!(window[0].1 < window[1].1);
+                }
+            }
+        }
+    }
 
     #[test]
-    fn test_cnt_sort_i8_vector() {
-        let test_vector: Vec<i8> = vec![2, -2, 1, -6];
-        let sorted_vector = counting_sort(test_vector.iter()).unwrap();
-        assert_eq!(vec![-6, -2, 1, 2], sorted_vector);
+    fn test_cnt_sort_checksum() {
+        let checksum = TEST_ARRAY_UNSORTED.iter().cnt_sort_checksum().unwrap();
+        assert_eq!(
+            checksum,
+            TEST_ARRAY_SORTED.iter().cnt_sort_checksum().unwrap()
+        );
+        let mut shuffled_vector = TEST_ARRAY_UNSORTED.to_vec();
+        shuffled_vector.rotate_left(11);
+        shuffled_vector.swap(0, 17);
+        assert_eq!(
+            checksum,
+            shuffled_vector.iter().cnt_sort_checksum().unwrap()
+        );
+
+        // changing a single element changes the checksum
+        shuffled_vector[4] += 1;
+        assert_ne!(
+            checksum,
+            shuffled_vector.iter().cnt_sort_checksum().unwrap()
+        );
+
+        // the checksum depends on the values, not only on their distances to the minimum value
+        assert_ne!(
+            [1_u8, 2].iter().cnt_sort_checksum().unwrap(),
+            [2_u8, 3].iter().cnt_sort_checksum().unwrap()
+        );
+        let empty: Vec<u8> = vec![];
+        assert!(empty.iter().cnt_sort_checksum().is_err());
     }
 
     #[test]
-    fn test_cnt_sort_i8_vector_with_overflow() {
-        let test_vector: Vec<i8> = vec![2, -100, 50, -6];
-        let sorted_vector = counting_sort(test_vector.iter()).unwrap();
-        assert_eq!(vec![-100, -6, 2, 50], sorted_vector);
+    fn test_fnv1a() {
+        use core::hash::Hasher;
+
+        // test vectors of the FNV-1a reference implementation
+        let hasher = Fnv1a::default();
+        assert_eq!(0xcbf2_9ce4_8422_2325, hasher.finish());
+        let mut hasher = Fnv1a::default();
+        hasher.write(b"a");
+        assert_eq!(0xaf63_dc4c_8601_ec8c, hasher.finish());
+        let mut hasher = Fnv1a::default();
+        hasher.write(b"foobar");
+        assert_eq!(0x8594_4171_f739_67e8, hasher.finish());
     }
 
     #[test]
-    fn test_cnt_sort_u8_vector() {
-        let mut test_vector = TEST_ARRAY_UNSORTED.to_vec();
-        test_vector = test_vector.iter().cnt_sort().unwrap();
-        let sorted_vector = TEST_ARRAY_SORTED.to_vec();
-        assert_eq!(sorted_vector, test_vector);
+    fn test_cnt_sort_or_empty() {
+        let empty: Vec<u8> = vec![];
+        assert_eq!(Vec::<u8>::new(), empty.iter().cnt_sort_or_empty().unwrap());
+        assert_eq!(
+            TEST_ARRAY_SORTED.to_vec(),
+            TEST_ARRAY_UNSORTED.iter().cnt_sort_or_empty().unwrap()
+        );
+        let result = [2_u8, 2].iter().cnt_sort_or_empty();
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            result.unwrap_err().to_string()
+        );
+        // genuine errors are still returned
+        let test_vector = [
+            ValueWithMaxIndex { value: 0 },
+            ValueWithMaxIndex { value: 7 },
+        ];
+        let result = test_vector.iter().cnt_sort_or_empty();
+        assert_eq!(
+            CountingSortError::from_try_into_index_failed().to_string(),
+            result.unwrap_err().to_string()
+        );
     }
 
     #[test]
-    fn test_cnt_sort_min_max_u8_vector() {
-        let mut test_vector = TEST_ARRAY_UNSORTED.to_vec();
-        test_vector = test_vector
+    fn test_reconstruct_sorted_desc() {
+        let (counts, _, _) = TEST_ARRAY_UNSORTED
             .iter()
-            .cnt_sort_min_max(&TEST_ARRAY_MIN_VALUE, &TEST_ARRAY_MAX_VALUE)
+            .cnt_sort_clamped_histogram(TEST_ARRAY_MIN_VALUE, TEST_ARRAY_MAX_VALUE)
             .unwrap();
-        let sorted_vector = TEST_ARRAY_SORTED.to_vec();
-        assert_eq!(sorted_vector, test_vector);
+        let sorted_vector = reconstruct_sorted_desc(&counts, &TEST_ARRAY_MIN_VALUE).unwrap();
+        let mut expected_vector = TEST_ARRAY_SORTED.to_vec();
+        expected_vector.reverse();
+        assert_eq!(expected_vector, sorted_vector);
+        assert_eq!(sorted_vector.len(), sorted_vector.capacity());
+
+        assert_eq!(
+            vec![2_i8, 0, 0, -1],
+            reconstruct_sorted_desc(&[1, 2, 0, 1], &-1).unwrap()
+        );
+        assert!(reconstruct_sorted_desc::<u8>(&[], &0).unwrap().is_empty());
+        assert_eq!(
+            CountingSortError::from_try_from_index_failed().to_string(),
+            reconstruct_sorted_desc(&[1, 0, 1], &u8::MAX)
+                .unwrap_err()
+                .to_string()
+        );
     }
 
     #[test]
-    fn test_into_index_i8() {
-        assert_eq!(255, i8::try_into_index(&127, &-128).unwrap());
-        assert_eq!(0, i8::try_into_index(&-128, &-128).unwrap());
-        assert_eq!(150, i8::try_into_index(&50, &-100).unwrap());
-        assert_eq!(50, i8::try_into_index(&-50, &-100).unwrap());
-        assert_eq!(27, i8::try_into_index(&127, &100).unwrap());
+    fn test_cnt_sort_cumulative() {
+        let cumulative: Vec<(u8, usize)> = TEST_ARRAY_UNSORTED
+            .iter()
+            .cnt_sort_cumulative()
+            .unwrap()
+            .collect();
+        assert_eq!(
+            Some(&(TEST_ARRAY_MAX_VALUE, TEST_ARRAY_UNSORTED.len())),
+            cumulative.last()
+        );
+        for (value, cumulative_count) in &cumulative {
+            let expected_count = TEST_ARRAY_UNSORTED
+                .iter()
+                .filter(|element| *element <= value)
+                .count();
+            assert_eq!(expected_count, *cumulative_count);
+        }
+        assert!(cumulative.windows(2).all(|window| window[0] < window[1]));
+
+        let cumulative: Vec<(u8, usize)> =
+            [4_u8, 4].iter().cnt_sort_cumulative().unwrap().collect();
+        assert_eq!(vec![(4, 2)], cumulative);
+
+        let empty: Vec<u8> = vec![];
+        assert!(empty.iter().cnt_sort_cumulative().is_err());
     }
 
+    type InPlaceSort<T> = fn(&mut [T]) -> Result<(), CountingSortError>;
+
     #[test]
-    fn test_into_index_i16() {
-        assert_eq!(0xFFFF, i16::try_into_index(&32767, &-32768).unwrap());
-        assert_eq!(0, i16::try_into_index(&-32768, &-32768).unwrap());
-        assert_eq!(0, i16::try_into_index(&32767, &32767).unwrap());
+    fn test_cnt_sort_in_place_u8() {
+        let in_place_sorts: [InPlaceSort<u8>; 2] = [cnt_sort_in_place, cnt_sort_in_place_stable];
+        for in_place_sort in &in_place_sorts {
+            let mut slice = TEST_ARRAY_UNSORTED;
+            assert!(in_place_sort(&mut slice).is_ok());
+            assert_eq!(TEST_ARRAY_SORTED, slice);
+
+            let mut slice = [9_u8];
+            assert!(in_place_sort(&mut slice).is_ok());
+            assert_eq!([9], slice);
+
+            let mut slice = [9_u8, 9];
+            assert_eq!(
+                CountingSortError::from_sorting_unnecessary().to_string(),
+                in_place_sort(&mut slice).unwrap_err().to_string()
+            );
+            let mut empty: [u8; 0] = [];
+            assert_eq!(
+                CountingSortError::from_empty_iterator().to_string(),
+                in_place_sort(&mut empty).unwrap_err().to_string()
+            );
+        }
     }
 
     #[test]
-    fn test_into_index_i32() {
+    fn test_cnt_sort_in_place_i16() {
+        let in_place_sorts: [InPlaceSort<i16>; 2] = [cnt_sort_in_place, cnt_sort_in_place_stable];
+        for in_place_sort in &in_place_sorts {
+            let mut rng = oorandom::Rand32::new(97);
+            let mut test_vector: Vec<i16> = (0..2000)
+                .map(|_| i16::try_from(rng.rand_range(0..2001)).unwrap() - 1000)
+                .collect();
+            let sorted_vector = test_vector.iter().cnt_sort().unwrap();
+            assert!(in_place_sort(&mut test_vector).is_ok());
+            assert_eq!(sorted_vector, test_vector);
+
+            let mut slice = [i16::MAX, i16::MIN, 0, -1, i16::MIN];
+            assert!(in_place_sort(&mut slice).is_ok());
+            assert_eq!([i16::MIN, i16::MIN, -1, 0, i16::MAX], slice);
+        }
+    }
+
+    #[test]
+    fn test_stable_partition_in_place() {
+        let mut slice = [1, 8, 2, 9, 3, 7, 4];
+        assert_eq!(
+            4,
+            stable_partition_in_place(&mut slice, &|value: &i32| *value < 5)
+        );
+        assert_eq!([1, 2, 3, 4, 8, 9, 7], slice);
+    }
+
+    #[test]
+    fn test_cnt_sort_read() {
+        let sorted_bytes = cnt_sort_read(std::io::Cursor::new(TEST_ARRAY_UNSORTED)).unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_bytes);
+
+        // more bytes than fit into a single chunk
+        let bytes: Vec<u8> = (0..20_000_u32).map(|value| (value % 251) as u8).collect();
+        let mut expected_bytes = bytes.clone();
+        expected_bytes.sort_unstable();
+        assert_eq!(expected_bytes, cnt_sort_read(bytes.as_slice()).unwrap());
+
+        assert_eq!(vec![7], cnt_sort_read(&[7_u8][..]).unwrap());
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            cnt_sort_read(&[7_u8, 7][..]).unwrap_err().to_string()
+        );
         assert_eq!(
-            0xFFFFFFFF,
-            i32::try_into_index(&2147483647, &-2147483648).unwrap()
+            CountingSortError::from_empty_iterator().to_string(),
+            cnt_sort_read(std::io::empty()).unwrap_err().to_string()
         );
-        assert_eq!(0, i32::try_into_index(&-2147483648, &-2147483648).unwrap());
-        assert_eq!(1, i32::try_into_index(&-2147483647, &-2147483648).unwrap());
-        assert_eq!(0, i32::try_into_index(&2147483647, &2147483647).unwrap());
     }
 
     #[test]
-    fn test_into_index_u8() {
-        assert_eq!(255, u8::try_into_index(&255, &0).unwrap());
-        assert_eq!(0, u8::try_into_index(&0, &0).unwrap());
-        assert_eq!(0, u8::try_into_index(&255, &255).unwrap());
-        assert_eq!(50, u8::try_into_index(&150, &100).unwrap());
-        assert_eq!(50, u8::try_into_index(&100, &50).unwrap());
-        assert_eq!(27, i8::try_into_index(&127, &100).unwrap());
-    }
+    fn test_cnt_sort_read_io_error() {
+        // fails after an interrupted read and a successful read
+        struct FailingReader {
+            calls: usize,
+        }
 
-    #[test]
-    fn test_into_index_u16() {
-        assert_eq!(0xFFFF, u16::try_into_index(&0xFFFF, &0).unwrap());
-        assert_eq!(0, u16::try_into_index(&0, &0).unwrap());
-        assert_eq!(0, u16::try_into_index(&0xFFFF, &0xFFFF).unwrap());
-        assert_eq!(1, u16::try_into_index(&0xFFFF, &0xFFFE).unwrap());
+        impl Read for FailingReader {
+            fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+                self.calls += 1;
+                match self.calls {
+                    1 => Err(std::io::Error::from(std::io::ErrorKind::Interrupted)),
+                    2 => {
+                        buffer[0] = 1;
+                        Ok(1)
+                    }
+                    _ => Err(std::io::Error::from(std::io::ErrorKind::Other)),
+                }
+            }
+        }
+
+        let result = cnt_sort_read(FailingReader { calls: 0 });
+        assert_eq!(
+            CountingSortError::from_io().to_string(),
+            result.unwrap_err().to_string()
+        );
     }
 
     #[test]
-    fn test_into_index_u32() {
-        assert_eq!(0xFFFFFFFF, u32::try_into_index(&0xFFFFFFFF, &0).unwrap());
-        assert_eq!(0, u32::try_into_index(&0, &0).unwrap());
-        assert_eq!(50, u32::try_into_index(&1000000, &999950).unwrap());
-        assert_eq!(50, u8::try_into_index(&100, &50).unwrap());
-        assert_eq!(27, i8::try_into_index(&127, &100).unwrap());
+    fn test_cnt_sort_check() {
+        assert!(TEST_ARRAY_UNSORTED.iter().cnt_sort_check().is_ok());
+        assert!([7_u8].iter().cnt_sort_check().is_ok());
+        assert!([i32::MIN, i32::MAX].iter().cnt_sort_check().is_ok());
     }
 
     #[test]
-    fn test_counting_sort() {
-        let test_vector: Vec<u8> = TEST_ARRAY_UNSORTED.to_vec();
-        let sorted_vector = counting_sort(test_vector.iter()).unwrap();
-        let expected_vector = TEST_ARRAY_SORTED.to_vec();
-        assert_eq!(expected_vector, sorted_vector);
+    fn test_cnt_sort_check_errors() {
+        let empty: Vec<u8> = vec![];
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            empty.iter().cnt_sort_check().unwrap_err().to_string()
+        );
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            [7_u8, 7].iter().cnt_sort_check().unwrap_err().to_string()
+        );
+        // the distance is usize::MAX, hence the length of the count values vector overflows
+        let test_vector = [
+            ValueWithMaxIndex { value: 0 },
+            ValueWithMaxIndex { value: 7 },
+        ];
+        assert_eq!(
+            CountingSortError::from_try_into_index_failed().to_string(),
+            test_vector.iter().cnt_sort_check().unwrap_err().to_string()
+        );
+        // both checks report the same error as sorting
+        for test_vector in &[[3_u8, 3], [3, 4]] {
+            assert_eq!(
+                test_vector
+                    .iter()
+                    .cnt_sort()
+                    .err()
+                    .map(|error| error.to_string()),
+                test_vector
+                    .iter()
+                    .cnt_sort_check()
+                    .err()
+                    .map(|error| error.to_string())
+            );
+        }
     }
 
     #[test]
-    fn test_counting_sort_min_max() {
-        let test_vector: Vec<u8> = TEST_ARRAY_UNSORTED.to_vec();
-        let sorted_vector = counting_sort_min_max(
-            test_vector.iter(),
-            &TEST_ARRAY_MIN_VALUE,
-            &TEST_ARRAY_MAX_VALUE,
-        )
-        .unwrap();
-        let expected_vector = TEST_ARRAY_SORTED.to_vec();
+    fn test_cnt_sort_dense() {
+        let mut rng = oorandom::Rand32::new(7);
+        let mut test_vector: Vec<u16> = (0..100).collect();
+        for index in (1..test_vector.len()).rev() {
+            let other_index = rng.rand_range(0..u32::try_from(index + 1).unwrap());
+            test_vector.swap(index, usize::try_from(other_index).unwrap());
+        }
+        let expected_vector: Vec<u16> = (0..100).collect();
+        assert_ne!(expected_vector, test_vector);
+        let sorted_vector = test_vector.iter().cnt_sort_dense().unwrap();
         assert_eq!(expected_vector, sorted_vector);
+        assert_eq!(sorted_vector.len(), sorted_vector.capacity());
+
+        assert_eq!(
+            vec![-1, 0, 1],
+            [0_i8, 1, -1].iter().cnt_sort_dense().unwrap()
+        );
+        assert_eq!(vec![5], [5_u8].iter().cnt_sort_dense().unwrap());
     }
 
     #[test]
-    fn test_count_values() {
-        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
-        let result_count_value_vector = count_values(
-            &mut test_vector.iter(),
-            &TEST_ARRAY_MIN_VALUE,
-            &TEST_ARRAY_MAX_VALUE,
+    fn test_cnt_sort_dense_errors() {
+        for test_vector in &[&[1_u8, 3][..], &[1, 2, 2, 3], &[2, 2], &[1, 1, 3]] {
+            assert_eq!(
+                CountingSortError::from_not_a_permutation().to_string(),
+                test_vector.iter().cnt_sort_dense().unwrap_err().to_string()
+            );
+        }
+        let empty: Vec<u8> = vec![];
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            empty.iter().cnt_sort_dense().unwrap_err().to_string()
         );
-        assert!(result_count_value_vector.is_ok());
-        let count_values_vector = result_count_value_vector.unwrap();
-        let expected_vector = TEST_COUNT_VALUES_ARRAY.to_vec();
-        assert_eq!(expected_vector, count_values_vector);
     }
 
     #[test]
-    fn test_get_min_max_unsigned() {
-        let test_vector: Vec<u8> = vec![1, 2, 3, 4];
-        let tuple = get_min_max(&mut test_vector.iter());
-        assert!(tuple.is_some());
-        let (min_value, max_value) = tuple.unwrap();
-        assert_eq!(1, *min_value);
-        assert_eq!(4, *max_value);
+    fn test_cnt_sort_group_payloads() {
+        let pairs: Vec<(u8, usize)> = TEST_ARRAY_UNSORTED
+            .iter()
+            .enumerate()
+            .map(|(position, key)| (*key, position))
+            .collect();
+        let (keys, payloads, offsets) = cnt_sort_group_payloads(&pairs).unwrap();
+        assert_eq!(keys.len() + 1, offsets.len());
+        assert_eq!(Some(&0), offsets.first());
+        assert_eq!(Some(&pairs.len()), offsets.last());
+        assert_eq!(pairs.len(), payloads.len());
+        for (index, key) in keys.iter().enumerate() {
+            let group = &payloads[offsets[index]..offsets[index + 1]];
+            let expected_group: Vec<usize> = pairs
+                .iter()
+                .filter(|(pair_key, _)| pair_key == key)
+                .map(|(_, position)| *position)
+                .collect();
+            assert_eq!(expected_group, group);
+        }
+
+        let (keys, payloads, offsets) =
+            cnt_sort_group_payloads(&[(-1_i16, 'x'), (-1, 'y')]).unwrap();
+        assert_eq!(vec![-1], keys);
+        assert_eq!(vec!['x', 'y'], payloads);
+        assert_eq!(vec![0, 2], offsets);
+
+        let empty: [(u8, u8); 0] = [];
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            cnt_sort_group_payloads(&empty).unwrap_err().to_string()
+        );
     }
 
     #[test]
-    fn test_get_min_max_without_min() {
-        let test_vector: Vec<u8> = Vec::new();
-        let tuple = get_min_max(&mut test_vector.iter());
-        assert!(tuple.is_none());
+    fn test_cnt_sort_with_ctx() {
+        // scales the distance of the keys by a runtime factor
+        struct Scale(usize);
+
+        impl TryIntoIndexWith<Scale> for u8 {
+            type Error = CountingSortError;
+
+            fn try_into_index_with(
+                value: &Self,
+                min_value: &Self,
+                ctx: &Scale,
+            ) -> Result<usize, Self::Error> {
+                u8::try_into_index(value, min_value)?
+                    .checked_mul(ctx.0)
+                    .ok_or_else(CountingSortError::from_try_into_index_failed)
+            }
+        }
+
+        for factor in 1..4 {
+            let sorted_vector = cnt_sort_with_ctx(TEST_ARRAY_UNSORTED.iter(), &Scale(factor));
+            assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector.unwrap());
+        }
+        assert_eq!(
+            vec![3],
+            cnt_sort_with_ctx([3_u8].iter(), &Scale(2)).unwrap()
+        );
+        // the context is used for the conversion
+        assert_eq!(
+            CountingSortError::from_try_into_index_failed().to_string(),
+            cnt_sort_with_ctx(TEST_ARRAY_UNSORTED.iter(), &Scale(usize::MAX))
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            cnt_sort_with_ctx([3_u8, 3].iter(), &Scale(2))
+                .unwrap_err()
+                .to_string()
+        );
+        let empty: Vec<u8> = vec![];
+        assert!(cnt_sort_with_ctx(empty.iter(), &Scale(1)).is_err());
     }
 
     #[test]
-    fn test_get_min_max_signed() {
-        let test_vector: Vec<i8> = vec![-128, 2, 3, 127];
-        let tuple = get_min_max(&mut test_vector.iter());
-        assert!(tuple.is_some());
-        let (min_value, max_value) = tuple.unwrap();
-        assert_eq!(-128, *min_value);
-        assert_eq!(127, *max_value);
+    #[allow(clippy::float_cmp)]
+    fn test_sortedness() {
+        assert_eq!(1.0, TEST_ARRAY_SORTED.iter().sortedness());
+        let mut test_vector = TEST_ARRAY_SORTED.to_vec();
+        test_vector.reverse();
+        // only the pairs of equal values are in non-decreasing order
+        let number_of_equal_pairs = test_vector
+            .windows(2)
+            .filter(|window| window[0] == window[1])
+            .count();
+        assert_eq!(
+            f64::from(u32::try_from(number_of_equal_pairs).unwrap()) / 29.0,
+            test_vector.iter().sortedness()
+        );
+        assert_eq!(0.0, [5, 4, 3, 2, 1].iter().sortedness());
+        // 4 out of 6 pairs are sorted
+        assert_eq!(4.0 / 6.0, [1, 2, 5, 3, 4, 0, 6].iter().sortedness());
+        assert_eq!(1.0, [7].iter().sortedness());
+        let empty: Vec<u8> = vec![];
+        assert_eq!(1.0, empty.iter().sortedness());
     }
 
+    #[cfg(feature = "rayon")]
     #[test]
-    fn test_calculate_prefix_sum_1() {
-        let mut test_vector: Vec<usize> = vec![1; 4];
-        calculate_prefix_sum(&mut test_vector);
-        assert_eq!(vec![1, 2, 3, 4], test_vector);
+    fn test_cnt_sort_parallel_merge() {
+        let mut rng = oorandom::Rand32::new(4711);
+        let test_vector: Vec<u16> = (0..1_000_000)
+            .map(|_| u16::try_from(rng.rand_range(0..65536)).unwrap())
+            .collect();
+        assert_eq!(
+            test_vector.iter().cnt_sort().unwrap(),
+            test_vector.iter().cnt_sort_parallel_merge().unwrap()
+        );
+        assert_eq!(
+            TEST_ARRAY_SORTED.to_vec(),
+            TEST_ARRAY_UNSORTED
+                .iter()
+                .cnt_sort_parallel_merge()
+                .unwrap()
+        );
+        assert_eq!(vec![7], [7].iter().cnt_sort_parallel_merge().unwrap());
+        assert!([7, 7].iter().cnt_sort_parallel_merge().is_err());
+        let empty_vector: Vec<u8> = vec![];
+        assert!(empty_vector.iter().cnt_sort_parallel_merge().is_err());
     }
 
     #[test]
-    fn test_calculate_prefix_sum_2() {
-        let mut test_vector: Vec<usize> = vec![1, 2, 3, 4, 5];
-        calculate_prefix_sum(&mut test_vector);
-        assert_eq!(vec![1, 3, 6, 10, 15], test_vector);
+    fn test_cnt_sort_hashed_with_capacity() {
+        for expected_distinct in &[0, 5, 28, 1000] {
+            let sorted_vector = TEST_ARRAY_UNSORTED
+                .iter()
+                .cnt_sort_hashed_with_capacity(*expected_distinct)
+                .unwrap();
+            assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector);
+        }
+        let test_vector = [i32::MAX, i32::MIN, 0, i32::MIN];
+        assert_eq!(
+            vec![i32::MIN, i32::MIN, 0, i32::MAX],
+            test_vector.iter().cnt_sort_hashed_with_capacity(3).unwrap()
+        );
+        assert_eq!(
+            vec![7],
+            [7].iter().cnt_sort_hashed_with_capacity(1).unwrap()
+        );
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            [7, 7]
+                .iter()
+                .cnt_sort_hashed_with_capacity(1)
+                .unwrap_err()
+                .to_string()
+        );
+        let empty: Vec<u8> = vec![];
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            empty
+                .iter()
+                .cnt_sort_hashed_with_capacity(0)
+                .unwrap_err()
+                .to_string()
+        );
     }
 
     #[test]
-    fn test_calculate_prefix_sum_3() {
-        let mut test_vector = TEST_COUNT_VALUES_ARRAY.to_vec();
-        calculate_prefix_sum(&mut test_vector);
-        assert_eq!(TEST_PREFIX_SUM_ARRAY.to_vec(), test_vector);
+    fn test_count_hashed_does_not_rehash_with_accurate_hint() {
+        let test_vector: Vec<u32> = (0..10_000).map(|value| value * 1000).collect();
+        let capacity = HashMap::<u32, usize>::with_capacity(test_vector.len()).capacity();
+        let count_map = count_hashed(test_vector.iter(), test_vector.len());
+        assert_eq!(test_vector.len(), count_map.len());
+        assert_eq!(capacity, count_map.capacity());
     }
 
     #[test]
-    fn test_re_order() {
-        let test_vector = TEST_ARRAY_UNSORTED.to_vec();
-        let mut test_count_vector = TEST_PREFIX_SUM_ARRAY.to_vec();
-        let result_sorted_vector = re_order(
-            test_vector.iter(),
-            &mut test_count_vector,
-            test_vector.len(),
-            &TEST_ARRAY_MIN_VALUE,
-        );
-        assert!(result_sorted_vector.is_ok());
-        let sorted_vector = result_sorted_vector.unwrap();
+    fn test_cnt_sort_and_ranks() {
+        let (sorted_vector, ranks) = TEST_ARRAY_UNSORTED.iter().cnt_sort_and_ranks().unwrap();
         assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector);
+        assert_eq!(TEST_ARRAY_UNSORTED.len(), ranks.len());
+        // applying the ranks to the original elements reproduces the sorted vector
+        let mut ranked_vector = vec![0; TEST_ARRAY_UNSORTED.len()];
+        for (value, rank) in TEST_ARRAY_UNSORTED.iter().zip(&ranks) {
+            ranked_vector[*rank] = *value;
+        }
+        assert_eq!(sorted_vector, ranked_vector);
+        let (_, inverse_permutation) = TEST_ARRAY_UNSORTED.iter().cnt_sort_permutations().unwrap();
+        assert_eq!(inverse_permutation, ranks);
+
+        let (sorted_vector, ranks) = [4_u8, 4].iter().cnt_sort_and_ranks().unwrap();
+        assert_eq!(vec![4, 4], sorted_vector);
+        assert_eq!(vec![0, 1], ranks);
+
+        let empty: Vec<u8> = vec![];
+        assert!(empty.iter().cnt_sort_and_ranks().is_err());
     }
 
     #[test]
-    fn test_min_value_larger_max_value_error() {
-        let test_vector = vec![1];
-        let result = counting_sort_min_max(test_vector.iter(), &1, &0);
-        assert!(result.is_err());
+    fn test_cnt_sort_chars_block() {
+        // the Greek lowercase letters are a small contiguous block
+        let vector: Vec<char> = "ωβαγεδ".chars().collect();
         assert_eq!(
-            "Minimum value is larger than maximum value",
-            format!("{}", result.unwrap_err())
+            vec!['α', 'β', 'γ', 'δ', 'ε', 'ω'],
+            vector.iter().cnt_sort_chars_block('α', 'ω').unwrap()
         );
-    }
 
-    #[test]
-    fn test_sorting_unnecessary_error() {
-        let test_vector = vec![1];
-        let result = counting_sort_min_max(test_vector.iter(), &1, &1);
-        assert!(result.is_err());
+        let vector = ['b', 'a', 'c'];
         assert_eq!(
-            "Minimum value is identical to maximum value, therefore no sorting is necessary",
-            format!("{}", result.unwrap_err())
+            vec!['a', 'b', 'c'],
+            vector.iter().cnt_sort_chars_block('a', 'z').unwrap()
         );
     }
 
     #[test]
-    fn test_empty_iterator_error() {
-        let test_vector: Vec<u8> = vec![];
-        let result = counting_sort(test_vector.iter());
-        assert!(result.is_err());
+    fn test_cnt_sort_chars_block_errors() {
+        let vector = ['β', 'a', 'α'];
         assert_eq!(
-            "There are no element available in the iterator",
-            format!("{}", result.unwrap_err())
+            CountingSortError::from_index_out_of_bounds().to_string(),
+            vector
+                .iter()
+                .cnt_sort_chars_block('α', 'ω')
+                .unwrap_err()
+                .to_string()
         );
-        let test_vector: Vec<u8> = vec![];
-        let result = counting_sort_min_max(test_vector.iter(), &0, &1);
-        assert!(result.is_ok());
-        assert_eq!(test_vector, result.unwrap());
-    }
-
-    #[test]
-    fn test_incorrect_given_min_max_values() {
-        let vec = vec![4, 3, 2, 1];
-
-        let error = vec.iter().cnt_sort_min_max(&2, &4);
-        assert!(error.is_err());
+        let vector = ['β', 'α'];
         assert_eq!(
-            "Conversion into index failed",
-            format!("{}", error.unwrap_err())
+            CountingSortError::from_min_value_larger_max_value().to_string(),
+            vector
+                .iter()
+                .cnt_sort_chars_block('ω', 'α')
+                .unwrap_err()
+                .to_string()
         );
-
-        let error = vec.iter().cnt_sort_min_max(&1, &3);
-        assert!(error.is_err());
+        let vector = ['α', 'α'];
         assert_eq!(
-            "Index is out of bounds, most likely the given maximum value is too small",
-            format!("{}", error.unwrap_err())
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            vector
+                .iter()
+                .cnt_sort_chars_block('α', 'α')
+                .unwrap_err()
+                .to_string()
         );
     }
 
     #[test]
-    fn test_try_into_error() {
-        #[derive(Ord, PartialOrd, PartialEq, Eq, Copy, Clone, Debug)]
-        struct ValueWithTryIntoError {
-            value: u8,
-        };
-
-        let min_value = ValueWithTryIntoError { value: 0 };
-        let max_value = ValueWithTryIntoError {
-            value: u8::max_value(),
-        };
+    fn test_cnt_sort_peekable() {
+        let mut first_stream = TEST_ARRAY_UNSORTED.iter().cnt_sort_peekable().unwrap();
+        let second_vector = vec![31, 0, 15, 7];
+        let mut second_stream = second_vector.iter().cnt_sort_peekable().unwrap();
+        assert_eq!(Some(&1), first_stream.peek());
+        assert_eq!(Some(&0), second_stream.peek());
 
-        impl TryIntoIndex for ValueWithTryIntoError {
-            type Error = String;
-            fn try_into_index(_value: &Self, _min_value: &Self) -> Result<usize, Self::Error> {
-                Err(String::from("TryInto always fails"))
+        let mut merged_vector = Vec::new();
+        while let (Some(first_head), Some(second_head)) =
+            (first_stream.peek(), second_stream.peek())
+        {
+            if first_head <= second_head {
+                merged_vector.extend(first_stream.next());
+            } else {
+                merged_vector.extend(second_stream.next());
             }
         }
+        merged_vector.extend(first_stream);
+        merged_vector.extend(second_stream);
 
-        let test_vector: Vec<ValueWithTryIntoError> = Vec::new();
-        let result = counting_sort_min_max(test_vector.iter(), &min_value, &max_value);
-        assert!(result.is_err());
+        let mut expected_vector: Vec<u8> = TEST_ARRAY_UNSORTED.to_vec();
+        expected_vector.extend_from_slice(&second_vector);
+        expected_vector.sort_unstable();
+        assert_eq!(expected_vector, merged_vector);
+
+        let stream = TEST_ARRAY_UNSORTED.iter().cnt_sort_peekable().unwrap();
+        assert_eq!(TEST_ARRAY_UNSORTED.len(), stream.len());
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), stream.collect::<Vec<u8>>());
         assert_eq!(
-            CountingSortError::from_try_into_index_failed().to_string(),
-            result.unwrap_err().to_string()
+            vec![42],
+            [42].iter()
+                .cnt_sort_peekable()
+                .unwrap()
+                .collect::<Vec<i32>>()
         );
-
-        let mut count_vector = vec![0, 0];
-        let test_vector = vec![max_value, min_value];
-        let result = re_order(test_vector.iter(), &mut count_vector, 2, &min_value);
-        assert!(result.is_err());
         assert_eq!(
-            CountingSortError::from_try_into_index_failed().to_string(),
-            result.unwrap_err().to_string()
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            [42, 42].iter().cnt_sort_peekable().unwrap_err().to_string()
         );
+        let empty_vector: Vec<u8> = vec![];
+        assert!(empty_vector.iter().cnt_sort_peekable().is_err());
     }
 
     #[test]
-    fn test_empty_count_values_vector_is_impossible() {
-        #[derive(Ord, PartialOrd, PartialEq, Eq, Copy, Clone, Debug)]
-        struct ValueWithWrongSubstraction {
-            value: usize,
-        };
-
-        let min_value = ValueWithWrongSubstraction { value: 0 };
-        let max_value = ValueWithWrongSubstraction {
-            value: usize::max_value(),
-        };
+    fn test_sort_strategy_default() {
+        let strategy = SortStrategy::new();
+        assert_eq!(SortStrategy::default(), strategy);
+        assert_eq!(
+            TEST_ARRAY_SORTED.to_vec(),
+            strategy.sort(TEST_ARRAY_UNSORTED.iter()).unwrap()
+        );
+        let test_vector: Vec<u8> = Vec::new();
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            strategy.sort(test_vector.iter()).unwrap_err().to_string()
+        );
+        let test_vector = [7_u8];
+        assert_eq!(vec![7], strategy.sort(test_vector.iter()).unwrap());
+        let test_vector = [7_u8, 7];
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            strategy.sort(test_vector.iter()).unwrap_err().to_string()
+        );
+        // the count values vector can not be sized for a distance of usize::MAX
+        let test_vector = [
+            ValueWithMaxIndex { value: 7 },
+            ValueWithMaxIndex { value: 0 },
+        ];
+        assert_eq!(
+            test_vector.iter().cnt_sort().unwrap_err().to_string(),
+            strategy.sort(test_vector.iter()).unwrap_err().to_string()
+        );
+    }
 
-        impl TryIntoIndex for ValueWithWrongSubstraction {
-            type Error = String;
-            fn try_into_index(_value: &Self, _min_value: &Self) -> Result<usize, Self::Error> {
-                Ok(0)
-            }
+    #[test]
+    fn test_sort_strategy_within_max_distance() {
+        // the distance of TEST_ARRAY_UNSORTED is exactly 29
+        let max_distance = usize::from(TEST_ARRAY_MAX_VALUE - TEST_ARRAY_MIN_VALUE);
+        for fallback in &[Fallback::Std, Fallback::Error] {
+            let strategy = SortStrategy::new()
+                .max_distance(max_distance)
+                .fallback(*fallback);
+            assert_eq!(
+                TEST_ARRAY_SORTED.to_vec(),
+                strategy.sort(TEST_ARRAY_UNSORTED.iter()).unwrap()
+            );
         }
+    }
 
-        let test_vector: Vec<ValueWithWrongSubstraction> = Vec::new();
-        let result = counting_sort_min_max(test_vector.iter(), &min_value, &max_value);
-        assert!(result.is_ok());
-        assert_eq!(test_vector, result.unwrap());
+    #[test]
+    fn test_sort_strategy_fallback_std() {
+        let max_distance = usize::from(TEST_ARRAY_MAX_VALUE - TEST_ARRAY_MIN_VALUE) - 1;
+        let strategy = SortStrategy::new()
+            .max_distance(max_distance)
+            .fallback(Fallback::Std);
+        assert_eq!(
+            TEST_ARRAY_SORTED.to_vec(),
+            strategy.sort(TEST_ARRAY_UNSORTED.iter()).unwrap()
+        );
+        // the count values vector can not be sized for a distance of usize::MAX
+        let test_vector = [
+            ValueWithMaxIndex { value: 7 },
+            ValueWithMaxIndex { value: 0 },
+        ];
+        assert_eq!(
+            vec![
+                ValueWithMaxIndex { value: 0 },
+                ValueWithMaxIndex { value: 7 }
+            ],
+            SortStrategy::new()
+                .fallback(Fallback::Std)
+                .sort(test_vector.iter())
+                .unwrap()
+        );
     }
 
     #[test]
-    fn test_re_order_index_out_of_bounds_error() {
-        let vec = vec![1, 2];
-        let mut count_vector = vec![1];
-        let result = re_order(vec.iter(), &mut count_vector, 2, &1);
-        assert!(result.is_err());
+    fn test_sort_strategy_fallback_error() {
+        let max_distance = usize::from(TEST_ARRAY_MAX_VALUE - TEST_ARRAY_MIN_VALUE) - 1;
+        let strategy = SortStrategy::new()
+            .max_distance(max_distance)
+            .fallback(Fallback::Error);
         assert_eq!(
-            CountingSortError::from_index_out_of_bounds().to_string(),
-            result.unwrap_err().to_string()
+            CountingSortError::from_distance_too_large().to_string(),
+            strategy
+                .sort(TEST_ARRAY_UNSORTED.iter())
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(Fallback::Error, Fallback::default());
+    }
+
+    #[test]
+    fn test_cnt_sort_with_working_set() {
+        let (sorted_vector, working_set) = TEST_ARRAY_UNSORTED
+            .iter()
+            .cnt_sort_with_working_set()
+            .unwrap();
+        assert_eq!(TEST_ARRAY_SORTED.to_vec(), sorted_vector);
+        let mut distinct_values = TEST_ARRAY_SORTED.to_vec();
+        distinct_values.dedup();
+        assert_eq!(distinct_values.len(), working_set);
+
+        let test_vector = [42_u8];
+        assert_eq!(
+            (vec![42], 1),
+            test_vector.iter().cnt_sort_with_working_set().unwrap()
+        );
+        let test_vector = [42_u8, 42];
+        assert_eq!(
+            CountingSortError::from_sorting_unnecessary().to_string(),
+            test_vector
+                .iter()
+                .cnt_sort_with_working_set()
+                .unwrap_err()
+                .to_string()
+        );
+        let test_vector: [u8; 0] = [];
+        assert_eq!(
+            CountingSortError::from_empty_iterator().to_string(),
+            test_vector
+                .iter()
+                .cnt_sort_with_working_set()
+                .unwrap_err()
+                .to_string()
         );
     }
 }
@@ -967,7 +11181,7 @@ mod unit_tests {
 macro_rules! doc_check {
     ($x:expr) => {
         #[doc = $x]
-        extern {}
+        extern "C" {}
     };
 }
 
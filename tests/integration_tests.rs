@@ -1,8 +1,20 @@
+// These lints were introduced after the tests were written, the tests are left unchanged.
+#![allow(
+    clippy::legacy_numeric_constants,
+    clippy::useless_vec,
+    clippy::vec_init_then_push
+)]
+
 #[cfg(test)]
 #[cfg(not(tarpaulin_include))]
 mod integration_tests {
 
-    use counting_sort::CountingSort;
+    use counting_sort::{
+        cnt_sort_in_place, cnt_sort_in_place_stable, impl_try_into_index_newtype, CountingSort,
+        CountingSortedExt, Stability,
+    };
+
+    use std::borrow::Cow;
 
     use std::collections::LinkedList;
 
@@ -14,6 +26,10 @@ mod integration_tests {
 
     use std::collections::HashSet;
 
+    use core::cmp::{Ord, Ordering};
+
+    use counting_sort::TryIntoIndex;
+
     #[test]
     fn test_with_list() {
         let mut list = LinkedList::new();
@@ -205,9 +221,6 @@ mod integration_tests {
 
     #[test]
     fn test_stable_sort() {
-        use core::cmp::{Ord, Ordering};
-        use counting_sort::TryIntoIndex;
-
         #[derive(Copy, Clone, Debug)]
         struct Person {
             name: &'static str,
@@ -289,4 +302,514 @@ mod integration_tests {
 
         assert_eq!(vec![2, 3, 4], result.unwrap());
     }
+
+    #[derive(Copy, Clone, Debug)]
+    struct Tagged {
+        tag: &'static str,
+        key: usize,
+    }
+
+    impl Ord for Tagged {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    impl PartialOrd for Tagged {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl PartialEq for Tagged {
+        fn eq(&self, other: &Self) -> bool {
+            self.key == other.key
+        }
+    }
+
+    impl Eq for Tagged {}
+
+    impl TryIntoIndex for Tagged {
+        type Error = &'static str;
+
+        fn try_into_index(value: &Self, min_value: &Self) -> Result<usize, Self::Error> {
+            Ok(value.key - min_value.key)
+        }
+    }
+
+    #[test]
+    fn test_cnt_sort_merge_into_is_stable() {
+        let mut sorted = vec![
+            Tagged {
+                tag: "old 1",
+                key: 1,
+            },
+            Tagged {
+                tag: "old 2",
+                key: 2,
+            },
+        ];
+        let batch = [
+            Tagged {
+                tag: "new 2",
+                key: 2,
+            },
+            Tagged {
+                tag: "new 1",
+                key: 1,
+            },
+            Tagged {
+                tag: "new 3",
+                key: 3,
+            },
+        ];
+
+        batch.iter().cnt_sort_merge_into(&mut sorted).unwrap();
+
+        assert_eq!(
+            vec!["old 1", "new 1", "old 2", "new 2", "new 3"],
+            sorted.iter().map(|x| x.tag).collect::<Vec<&'static str>>()
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_shuffle_ties() {
+        let tags = ["a", "b", "c", "d", "e", "f", "g", "h"];
+        let mut vec = Vec::new();
+        for (index, tag) in tags.iter().enumerate() {
+            vec.push(Tagged {
+                tag,
+                key: index % 2,
+            });
+            vec.push(Tagged { tag, key: 5 });
+        }
+        let tags_of = |sorted: &[Tagged]| sorted.iter().map(|t| t.tag).collect::<Vec<_>>();
+
+        let first = vec.iter().cnt_sort_shuffle_ties(7).unwrap();
+        let second = vec.iter().cnt_sort_shuffle_ties(7).unwrap();
+        let other_seed = vec.iter().cnt_sort_shuffle_ties(8).unwrap();
+
+        assert!(first.windows(2).all(|pair| pair[0].key <= pair[1].key));
+        assert_eq!(tags_of(&first), tags_of(&second));
+        assert_ne!(tags_of(&first), tags_of(&other_seed));
+        assert_eq!(vec.len(), first.len());
+    }
+
+    #[test]
+    fn test_cnt_sort_with_payload_keeps_keys_and_payload_in_sync() {
+        let mut keys: Vec<u16> = vec![500, 20, 500, 7, 20, 1000];
+        let mut payload: Vec<String> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| format!("{}-{}", key, i))
+            .collect();
+
+        counting_sort::cnt_sort_with_payload(&mut keys, &mut payload).unwrap();
+
+        assert_eq!(vec![7, 20, 20, 500, 500, 1000], keys);
+        assert_eq!(
+            vec!["7-3", "20-1", "20-4", "500-0", "500-2", "1000-5"],
+            payload
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_with_payload_length_mismatch() {
+        let mut keys: Vec<u16> = vec![2, 1];
+        let mut payload = vec!["two"];
+
+        assert!(counting_sort::cnt_sort_with_payload(&mut keys, &mut payload).is_err());
+        assert_eq!(vec![2, 1], keys);
+    }
+
+    #[test]
+    fn test_cnt_sort_tiebreak_by() {
+        let vector = [
+            Tagged { tag: "d", key: 3 },
+            Tagged { tag: "b", key: 1 },
+            Tagged { tag: "c", key: 3 },
+            Tagged { tag: "a", key: 1 },
+            Tagged { tag: "e", key: 2 },
+            Tagged { tag: "c", key: 1 },
+        ];
+        let sorted_vector = vector
+            .iter()
+            .cnt_sort_tiebreak_by(|tagged| tagged.tag)
+            .unwrap();
+        let keys: Vec<usize> = sorted_vector.iter().map(|tagged| tagged.key).collect();
+        let tags: Vec<&str> = sorted_vector.iter().map(|tagged| tagged.tag).collect();
+        assert_eq!(vec![1, 1, 1, 2, 3, 3], keys);
+        assert_eq!(vec!["a", "b", "c", "e", "c", "d"], tags);
+
+        // descending secondary key via Reverse
+        let sorted_vector = vector
+            .iter()
+            .cnt_sort_tiebreak_by(|tagged| core::cmp::Reverse(tagged.tag))
+            .unwrap();
+        let tags: Vec<&str> = sorted_vector.iter().map(|tagged| tagged.tag).collect();
+        assert_eq!(vec!["c", "b", "a", "e", "d", "c"], tags);
+    }
+
+    #[test]
+    fn test_cnt_sort_grouped_keeps_payload() {
+        let vector = [
+            Tagged { tag: "x", key: 20 },
+            Tagged { tag: "y", key: 10 },
+            Tagged { tag: "z", key: 20 },
+        ];
+        let groups = vector.iter().cnt_sort_grouped().unwrap();
+        let tags: Vec<(usize, Vec<&str>)> = groups
+            .iter()
+            .map(|(value, group)| (value.key, group.iter().map(|tagged| tagged.tag).collect()))
+            .collect();
+        assert_eq!(vec![(10, vec!["y"]), (20, vec!["x", "z"])], tags);
+    }
+
+    #[test]
+    fn test_cnt_sort_with_stability() {
+        let vector = [
+            Tagged { tag: "a", key: 2 },
+            Tagged { tag: "b", key: 1 },
+            Tagged { tag: "c", key: 2 },
+            Tagged { tag: "d", key: 3 },
+            Tagged { tag: "e", key: 1 },
+            Tagged { tag: "f", key: 2 },
+        ];
+        let tags = |stability| -> Vec<&str> {
+            vector
+                .iter()
+                .cnt_sort_with_stability(stability)
+                .unwrap()
+                .iter()
+                .map(|tagged| tagged.tag)
+                .collect()
+        };
+        assert_eq!(vec!["b", "e", "a", "c", "f", "d"], tags(Stability::Stable));
+        assert_eq!(
+            vec!["e", "b", "f", "c", "a", "d"],
+            tags(Stability::ReverseStable)
+        );
+        assert_eq!(Stability::Stable, Stability::default());
+    }
+
+    #[test]
+    fn test_cnt_sort_cow() {
+        let borrowed: Vec<u32> = vec![70_000, 3, 500];
+        let vector: Vec<Cow<u32>> = vec![
+            Cow::Borrowed(&borrowed[0]),
+            Cow::Owned(12),
+            Cow::Borrowed(&borrowed[1]),
+            Cow::Owned(70_000),
+            Cow::Borrowed(&borrowed[2]),
+            Cow::Owned(3),
+        ];
+        let sorted_vector = counting_sort::cnt_sort_cow(vector.into_iter()).unwrap();
+        assert_eq!(vec![3, 3, 12, 500, 70_000, 70_000], sorted_vector);
+
+        let empty: Vec<Cow<u32>> = vec![];
+        assert!(counting_sort::cnt_sort_cow(empty.into_iter()).is_err());
+    }
+
+    #[test]
+    fn test_cnt_sort_reduce_by_key_sums_weights() {
+        let weighted = [
+            (-3_i16, 1.5_f64),
+            (7, 2.0),
+            (-3, 0.25),
+            (1000, 4.0),
+            (7, 1.0),
+            (-3, 0.25),
+        ];
+        let sums = counting_sort::cnt_sort_reduce_by_key(
+            weighted.iter(),
+            |entry| entry.0,
+            |entry| entry.1,
+            |sum, weight| sum + weight,
+        )
+        .unwrap();
+        assert_eq!(vec![(-3, 2.0), (7, 3.0), (1000, 4.0)], sums);
+
+        // the values are reduced in input order
+        let tags = [
+            Tagged { tag: "b", key: 2 },
+            Tagged { tag: "a", key: 1 },
+            Tagged { tag: "c", key: 2 },
+        ];
+        let joined = counting_sort::cnt_sort_reduce_by_key(
+            tags.iter(),
+            |tagged| tagged.key,
+            |tagged| String::from(tagged.tag),
+            |joined, tag| joined + &tag,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![(1, String::from("a")), (2, String::from("bc"))],
+            joined
+        );
+
+        let empty: Vec<(u8, u8)> = vec![];
+        assert!(counting_sort::cnt_sort_reduce_by_key(
+            empty.iter(),
+            |entry| entry.0,
+            |entry| entry.1,
+            |a, b| a + b
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_counting_sorted_with_take() {
+        let vector: Vec<i16> = vec![300, -7, 42, 300, -1000, 5, 42];
+        let three_smallest: Vec<i16> = vector
+            .iter()
+            .filter(|value| **value != 5)
+            .map(|value| value * 2)
+            .counting_sorted()
+            .take(3)
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(vec![-2000, -14, 84], three_smallest);
+
+        // nothing is sorted until the adaptor is polled
+        let polled = std::cell::Cell::new(false);
+        let mut sorted = [3_u8, 1, 2]
+            .iter()
+            .map(|value| {
+                polled.set(true);
+                *value
+            })
+            .counting_sorted();
+        assert!(!polled.get());
+        assert_eq!(Some(1), sorted.next().map(Result::unwrap));
+        assert!(polled.get());
+
+        let equal: Vec<Result<u8, _>> = vec![7_u8, 7].into_iter().counting_sorted().collect();
+        assert_eq!(
+            vec![7, 7],
+            equal.into_iter().map(Result::unwrap).collect::<Vec<u8>>()
+        );
+        assert_eq!(0, Vec::<u8>::new().into_iter().counting_sorted().count());
+    }
+
+    // Sorts random inputs near the given anchor with correct and slightly wrong bounds and checks
+    // that the sort never panics but returns either the correctly sorted elements or an error.
+    fn fuzz_without_panic<INT>(rng: &mut Rand32, anchor: i128, type_min: i128, type_max: i128)
+    where
+        INT: Ord + Copy + TryIntoIndex + TryFrom<i128> + core::fmt::Debug,
+    {
+        let clamp = |value: i128| INT::try_from(value.max(type_min).min(type_max)).ok();
+        for _ in 0..200 {
+            let number_of_elements = rng.rand_range(0..40);
+            let spread = i128::from(rng.rand_range(0..300));
+            let vector: Vec<INT> = (0..number_of_elements)
+                .filter_map(|_| clamp(anchor + i128::from(rng.rand_range(0..300)) % (spread + 1)))
+                .collect();
+            let mut expected = vector.clone();
+            expected.sort();
+
+            for sorted_vector in [
+                vector.iter().cnt_sort(),
+                vector.iter().cnt_sort_adaptive(),
+                vector
+                    .iter()
+                    .cnt_sort_with_stability(Stability::ReverseStable),
+            ]
+            .iter()
+            .flatten()
+            {
+                assert_eq!(&expected, sorted_vector);
+            }
+
+            // bounds which may be too small, too large or swapped
+            let lower_offset = i128::from(rng.rand_range(0..7)) - 3;
+            let upper_offset = i128::from(rng.rand_range(0..7)) - 3;
+            if let (Some(min_value), Some(max_value)) = (
+                clamp(anchor + lower_offset),
+                clamp(anchor + spread + upper_offset),
+            ) {
+                let result = vector.iter().cnt_sort_min_max(&min_value, &max_value);
+                if let Ok(sorted_vector) = result {
+                    assert_eq!(expected, sorted_vector);
+                }
+                let result = vector.iter().cnt_sort_min_max(&max_value, &min_value);
+                if let Ok(sorted_vector) = result {
+                    assert_eq!(expected, sorted_vector);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fuzz_all_integer_types_without_panic() {
+        let mut rng = Rand32::new(0x5EED);
+        macro_rules! fuzz {
+            ($int:ty) => {
+                let type_min = i128::try_from(<$int>::MIN).unwrap();
+                let type_max = i128::try_from(<$int>::MAX).unwrap();
+                for anchor in [type_min, type_max - 300, type_max, -150, 0].iter() {
+                    fuzz_without_panic::<$int>(&mut rng, *anchor, type_min, type_max);
+                }
+            };
+        }
+        fuzz!(u8);
+        fuzz!(u16);
+        fuzz!(u32);
+        fuzz!(usize);
+        fuzz!(i8);
+        fuzz!(i16);
+        fuzz!(i32);
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    #[repr(u8)]
+    enum Suit {
+        Clubs = 1,
+        Diamonds = 2,
+        Hearts = 4,
+        Spades = 8,
+    }
+
+    impl From<Suit> for u8 {
+        fn from(suit: Suit) -> u8 {
+            suit as u8
+        }
+    }
+
+    #[test]
+    fn test_cnt_sort_via_u8_with_repr_u8_enum() {
+        let suits = [
+            Suit::Spades,
+            Suit::Clubs,
+            Suit::Hearts,
+            Suit::Clubs,
+            Suit::Diamonds,
+            Suit::Spades,
+        ];
+        let sorted_suits = counting_sort::cnt_sort_via_u8(suits.iter()).unwrap();
+        let mut expected = suits.to_vec();
+        expected.sort();
+        assert_eq!(expected, sorted_suits);
+
+        assert_eq!(
+            vec![Suit::Hearts],
+            counting_sort::cnt_sort_via_u8([Suit::Hearts].iter()).unwrap()
+        );
+        assert!(counting_sort::cnt_sort_via_u8([Suit::Hearts, Suit::Hearts].iter()).is_err());
+        assert!(counting_sort::cnt_sort_via_u8(Vec::<Suit>::new().iter()).is_err());
+    }
+
+    #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+    struct UserId(u32);
+
+    #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+    struct Offset(i8);
+
+    impl_try_into_index_newtype!(UserId(u32), Offset(i8),);
+
+    #[test]
+    fn test_newtypes_from_macro() {
+        let user_ids: Vec<UserId> = vec![900, 12, 513, 12, 64].into_iter().map(UserId).collect();
+        let sorted_user_ids = user_ids.iter().cnt_sort().unwrap();
+        let expected_user_ids: Vec<UserId> =
+            vec![12, 12, 64, 513, 900].into_iter().map(UserId).collect();
+        assert_eq!(expected_user_ids, sorted_user_ids);
+
+        let offsets: Vec<Offset> = vec![127, -128, 0, -1].into_iter().map(Offset).collect();
+        let sorted_offsets = offsets.iter().cnt_sort().unwrap();
+        let expected_offsets: Vec<Offset> =
+            vec![-128, -1, 0, 127].into_iter().map(Offset).collect();
+        assert_eq!(expected_offsets, sorted_offsets);
+    }
+
+    // the macro must not depend on the names in scope at the call site
+    mod shadowed_result {
+        use counting_sort::{impl_try_into_index_newtype, CountingSort};
+
+        #[allow(dead_code)]
+        type Result<T> = std::result::Result<T, String>;
+
+        #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+        struct Level(u8);
+
+        impl_try_into_index_newtype!(Level(u8));
+
+        #[test]
+        fn test_newtype_from_macro_with_shadowed_result() {
+            let levels = [Level(3), Level(1), Level(2)];
+            assert_eq!(
+                vec![Level(1), Level(2), Level(3)],
+                levels.iter().cnt_sort().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_cnt_sort_in_place_stable_is_stable() {
+        let keys = [3, 1, 3, 0, 1, 3, 2, 0, 1, 3];
+        let tags = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
+        let mut slice: Vec<Tagged> = tags
+            .iter()
+            .zip(keys.iter())
+            .map(|(tag, key)| Tagged { tag, key: *key })
+            .collect();
+        cnt_sort_in_place_stable(&mut slice).unwrap();
+        let sorted_tags: Vec<&str> = slice.iter().map(|tagged| tagged.tag).collect();
+        assert_eq!(
+            vec!["d", "h", "b", "e", "i", "g", "a", "c", "f", "j"],
+            sorted_tags
+        );
+    }
+
+    #[test]
+    fn test_cnt_sort_in_place_keeps_all_tagged_elements() {
+        let keys = [3, 1, 3, 0, 1, 3, 2, 0, 1, 3];
+        let tags = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
+        let mut slice: Vec<Tagged> = tags
+            .iter()
+            .zip(keys.iter())
+            .map(|(tag, key)| Tagged { tag, key: *key })
+            .collect();
+        cnt_sort_in_place(&mut slice).unwrap();
+        let sorted_keys: Vec<usize> = slice.iter().map(|tagged| tagged.key).collect();
+        assert_eq!(vec![0, 0, 1, 1, 1, 2, 3, 3, 3, 3], sorted_keys);
+        // not stable, but no element is lost or duplicated
+        let mut sorted_tags: Vec<&str> = slice.iter().map(|tagged| tagged.tag).collect();
+        sorted_tags.sort_unstable();
+        assert_eq!(tags.to_vec(), sorted_tags);
+    }
+
+    #[test]
+    fn test_cnt_sort_tiebreak_by_timestamp() {
+        // events with equal keys in arbitrary input order, the tags are ISO 8601 timestamps
+        let events = [
+            ("2021-03-04T10:00:00", 2),
+            ("2021-03-01T08:30:00", 1),
+            ("2021-03-02T23:59:59", 2),
+            ("2021-03-01T08:29:59", 1),
+            ("2021-02-28T12:00:00", 2),
+        ];
+        let tagged_events: Vec<Tagged> = events
+            .iter()
+            .map(|(tag, key)| Tagged { tag, key: *key })
+            .collect();
+        let mut reversed_events = tagged_events.clone();
+        reversed_events.reverse();
+        for input in &[tagged_events, reversed_events] {
+            let sorted_events = input
+                .iter()
+                .cnt_sort_tiebreak_by(|tagged| tagged.tag)
+                .unwrap();
+            let timestamps: Vec<&str> = sorted_events.iter().map(|tagged| tagged.tag).collect();
+            assert_eq!(
+                vec![
+                    "2021-03-01T08:29:59",
+                    "2021-03-01T08:30:00",
+                    "2021-02-28T12:00:00",
+                    "2021-03-02T23:59:59",
+                    "2021-03-04T10:00:00",
+                ],
+                timestamps
+            );
+        }
+    }
 }
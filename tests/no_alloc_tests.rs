@@ -0,0 +1,50 @@
+// Checks that the allocation-free functions really do not touch the allocator. This needs its own
+// test binary, since the global allocator is replaced by one that counts the allocations.
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod no_alloc_tests {
+
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingAllocator;
+
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn test_cnt_sort_u8_inplace_does_not_allocate() {
+        let mut slice = [0_u8; 4096];
+        let mut state: u32 = 0x1234_5678;
+        for element in slice.iter_mut() {
+            // xorshift, which does not allocate either
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            *element = state.to_le_bytes()[0];
+        }
+
+        let allocations_before = ALLOCATIONS.load(Ordering::SeqCst);
+        let result = counting_sort::cnt_sort_u8_inplace(&mut slice);
+        let counts = counting_sort::cnt_sort_u8_counts(&slice);
+        let allocations_after = ALLOCATIONS.load(Ordering::SeqCst);
+
+        assert!(result.is_ok());
+        assert!(slice.windows(2).all(|window| window[0] <= window[1]));
+        assert_eq!(slice.len(), counts.iter().sum::<usize>());
+        assert_eq!(allocations_before, allocations_after);
+    }
+}